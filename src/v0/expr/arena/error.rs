@@ -0,0 +1,12 @@
+//! Error types for expression arenas.
+
+use thiserror::Error;
+
+/// Error looking up a [`NodeId`](super::NodeId) in an [`ExprArena`](super::ExprArena), e.g. while writing it.
+#[derive(Debug, Error, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ArenaDecomposeError {
+    /// The node is not a node of the arena, e.g. because it was created by another arena.
+    #[error("node {node} is not a node of the arena with {len} nodes")]
+    UnknownNode { node: usize, len: usize },
+}
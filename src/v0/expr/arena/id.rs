@@ -0,0 +1,19 @@
+/// Identifier of a node stored in an [`ExprArena`](super::ExprArena).
+///
+/// Identifiers are indices into the arena, so they are only meaningful for the arena, which created them.
+/// Operands of a node always have smaller identifiers than the node itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId {
+    index: usize,
+}
+
+impl NodeId {
+    pub(super) fn new(index: usize) -> Self {
+        Self { index }
+    }
+
+    /// Returns the index of the node in the arena.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
@@ -0,0 +1,45 @@
+//! Storage of expressions in a single contiguous vector.
+//!
+//! An [`ExprTree`](super::ExprTree) allocates every node separately, which scatters large expressions over the heap.
+//! An [`ExprArena`] stores all nodes as [`Expr<NodeId>`](super::Expr) in one vector and operands refer to other nodes by their [`NodeId`].
+//! Nodes are only ever added to the arena, every node after all of its operands, so processing nodes in the order of their identifiers
+//! processes operands before the operations using them, without any recursion.
+//!
+//! # Reading and writing
+//! [`ExprArena`] implements [`Composer`](super::traits::Composer), so expressions can be [read](crate::v0::read::read_expression)
+//! directly into the arena. Nodes are written by [`write_expression_from_arena`](crate::v0::write::write_expression_from_arena).
+//! A [`Decomposer`](super::traits::Decomposer) cannot be implemented for [`NodeId`], because it has to return references
+//! to expressions, which live as long as the reference to the storage, while the expression of a [`NodeId`] lives in the arena.
+//!
+//! # Examples
+//! ```rust
+//! # use fef::v0::expr::arena::ExprArena;
+//! # use fef::v0::config::DEFAULT_CONFIG;
+//! # use fef::v0::read::read_expression;
+//! # use fef::v0::write::write_expression_from_arena;
+//! # use fef::v0::text::{infix::parse_infix, VariableNames};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let tree = parse_infix("(x + y) * sqrt(x - y)", &mut VariableNames::new())?;
+//!
+//! let mut arena = ExprArena::new();
+//! let root = arena.add_tree(&tree);
+//! assert_eq!(arena.len(), 8);
+//!
+//! let mut bytes = Vec::new();
+//! write_expression_from_arena(&mut bytes, &arena, root, &DEFAULT_CONFIG)?;
+//!
+//! let mut read = ExprArena::new();
+//! let read_root = read_expression(&mut bytes.as_slice(), &DEFAULT_CONFIG, &mut read)?;
+//! assert_eq!(read, arena);
+//! assert_eq!(read.to_tree(read_root), tree);
+//! # Ok(())
+//! # }
+//! ```
+
+mod id;
+mod storage;
+
+pub mod error;
+
+pub use id::NodeId;
+pub use storage::ExprArena;
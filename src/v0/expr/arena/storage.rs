@@ -0,0 +1,156 @@
+use std::{convert::Infallible, ops::Index};
+
+use crate::v0::expr::{
+    error::ComposeError,
+    traits::{Composer, ExprObj},
+    Expr, ExprTree,
+};
+
+use super::NodeId;
+
+/// Storage of expressions, in which all nodes are stored in one vector.
+///
+/// For more information, see the [module documentation](super).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExprArena {
+    nodes: Vec<Expr<NodeId>>,
+}
+
+impl ExprArena {
+    /// Creates an empty arena.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty arena with space for at least `capacity` nodes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            nodes: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of nodes stored in the arena.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the arena stores no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the expression of the node, or `None` if the arena has no such node.
+    pub fn get(&self, node: NodeId) -> Option<&Expr<NodeId>> {
+        self.nodes.get(node.index())
+    }
+
+    /// Returns all nodes with their expressions, every node after its operands.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (NodeId, &Expr<NodeId>)> + '_ {
+        self.nodes
+            .iter()
+            .enumerate()
+            .map(|(index, expr)| (NodeId::new(index), expr))
+    }
+
+    /// Stores the expression as a new node and returns its identifier.
+    ///
+    /// # Panics
+    /// Panics, if an operand of the expression is not a node of this arena.
+    pub fn push(&mut self, expr: Expr<NodeId>) -> NodeId {
+        let id = NodeId::new(self.nodes.len());
+        for operand in expr.children() {
+            assert!(
+                *operand < id,
+                "operand {} is not a node of the arena with {} nodes",
+                operand.index(),
+                id.index()
+            );
+        }
+        self.nodes.push(expr);
+        id
+    }
+
+    /// Stores all expressions of the tree and returns the node of the whole expression.
+    ///
+    /// Every expression of the tree is stored as a new node, nodes are stored in post-order.
+    pub fn add_tree(&mut self, tree: &ExprTree) -> NodeId {
+        // Trees waiting to be stored, `true` if their operands are already stored.
+        let mut stack = vec![(tree, false)];
+        // Nodes of stored trees, whose parents are not stored yet.
+        let mut stored: Vec<NodeId> = Vec::new();
+        while let Some((tree, operands_stored)) = stack.pop() {
+            if operands_stored {
+                let mut operands = stored.split_off(stored.len() - tree.inner().arity());
+                let mut operands = operands.drain(..);
+                let expr = tree
+                    .inner()
+                    .map(|_| operands.next().expect("all operands are stored"));
+                stored.push(self.push(expr));
+            } else {
+                stack.push((tree, true));
+                stack.extend(
+                    tree.inner()
+                        .children()
+                        .rev()
+                        .map(|operand| (operand, false)),
+                );
+            }
+        }
+        stored.pop().expect("the tree is stored last")
+    }
+
+    /// Converts the node and its operands to an [`ExprTree`].
+    ///
+    /// A node used as an operand more than once is converted for every use.
+    ///
+    /// # Panics
+    /// Panics, if the node is not a node of this arena.
+    pub fn to_tree(&self, node: NodeId) -> ExprTree {
+        // Nodes waiting to be converted, `true` if their operands are already converted.
+        let mut stack = vec![(node, false)];
+        // Converted trees, whose parents are not converted yet.
+        let mut converted: Vec<ExprTree> = Vec::new();
+        while let Some((node, operands_converted)) = stack.pop() {
+            let expr = &self[node];
+            if operands_converted {
+                let mut operands = converted.split_off(converted.len() - expr.arity());
+                let mut operands = operands.drain(..);
+                let tree = expr.map(|_| operands.next().expect("all operands are converted"));
+                converted.push(ExprTree::from(tree));
+            } else {
+                stack.push((node, true));
+                stack.extend(expr.children().rev().map(|operand| (*operand, false)));
+            }
+        }
+        converted.pop().expect("the node is converted last")
+    }
+
+    /// Removes all nodes.
+    pub fn clear(&mut self) {
+        self.nodes.clear()
+    }
+}
+
+impl Index<NodeId> for ExprArena {
+    type Output = Expr<NodeId>;
+
+    /// Returns the expression of the node.
+    ///
+    /// # Panics
+    /// Panics, if the arena has no such node.
+    fn index(&self, node: NodeId) -> &Expr<NodeId> {
+        &self.nodes[node.index()]
+    }
+}
+
+/// Stores every read expression as a new node of the arena.
+impl Composer<NodeId> for ExprArena {
+    type Error = Infallible;
+
+    fn compose_default<E: ExprObj<NodeId>>(
+        &mut self,
+        expr: E,
+    ) -> Result<NodeId, ComposeError<Self::Error>> {
+        Ok(self.push(expr.into_expr()))
+    }
+}
@@ -0,0 +1,528 @@
+//! Building of [`ExprTree`]s using operators, constructor methods and the [`fef!`](crate::fef) macro.
+//!
+//! Implementations in this module are private, the API is documented on [`ExprTree`] and the macro.
+//! The `pub(crate)` helpers at the end create and inspect literals for the parsers of text formats and the transformations of trees.
+//! The helpers called by the expansion of the macro are public, but only reachable through the hidden `__private` module.
+
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+use crate::{
+    common::traits::private::Sealed,
+    v0::{
+        expr::{
+            traits::LiteralValue, Expr, ExprAddition, ExprBinaryFloat32Literal,
+            ExprBinaryFloat64Literal, ExprCube, ExprCubeRoot, ExprDivision, ExprFalseLiteral,
+            ExprIntDivision, ExprIntRoot, ExprModulo, ExprMultiplication, ExprNegation, ExprPower,
+            ExprReciprocal, ExprRoot, ExprSignedIntLiteral, ExprSquare, ExprSquareRoot,
+            ExprSubtraction, ExprTree, ExprTrueLiteral, ExprUnsignedIntLiteral, ExprVariable,
+        },
+        raw::VariableLengthEnum,
+    },
+};
+
+macro_rules! unary_method {
+    ($(#[$doc:meta])* $name:ident, $type:ident) => {
+        $(#[$doc])*
+        pub fn $name(self) -> ExprTree {
+            let expr: Expr<ExprTree> = $type::from(self).into();
+            ExprTree::from(expr)
+        }
+    };
+}
+
+macro_rules! binary_method {
+    ($(#[$doc:meta])* $name:ident, $rhs:ident, $type:ident) => {
+        $(#[$doc])*
+        pub fn $name<T: Into<ExprTree>>(self, $rhs: T) -> ExprTree {
+            let expr: Expr<ExprTree> = $type::from((self, $rhs.into())).into();
+            ExprTree::from(expr)
+        }
+    };
+}
+
+impl ExprTree {
+    /// Creates a variable expression with the given identifier.
+    pub fn var(id: impl Into<VariableLengthEnum>) -> ExprTree {
+        let expr: Expr<ExprTree> = ExprVariable::from(id.into()).into();
+        ExprTree::from(expr)
+    }
+
+    /// Creates a literal expression of the value.
+    ///
+    /// The type of the literal is chosen by the type of the value, see [`LiteralValue`].
+    pub fn lit(value: impl LiteralValue) -> ExprTree {
+        ExprTree::from(value.into_expr())
+    }
+
+    binary_method!(
+        /// Creates a [power](ExprPower) of this expression to the exponent.
+        pow,
+        exponent,
+        ExprPower
+    );
+    binary_method!(
+        /// Creates a [root](ExprRoot) of this expression of the given degree.
+        root,
+        degree,
+        ExprRoot
+    );
+    binary_method!(
+        /// Creates an [integer root](ExprIntRoot) of this expression of the given degree.
+        int_root,
+        degree,
+        ExprIntRoot
+    );
+    binary_method!(
+        /// Creates an [integer division](ExprIntDivision) of this expression by the divisor.
+        int_div,
+        divisor,
+        ExprIntDivision
+    );
+    unary_method!(
+        /// Creates a [square](ExprSquare) of this expression.
+        square,
+        ExprSquare
+    );
+    unary_method!(
+        /// Creates a [cube](ExprCube) of this expression.
+        cube,
+        ExprCube
+    );
+    unary_method!(
+        /// Creates a [square root](ExprSquareRoot) of this expression.
+        sqrt,
+        ExprSquareRoot
+    );
+    unary_method!(
+        /// Creates a [cube root](ExprCubeRoot) of this expression.
+        cbrt,
+        ExprCubeRoot
+    );
+    unary_method!(
+        /// Creates a [reciprocal](ExprReciprocal) of this expression.
+        recip,
+        ExprReciprocal
+    );
+}
+
+/// Creates a [negation](ExprNegation) of the expression.
+impl Neg for ExprTree {
+    type Output = ExprTree;
+
+    fn neg(self) -> ExprTree {
+        let expr: Expr<ExprTree> = ExprNegation::from(self).into();
+        ExprTree::from(expr)
+    }
+}
+
+macro_rules! impl_binary_operator {
+    ($trait:ident, $method:ident, $type:ident) => {
+        #[doc = concat!("Creates [`", stringify!($type), "`] of the expressions.")]
+        impl<T: Into<ExprTree>> $trait<T> for ExprTree {
+            type Output = ExprTree;
+
+            fn $method(self, rhs: T) -> ExprTree {
+                let expr: Expr<ExprTree> = $type::from((self, rhs.into())).into();
+                ExprTree::from(expr)
+            }
+        }
+    };
+}
+
+impl_binary_operator!(Add, add, ExprAddition);
+impl_binary_operator!(Sub, sub, ExprSubtraction);
+impl_binary_operator!(Mul, mul, ExprMultiplication);
+impl_binary_operator!(Div, div, ExprDivision);
+impl_binary_operator!(Rem, rem, ExprModulo);
+
+/// Implements [`LiteralValue`], conversion to [`ExprTree`] and operators with an [`ExprTree`] on the right-hand side.
+macro_rules! impl_literal_value {
+    ($value:ty, $literal:ident) => {
+        impl Sealed for $value {}
+
+        impl LiteralValue for $value {
+            fn into_expr<S: Sized>(self) -> Expr<S> {
+                $literal::from(self).into()
+            }
+        }
+
+        /// Creates a literal expression of the value.
+        impl From<$value> for ExprTree {
+            fn from(value: $value) -> Self {
+                ExprTree::lit(value)
+            }
+        }
+
+        impl_literal_operator!($value, Add, add);
+        impl_literal_operator!($value, Sub, sub);
+        impl_literal_operator!($value, Mul, mul);
+        impl_literal_operator!($value, Div, div);
+        impl_literal_operator!($value, Rem, rem);
+    };
+}
+
+macro_rules! impl_literal_operator {
+    ($value:ty, $trait:ident, $method:ident) => {
+        impl $trait<ExprTree> for $value {
+            type Output = ExprTree;
+
+            fn $method(self, rhs: ExprTree) -> ExprTree {
+                ExprTree::lit(self).$method(rhs)
+            }
+        }
+    };
+}
+
+impl_literal_value!(u8, ExprUnsignedIntLiteral);
+impl_literal_value!(u16, ExprUnsignedIntLiteral);
+impl_literal_value!(u32, ExprUnsignedIntLiteral);
+impl_literal_value!(u64, ExprUnsignedIntLiteral);
+impl_literal_value!(i8, ExprSignedIntLiteral);
+impl_literal_value!(i16, ExprSignedIntLiteral);
+impl_literal_value!(i32, ExprSignedIntLiteral);
+impl_literal_value!(i64, ExprSignedIntLiteral);
+impl_literal_value!(f32, ExprBinaryFloat32Literal);
+impl_literal_value!(f64, ExprBinaryFloat64Literal);
+
+impl Sealed for bool {}
+
+impl LiteralValue for bool {
+    fn into_expr<S: Sized>(self) -> Expr<S> {
+        if self {
+            ExprTrueLiteral::default().into()
+        } else {
+            ExprFalseLiteral::default().into()
+        }
+    }
+}
+
+/// Creates a literal expression of the value.
+impl From<bool> for ExprTree {
+    fn from(value: bool) -> Self {
+        ExprTree::lit(value)
+    }
+}
+
+/// Creates a tree of the expression.
+pub(crate) fn tree(expr: impl Into<Expr<ExprTree>>) -> ExprTree {
+    ExprTree::from(expr.into())
+}
+
+/// Picks [`ExprBinaryFloat32Literal`] if the value can be represented by it exactly, otherwise [`ExprBinaryFloat64Literal`].
+pub(crate) fn float_literal(value: f64) -> ExprTree {
+    let narrow = value as f32;
+    if (narrow as f64).to_bits() == value.to_bits() {
+        tree(ExprBinaryFloat32Literal::from(narrow))
+    } else {
+        tree(ExprBinaryFloat64Literal::from(value))
+    }
+}
+
+/// Negates a literal, if the expression is a number literal whose negation is representable by a literal.
+pub(crate) fn negate_literal(expr: &Expr<ExprTree>) -> Option<ExprTree> {
+    match expr {
+        Expr::UnsignedIntLiteral(literal) if literal.value <= i64::MAX as u64 + 1 => Some(tree(
+            ExprSignedIntLiteral::from((literal.value as i64).wrapping_neg()),
+        )),
+        Expr::BinaryFloat32Literal(literal) => {
+            Some(tree(ExprBinaryFloat32Literal::from(-literal.value)))
+        }
+        Expr::BinaryFloat64Literal(literal) => {
+            Some(tree(ExprBinaryFloat64Literal::from(-literal.value)))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the value of a number literal.
+pub(crate) fn literal_value(tree: &ExprTree) -> Option<f64> {
+    match tree.inner() {
+        Expr::UnsignedIntLiteral(literal) => Some(literal.value as f64),
+        Expr::SignedIntLiteral(literal) => Some(literal.value as f64),
+        Expr::BinaryFloat32Literal(literal) => Some(literal.value as f64),
+        Expr::BinaryFloat64Literal(literal) => Some(literal.value),
+        _ => None,
+    }
+}
+
+/// Returns `true` if the expression is a number literal of the value.
+pub(crate) fn is_value(tree: &ExprTree, value: f64) -> bool {
+    literal_value(tree) == Some(value)
+}
+
+/// Returns the identifier of a variable named in the [`fef!`](crate::fef) macro.
+///
+/// Evaluated in a constant, so that invalid names are reported at compile time.
+pub const fn variable_id(name: &str) -> usize {
+    let name = name.as_bytes();
+    if name.len() < 2 || name[0] != b'v' {
+        panic!("variables in `fef!` are named `v` followed by their identifier, like `v0`");
+    }
+    let mut id: usize = 0;
+    let mut index = 1;
+    while index < name.len() {
+        let digit = name[index];
+        if !digit.is_ascii_digit() {
+            panic!("variables in `fef!` are named `v` followed by their identifier, like `v0`");
+        }
+        id = match id.checked_mul(10) {
+            Some(id) => match id.checked_add((digit - b'0') as usize) {
+                Some(id) => id,
+                None => panic!("variable identifier in `fef!` is too large"),
+            },
+            None => panic!("variable identifier in `fef!` is too large"),
+        };
+        index += 1;
+    }
+    id
+}
+
+/// Creates the negated literal of a value written as `-value` in the [`fef!`](crate::fef) macro.
+///
+/// Signed integers and floats become a negative literal, unsigned integers and booleans a negation of the literal.
+pub fn negated_literal(value: impl LiteralValue) -> ExprTree {
+    let literal = ExprTree::lit(value);
+    match literal.inner() {
+        Expr::SignedIntLiteral(literal) => tree(ExprSignedIntLiteral::from(-literal.value)),
+        Expr::BinaryFloat32Literal(literal) => tree(ExprBinaryFloat32Literal::from(-literal.value)),
+        Expr::BinaryFloat64Literal(literal) => tree(ExprBinaryFloat64Literal::from(-literal.value)),
+        _ => -literal,
+    }
+}
+
+/// Builds an [`ExprTree`](crate::v0::expr::ExprTree) from an expression written in infix syntax.
+///
+/// The macro expands to the [operators and builder methods](crate::v0::expr::ExprTree#building) of [`ExprTree`](crate::v0::expr::ExprTree),
+/// so the expression is checked at compile time and no parsing happens at runtime.
+///
+/// # Syntax
+/// * Variables are named `v` followed by their identifier, e.g. `v0` or `v12`.
+/// * Literals are Rust literals, their type decides the type of the literal expression (see [`LiteralValue`](crate::v0::expr::traits::LiteralValue)).
+///   `3u8` is an unsigned integer literal, `-3i64` a signed one, `2.5f32` a 32-bit float and `true` a boolean literal. Unsuffixed integers are
+///   `i32` and so signed and unsuffixed floats are `f64`.
+/// * Binary operators `+`, `-`, `*`, `/` and `%` with the usual precedence and `^` for powers, which binds tighter and is right associative.
+/// * Unary `-` for negation, `-v0 ^ 2` is `-(v0 ^ 2)`. A negated signed integer or float literal is a single negative literal,
+///   e.g. in `v0 ^ -1`, unless it is the base of a power, so `-2 ^ 2` is `-(2 ^ 2)`. Unsigned integers and booleans cannot be negative,
+///   so `-3u8` is a negation of the literal. The smallest value of a signed type, like `-128i8`, doesn't fit the type without the sign,
+///   so it has to be written in braces, e.g. `{i8::MIN}`.
+/// * Functions `sqrt`, `cbrt`, `square`, `cube` and `recip` of one argument and `pow`, `root`, `int_root` and `int_div` of two arguments.
+/// * Parentheses for grouping.
+/// * Rust expressions in braces, e.g. `{tree}`, are converted to [`ExprTree`](crate::v0::expr::ExprTree) using [`From`].
+///
+/// The macro is recursive and every token is one level of recursion, so very long expressions may need a larger
+/// [`recursion_limit`](https://doc.rust-lang.org/reference/attributes/limits.html#the-recursion_limit-attribute).
+///
+/// # Examples
+/// ```rust
+/// # use fef::fef;
+/// # use fef::v0::expr::ExprTree;
+/// let hypotenuse = fef!(sqrt(v0^2 + v1^2));
+/// assert_eq!(hypotenuse, (ExprTree::var(0).pow(2) + ExprTree::var(1).pow(2)).sqrt());
+///
+/// let a = ExprTree::var(0);
+/// let quadratic = fef!((-v1 + sqrt(square(v1) - 4u8 * {a.clone()} * v2)) / (2u8 * {a}));
+///
+/// let literals = fef!(3u8 * v0 + 2.5f32 - v1 ^ -1);
+/// ```
+///
+/// Negated literals:
+/// ```rust
+/// # use fef::fef;
+/// # use fef::v0::expr::{Expr, ExprTree};
+/// assert!(matches!(fef!(-3i64).inner(), Expr::SignedIntLiteral(_)));
+/// assert_eq!(fef!(-3i64), ExprTree::lit(-3i64));
+/// assert_eq!(fef!(v1 ^ -1), ExprTree::var(1).pow(-1));
+///
+/// assert!(matches!(fef!(-3u8).inner(), Expr::Negation(_)));
+/// assert!(matches!(fef!(-2 ^ 2).inner(), Expr::Negation(_)));
+/// ```
+///
+/// A negated exponent extends over the rest of the chain of powers, like the negation of a base:
+/// ```rust
+/// # use fef::fef;
+/// # use fef::v0::expr::ExprTree;
+/// assert_eq!(fef!(v0 ^ -v1 ^ 2), ExprTree::var(0).pow(-ExprTree::var(1).pow(2)));
+/// assert_eq!(fef!(v0 ^ -2 ^ 2), ExprTree::var(0).pow(-ExprTree::lit(2).pow(2)));
+/// assert_eq!(fef!(v0 ^ -v1 ^ -1 * v2), ExprTree::var(0).pow(-ExprTree::var(1).pow(-1)) * ExprTree::var(2));
+/// ```
+///
+/// Syntax errors are reported at compile time:
+/// ```compile_fail
+/// # use fef::fef;
+/// let tree = fef!(v0 + * v1);
+/// ```
+/// ```compile_fail
+/// # use fef::fef;
+/// let tree = fef!(log(v0));
+/// ```
+/// ```compile_fail
+/// # use fef::fef;
+/// let tree = fef!(x + 1);
+/// ```
+#[macro_export]
+macro_rules! fef {
+    // Folds a chain of powers from the right, `a ^ b ^ c` is `a ^ (b ^ c)`.
+    (@fold [$base:tt]) => { $base };
+    (@fold [$base:tt $($exponents:tt)+]) => {
+        $base.pow($crate::fef!(@fold [$($exponents)+]))
+    };
+
+    // An operand is expected, `out` holds the translated tokens.
+    // A negated literal is folded into the literal, unless it is the base of a power.
+    (@operand [$($out:tt)*] - - $($rest:tt)*) => {
+        $crate::fef!(@operand [$($out)* -] - $($rest)*)
+    };
+    (@operand [$($out:tt)*] - $literal:literal ^ $($rest:tt)*) => {
+        $crate::fef!(@operand [$($out)* -] $literal ^ $($rest)*)
+    };
+    (@operand [$($out:tt)*] - $literal:literal $($rest:tt)*) => {
+        $crate::fef!(@after [$($out)*] [($crate::fef!(@negate $literal))] $($rest)*)
+    };
+    (@operand [$($out:tt)*] - $($rest:tt)*) => {
+        $crate::fef!(@operand [$($out)* -] $($rest)*)
+    };
+    (@operand [$($out:tt)*] $($rest:tt)*) => {
+        $crate::fef!(@atom [$($out)*] [] $($rest)*)
+    };
+
+    // Translates one operand and appends it to the current chain of powers.
+    (@atom [$($out:tt)*] [$($chain:tt)*] $function:ident ($($args:tt)*) $($rest:tt)*) => {
+        $crate::fef!(@after [$($out)*] [$($chain)* ($crate::fef!(@call $function ($($args)*)))] $($rest)*)
+    };
+    (@atom [$($out:tt)*] [$($chain:tt)*] $literal:literal $($rest:tt)*) => {
+        $crate::fef!(@after [$($out)*] [$($chain)* ($crate::v0::expr::ExprTree::lit($literal))] $($rest)*)
+    };
+    (@atom [$($out:tt)*] [$($chain:tt)*] $variable:ident $($rest:tt)*) => {
+        $crate::fef!(@after [$($out)*] [$($chain)* ($crate::fef!(@variable $variable))] $($rest)*)
+    };
+    (@atom [$($out:tt)*] [$($chain:tt)*] ($($inner:tt)+) $($rest:tt)*) => {
+        $crate::fef!(@after [$($out)*] [$($chain)* ($crate::fef!($($inner)+))] $($rest)*)
+    };
+    (@atom [$($out:tt)*] [$($chain:tt)*] {$value:expr} $($rest:tt)*) => {
+        $crate::fef!(@after [$($out)*] [$($chain)* ($crate::v0::expr::ExprTree::from($value))] $($rest)*)
+    };
+    (@atom [$($out:tt)*] [$($chain:tt)*]) => {
+        compile_error!("expected an operand at the end of `fef!`")
+    };
+    (@atom [$($out:tt)*] [$($chain:tt)*] $($rest:tt)+) => {
+        compile_error!(concat!(
+            "expected a variable, literal, function call or parenthesized expression in `fef!`, found `",
+            stringify!($($rest)+),
+            "`"
+        ))
+    };
+
+    // A negated exponent has been started by `^ -`, `negated` collects its tokens up to the next operator other than `^`
+    // and `minus` the leading minus signs of it.
+    // The exponent is translated on its own and negated, so `a ^ -b ^ c` is `a ^ -(b ^ c)`.
+    (@exponent [$($out:tt)*] [$($chain:tt)+] [$($minus:tt)*] [] - $($rest:tt)*) => {
+        $crate::fef!(@exponent [$($out)*] [$($chain)+] [$($minus)* -] [] $($rest)*)
+    };
+    (@exponent [$($out:tt)*] [$($chain:tt)+] [$($minus:tt)*] [$($negated:tt)*] ^ - $($rest:tt)*) => {
+        $crate::fef!(@exponent [$($out)*] [$($chain)+] [$($minus)*] [$($negated)* ^ -] $($rest)*)
+    };
+    (@exponent [$($out:tt)*] [$($chain:tt)+] [$($minus:tt)*] [$($negated:tt)+] + $($rest:tt)*) => {
+        $crate::fef!(@after [$($out)*] [$($chain)+ ($crate::fef!(@negate $($minus)* $($negated)+))] + $($rest)*)
+    };
+    (@exponent [$($out:tt)*] [$($chain:tt)+] [$($minus:tt)*] [$($negated:tt)+] - $($rest:tt)*) => {
+        $crate::fef!(@after [$($out)*] [$($chain)+ ($crate::fef!(@negate $($minus)* $($negated)+))] - $($rest)*)
+    };
+    (@exponent [$($out:tt)*] [$($chain:tt)+] [$($minus:tt)*] [$($negated:tt)+] * $($rest:tt)*) => {
+        $crate::fef!(@after [$($out)*] [$($chain)+ ($crate::fef!(@negate $($minus)* $($negated)+))] * $($rest)*)
+    };
+    (@exponent [$($out:tt)*] [$($chain:tt)+] [$($minus:tt)*] [$($negated:tt)+] / $($rest:tt)*) => {
+        $crate::fef!(@after [$($out)*] [$($chain)+ ($crate::fef!(@negate $($minus)* $($negated)+))] / $($rest)*)
+    };
+    (@exponent [$($out:tt)*] [$($chain:tt)+] [$($minus:tt)*] [$($negated:tt)+] % $($rest:tt)*) => {
+        $crate::fef!(@after [$($out)*] [$($chain)+ ($crate::fef!(@negate $($minus)* $($negated)+))] % $($rest)*)
+    };
+    (@exponent [$($out:tt)*] [$($chain:tt)+] [$($minus:tt)*] [$($negated:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::fef!(@exponent [$($out)*] [$($chain)+] [$($minus)*] [$($negated)* $next] $($rest)*)
+    };
+    (@exponent [$($out:tt)*] [$($chain:tt)+] [$($minus:tt)*] []) => {
+        compile_error!("expected an operand at the end of `fef!`")
+    };
+    (@exponent [$($out:tt)*] [$($chain:tt)+] [$($minus:tt)*] [$($negated:tt)+]) => {
+        $crate::fef!(@after [$($out)*] [$($chain)+ ($crate::fef!(@negate $($minus)* $($negated)+))])
+    };
+
+    // Negates an operand, a negated literal is folded into the literal.
+    // A `literal` fragment accepts a leading minus sign, so negated operands starting with one are matched first.
+    (@negate - $($operand:tt)+) => {
+        -$crate::fef!(- $($operand)+)
+    };
+    (@negate $literal:literal) => {
+        $crate::v0::expr::__private::negated_literal($literal)
+    };
+    (@negate $($operand:tt)+) => {
+        -$crate::fef!($($operand)+)
+    };
+
+    // An operand has been translated, an operator or the end is expected.
+    (@after [$($out:tt)*] [$($chain:tt)+] ^ - $($rest:tt)*) => {
+        $crate::fef!(@exponent [$($out)*] [$($chain)+] [] [] $($rest)*)
+    };
+    (@after [$($out:tt)*] [$($chain:tt)+] ^ $($rest:tt)*) => {
+        $crate::fef!(@atom [$($out)*] [$($chain)+] $($rest)*)
+    };
+    (@after [$($out:tt)*] [$($chain:tt)+] + $($rest:tt)*) => {
+        $crate::fef!(@operand [$($out)* $crate::fef!(@fold [$($chain)+]) +] $($rest)*)
+    };
+    (@after [$($out:tt)*] [$($chain:tt)+] - $($rest:tt)*) => {
+        $crate::fef!(@operand [$($out)* $crate::fef!(@fold [$($chain)+]) -] $($rest)*)
+    };
+    (@after [$($out:tt)*] [$($chain:tt)+] * $($rest:tt)*) => {
+        $crate::fef!(@operand [$($out)* $crate::fef!(@fold [$($chain)+]) *] $($rest)*)
+    };
+    (@after [$($out:tt)*] [$($chain:tt)+] / $($rest:tt)*) => {
+        $crate::fef!(@operand [$($out)* $crate::fef!(@fold [$($chain)+]) /] $($rest)*)
+    };
+    (@after [$($out:tt)*] [$($chain:tt)+] % $($rest:tt)*) => {
+        $crate::fef!(@operand [$($out)* $crate::fef!(@fold [$($chain)+]) %] $($rest)*)
+    };
+    (@after [$($out:tt)*] [$($chain:tt)+]) => {
+        $($out)* $crate::fef!(@fold [$($chain)+])
+    };
+    (@after [$($out:tt)*] [$($chain:tt)+] $($rest:tt)+) => {
+        compile_error!(concat!("expected an operator in `fef!`, found `", stringify!($($rest)+), "`"))
+    };
+
+    (@variable $variable:ident) => {
+        $crate::v0::expr::ExprTree::var({
+            const ID: usize = $crate::v0::expr::__private::variable_id(stringify!($variable));
+            ID
+        })
+    };
+
+    (@call sqrt ($($arg:tt)+)) => { $crate::fef!($($arg)+).sqrt() };
+    (@call cbrt ($($arg:tt)+)) => { $crate::fef!($($arg)+).cbrt() };
+    (@call square ($($arg:tt)+)) => { $crate::fef!($($arg)+).square() };
+    (@call cube ($($arg:tt)+)) => { $crate::fef!($($arg)+).cube() };
+    (@call recip ($($arg:tt)+)) => { $crate::fef!($($arg)+).recip() };
+    (@call pow ($($args:tt)+)) => { $crate::fef!(@call2 pow [] $($args)+) };
+    (@call root ($($args:tt)+)) => { $crate::fef!(@call2 root [] $($args)+) };
+    (@call int_root ($($args:tt)+)) => { $crate::fef!(@call2 int_root [] $($args)+) };
+    (@call int_div ($($args:tt)+)) => { $crate::fef!(@call2 int_div [] $($args)+) };
+    (@call $function:ident ($($args:tt)*)) => {
+        compile_error!(concat!("unknown function `", stringify!($function), "` in `fef!`"))
+    };
+
+    // Splits the arguments of a function of two arguments at the comma.
+    (@call2 $function:ident [$($lhs:tt)+] , $($rhs:tt)+) => {
+        $crate::fef!($($lhs)+).$function($crate::fef!($($rhs)+))
+    };
+    (@call2 $function:ident [$($lhs:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::fef!(@call2 $function [$($lhs)* $next] $($rest)*)
+    };
+    (@call2 $function:ident [$($lhs:tt)*]) => {
+        compile_error!(concat!("`", stringify!($function), "` in `fef!` takes two arguments separated by a comma"))
+    };
+
+    () => {
+        compile_error!("`fef!` expects an expression")
+    };
+    ($($tokens:tt)+) => {{
+        let tree: $crate::v0::expr::ExprTree = $crate::fef!(@operand [] $($tokens)+);
+        tree
+    }};
+}
@@ -0,0 +1,167 @@
+use crate::v0::expr::{
+    float_literal, traits::BinaryOperationExpr, tree as node, Expr, ExprAddition,
+    ExprBinaryFloat32Literal, ExprMultiplication, ExprTree, ExprUnsignedIntLiteral,
+};
+
+use super::order::compare;
+
+/// Rewrites an expression into its [canonical form](super).
+///
+/// For an example, see the [module documentation](super).
+///
+/// The expression is traversed without recursion, so deeply nested expressions, which can be read from untrusted input,
+/// are canonicalized without overflowing the stack. Neither [`compare`](super::compare) nor the traits of
+/// [`CanonicalTree`](super::CanonicalTree) recurse, so deep canonical forms can be deduplicated as well:
+/// ```rust
+/// # use std::collections::HashSet;
+/// # use fef::v0::read::read_expression_into_tree;
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # use fef::v0::expr::canonical::CanonicalTree;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut bytes = vec![0x17; 50_000]; // Negations
+/// bytes.extend([0x10; 50_000]); // Additions
+/// bytes.extend([0x04, 0x00]); // Variable 0
+/// for _ in 0..50_000 {
+///     bytes.extend([0x38, 0x01]); // Number 1
+/// }
+/// let tree = read_expression_into_tree(&mut &bytes[..], &DEFAULT_CONFIG)?;
+///
+/// let catalog: HashSet<CanonicalTree> = [&tree, &tree].into_iter().map(CanonicalTree::new).collect();
+/// assert_eq!(catalog.len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+pub fn canonicalize(tree: &ExprTree) -> ExprTree {
+    let mut steps = vec![Step::Enter(tree)];
+    let mut canonical: Vec<ExprTree> = Vec::new();
+    while let Some(step) = steps.pop() {
+        match step {
+            Step::Enter(tree) => match ExprKind::of(tree) {
+                Some(kind) => {
+                    let operands = collect(tree, kind);
+                    steps.push(Step::Chain {
+                        kind,
+                        operands: operands.len(),
+                    });
+                    steps.extend(operands.into_iter().rev().map(Step::Enter));
+                }
+                None => {
+                    steps.push(Step::Exit(tree));
+                    // Operands are pushed in reverse, so that lhs is canonicalized first.
+                    let operands_start = steps.len();
+                    steps.extend(tree.inner().children().map(Step::Enter));
+                    steps[operands_start..].reverse();
+                }
+            },
+            Step::Exit(tree) => {
+                let arity = tree.inner().arity();
+                let result = if arity == 0 {
+                    leaf(tree)
+                } else {
+                    let mut operands = canonical.split_off(canonical.len() - arity).into_iter();
+                    node(tree.inner().map(|_| {
+                        operands
+                            .next()
+                            .expect("every operand is canonicalized before its parent")
+                    }))
+                };
+                canonical.push(result);
+            }
+            Step::Chain { kind, operands } => {
+                let operands = canonical.split_off(canonical.len() - operands);
+                canonical.push(chain(operands, kind));
+            }
+        }
+    }
+    canonical.pop().expect("the root is canonicalized last")
+}
+
+/// Step of the canonicalization, kept on a heap stack instead of the call stack.
+enum Step<'a> {
+    /// Schedules canonicalization of the operands of the expression.
+    Enter(&'a ExprTree),
+    /// Rebuilds the expression from its canonical operands.
+    Exit(&'a ExprTree),
+    /// Rebuilds a chain of the operation from the given number of its canonical operands.
+    Chain { kind: ExprKind, operands: usize },
+}
+
+/// Associative and commutative operation, whose chains are flattened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExprKind {
+    Addition,
+    Multiplication,
+}
+
+impl ExprKind {
+    /// Returns the operation of the expression, if it is associative and commutative.
+    fn of(tree: &ExprTree) -> Option<Self> {
+        match tree.inner() {
+            Expr::Addition(_) => Some(ExprKind::Addition),
+            Expr::Multiplication(_) => Some(ExprKind::Multiplication),
+            _ => None,
+        }
+    }
+
+    /// Returns the operands of the expression, if it is this operation.
+    fn operands<'a>(&self, tree: &'a ExprTree) -> Option<(&'a ExprTree, &'a ExprTree)> {
+        match (self, tree.inner()) {
+            (ExprKind::Addition, Expr::Addition(expr)) => Some((expr.lhs(), expr.rhs())),
+            (ExprKind::Multiplication, Expr::Multiplication(expr)) => {
+                Some((expr.lhs(), expr.rhs()))
+            }
+            _ => None,
+        }
+    }
+
+    fn build(&self, lhs: ExprTree, rhs: ExprTree) -> ExprTree {
+        match self {
+            ExprKind::Addition => node(ExprAddition::from((lhs, rhs))),
+            ExprKind::Multiplication => node(ExprMultiplication::from((lhs, rhs))),
+        }
+    }
+}
+
+/// Rewrites the encoding of a literal, other leaves are already canonical.
+fn leaf(tree: &ExprTree) -> ExprTree {
+    match tree.inner() {
+        Expr::SignedIntLiteral(literal) => match u64::try_from(literal.value) {
+            Ok(value) => node(ExprUnsignedIntLiteral::from(value)),
+            Err(_) => tree.clone(),
+        },
+        Expr::BinaryFloat32Literal(literal) => float(literal.value as f64),
+        Expr::BinaryFloat64Literal(literal) => float(literal.value),
+        _ => tree.clone(),
+    }
+}
+
+/// Picks the narrowest float literal representing the value exactly, all NaNs are the same 32-bit NaN.
+fn float(value: f64) -> ExprTree {
+    if value.is_nan() {
+        return node(ExprBinaryFloat32Literal::from(f32::NAN));
+    }
+    float_literal(value)
+}
+
+/// Collects the operands of a chain of the operation from left to right.
+fn collect(tree: &ExprTree, kind: ExprKind) -> Vec<&ExprTree> {
+    let mut operands = Vec::new();
+    let mut pending = vec![tree];
+    while let Some(tree) = pending.pop() {
+        match kind.operands(tree) {
+            Some((lhs, rhs)) => pending.extend([rhs, lhs]),
+            None => operands.push(tree),
+        }
+    }
+    operands
+}
+
+/// Sorts the canonical operands of a chain of the operation and rebuilds it associated to the left.
+fn chain(mut operands: Vec<ExprTree>, kind: ExprKind) -> ExprTree {
+    operands.sort_by(compare);
+    let mut operands = operands.into_iter();
+    let first = operands
+        .next()
+        .expect("a chain of operations has at least two operands");
+    operands.fold(first, |lhs, rhs| kind.build(lhs, rhs))
+}
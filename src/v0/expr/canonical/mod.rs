@@ -0,0 +1,119 @@
+//! Canonical form of expressions for comparing formulas up to commutativity and associativity.
+//!
+//! The derived [`PartialEq`] of [`ExprTree`] compares expressions structurally, so `a + b` and `b + a` are different.
+//! [`canonicalize`] rewrites an expression into a canonical form, in which two expressions are equal exactly when they are equal
+//! modulo the [canonicalization rules](#canonicalization-rules).
+//!
+//! # Canonicalization rules
+//! * Chains of [`Addition`](crate::v0::expr::Expr::Addition) and of [`Multiplication`](crate::v0::expr::Expr::Multiplication) are flattened,
+//!   their operands sorted by the [order of expressions](#order-of-expressions) and the chain is rebuilt associated to the left,
+//!   so `c + (b + a)` becomes `(a + b) + c`.
+//! * Integer literals are stored as [unsigned](crate::v0::expr::ExprUnsignedIntLiteral) if they are not negative,
+//!   otherwise as [signed](crate::v0::expr::ExprSignedIntLiteral).
+//! * Float literals are stored as [32-bit floats](crate::v0::expr::ExprBinaryFloat32Literal) if they represent the value exactly,
+//!   otherwise as [64-bit floats](crate::v0::expr::ExprBinaryFloat64Literal). All NaNs are replaced by the same NaN.
+//!
+//! Integer and float literals are never equal, even if they have the same value, as they have a different [type](super::typing::ExprType).
+//! The signed zeros of floats are different as well. Note that floating-point addition and multiplication are not associative,
+//! so expressions with a canonical form in common may evaluate to slightly different floating-point values.
+//!
+//! # Order of expressions
+//! Expressions are ordered by their kind first: integer literals, float literals, `false`, `true`, variables and then operations in the order of
+//! the variants of [`Expr`](crate::v0::expr::Expr). Variables are ordered by their identifier, literals by their value, with float literals
+//! ordered by [`f64::total_cmp`], and operations by their operands from left to right.
+//!
+//! # Comparing canonical forms
+//! The NaN literals of a canonical form don't compare equal by [`PartialEq`], so comparing them by [`ExprTree`] may report different
+//! expressions with NaN as different. [`CanonicalTree`] wraps a canonical form and implements [`Eq`], [`Ord`] and [`Hash`]
+//! following the order of expressions, so it can be used to deduplicate formulas in sets and maps.
+//!
+//! # Examples
+//! ```rust
+//! # use std::collections::HashSet;
+//! # use fef::v0::expr::canonical::{canonicalize, CanonicalTree};
+//! # use fef::v0::text::{infix::{parse_infix, InfixPrinter, InfixStyle}, VariableNames};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut names = VariableNames::new();
+//! let first = parse_infix("a * (b + c) * 2", &mut names)?;
+//! let second = parse_infix("2 * ((c + b) * a)", &mut names)?;
+//! assert_ne!(first, second);
+//!
+//! let printer = InfixPrinter::new(InfixStyle::Ascii, Some(&names));
+//! assert_eq!(printer.print(&canonicalize(&first)), "2 * a * (b + c)");
+//! assert_eq!(canonicalize(&first), canonicalize(&second));
+//!
+//! let catalog: HashSet<CanonicalTree> = [first, second].iter().map(CanonicalTree::new).collect();
+//! assert_eq!(catalog.len(), 1);
+//! # Ok(())
+//! # }
+//! ```
+
+mod canonicalize;
+mod order;
+
+pub use canonicalize::canonicalize;
+pub use order::compare;
+
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+use crate::v0::expr::ExprTree;
+
+/// Expression in the [canonical form](self), compared by the [order of expressions](self#order-of-expressions).
+#[derive(Debug, Clone)]
+pub struct CanonicalTree {
+    tree: ExprTree,
+}
+
+impl CanonicalTree {
+    /// Creates the canonical form of an expression.
+    pub fn new(tree: &ExprTree) -> Self {
+        Self {
+            tree: canonicalize(tree),
+        }
+    }
+
+    /// Returns the expression in the canonical form.
+    pub fn tree(&self) -> &ExprTree {
+        &self.tree
+    }
+
+    /// Converts into the expression in the canonical form.
+    pub fn into_tree(self) -> ExprTree {
+        self.tree
+    }
+}
+
+impl From<ExprTree> for CanonicalTree {
+    fn from(tree: ExprTree) -> Self {
+        Self::new(&tree)
+    }
+}
+
+impl PartialEq for CanonicalTree {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for CanonicalTree {}
+
+impl PartialOrd for CanonicalTree {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CanonicalTree {
+    fn cmp(&self, other: &Self) -> Ordering {
+        compare(&self.tree, &other.tree)
+    }
+}
+
+impl Hash for CanonicalTree {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        order::hash(&self.tree, state);
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
+use crate::v0::{
+    expr::{Expr, ExprTree},
+    raw::VariableLengthEnum,
+};
+
+/// Value of a leaf expression, which decides the order of leaves of the same kind.
+#[derive(Clone, Copy)]
+enum Key<'a> {
+    Variable(&'a VariableLengthEnum),
+    Integer(i128),
+    Float(f64),
+    None,
+}
+
+/// Returns the position of the kind of an expression in the [order of expressions](super#order-of-expressions).
+fn rank(expr: &Expr<ExprTree>) -> u8 {
+    match expr {
+        Expr::SignedIntLiteral(_) | Expr::UnsignedIntLiteral(_) => 0,
+        Expr::BinaryFloat32Literal(_) | Expr::BinaryFloat64Literal(_) => 1,
+        Expr::FalseLiteral(_) => 2,
+        Expr::TrueLiteral(_) => 3,
+        Expr::Variable(_) => 4,
+        Expr::Addition(_) => 5,
+        Expr::Subtraction(_) => 6,
+        Expr::Multiplication(_) => 7,
+        Expr::Division(_) => 8,
+        Expr::IntDivision(_) => 9,
+        Expr::Modulo(_) => 10,
+        Expr::Power(_) => 11,
+        Expr::Negation(_) => 12,
+        Expr::Root(_) => 13,
+        Expr::IntRoot(_) => 14,
+        Expr::Square(_) => 15,
+        Expr::Cube(_) => 16,
+        Expr::SquareRoot(_) => 17,
+        Expr::CubeRoot(_) => 18,
+        Expr::Reciprocal(_) => 19,
+    }
+}
+
+fn key(expr: &Expr<ExprTree>) -> Key<'_> {
+    let float = |value: f64| Key::Float(if value.is_nan() { f64::NAN } else { value });
+    match expr {
+        Expr::Variable(variable) => Key::Variable(variable.as_ref()),
+        Expr::SignedIntLiteral(literal) => Key::Integer(literal.value as i128),
+        Expr::UnsignedIntLiteral(literal) => Key::Integer(literal.value as i128),
+        Expr::BinaryFloat32Literal(literal) => float(literal.value as f64),
+        Expr::BinaryFloat64Literal(literal) => float(literal.value),
+        _ => Key::None,
+    }
+}
+
+fn compare_keys(lhs: Key<'_>, rhs: Key<'_>) -> Ordering {
+    match (lhs, rhs) {
+        (Key::Variable(lhs), Key::Variable(rhs)) => lhs.cmp(rhs),
+        (Key::Integer(lhs), Key::Integer(rhs)) => lhs.cmp(&rhs),
+        (Key::Float(lhs), Key::Float(rhs)) => lhs.total_cmp(&rhs),
+        _ => Ordering::Equal,
+    }
+}
+
+/// Compares two expressions by the [order of expressions](super#order-of-expressions).
+///
+/// Literals are compared by their value, so integer literals of different encodings with the same value are equal,
+/// as are float literals of different widths and all NaNs. For [canonical forms](super), the order is equal exactly when the expressions are.
+pub fn compare(lhs: &ExprTree, rhs: &ExprTree) -> Ordering {
+    // Pairs of expressions are compared in pre-order without recursion, the next pair on top of the stack.
+    let mut pairs = vec![(lhs, rhs)];
+    while let Some((lhs, rhs)) = pairs.pop() {
+        let (lhs, rhs) = (lhs.inner(), rhs.inner());
+        let ordering = rank(lhs)
+            .cmp(&rank(rhs))
+            .then_with(|| compare_keys(key(lhs), key(rhs)));
+        if ordering.is_ne() {
+            return ordering;
+        }
+        // Operands are pushed in reverse, so that the lhs are compared first.
+        let operands_start = pairs.len();
+        pairs.extend(lhs.children().zip(rhs.children()));
+        pairs[operands_start..].reverse();
+    }
+    Ordering::Equal
+}
+
+/// Hashes an expression consistently with [`compare`].
+pub(super) fn hash<H: Hasher>(tree: &ExprTree, state: &mut H) {
+    // Expressions are hashed in pre-order without recursion, the next one on top of the stack.
+    let mut pending = vec![tree];
+    while let Some(tree) = pending.pop() {
+        let expr = tree.inner();
+        rank(expr).hash(state);
+        match key(expr) {
+            Key::Variable(variable) => variable.hash(state),
+            Key::Integer(value) => value.hash(state),
+            Key::Float(value) => value.to_bits().hash(state),
+            Key::None => {}
+        }
+        let operands_start = pending.len();
+        pending.extend(expr.children());
+        pending[operands_start..].reverse();
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use crate::v0::{
+    expr::{
+        dag::{ExprDag, ExprDagNode},
+        Expr, ExprTree, ExprVariable,
+    },
+    raw::VariableLengthEnum,
+};
+
+use super::{Definition, Elimination};
+
+/// Replaces common subexpressions of the expression by new variables.
+///
+/// A variable is introduced for every operation, which would otherwise be evaluated more than once. Operations occurring only
+/// inside of another repeated subexpression are evaluated once as a part of its definition, so they get no variable of their own.
+/// The identifiers of the new variables start right after the largest variable identifier used by the expression.
+///
+/// For an example, see the [module documentation](super).
+pub fn eliminate_common_subexpressions(tree: &ExprTree) -> Elimination {
+    let root = ExprDag::new().add_tree(tree);
+    let order = root.post_order();
+    // Number of operands of distinct nodes referring to a node. A node used more than once would be evaluated more than once.
+    let mut uses: HashMap<ExprDagNode, usize> = HashMap::new();
+    for node in &order {
+        for operand in node.inner().children() {
+            *uses.entry(operand.clone()).or_default() += 1;
+        }
+    }
+    let mut next_variable = first_free_variable(&order);
+    let mut trees: HashMap<ExprDagNode, ExprTree> = HashMap::new();
+    let mut definitions = Vec::new();
+    for node in order {
+        let tree = ExprTree::from(node.inner().map(|operand| {
+            // Operands used more than once are variables or leaves, so they are cheap to clone.
+            let tree = if uses[operand] > 1 {
+                trees.get(operand).cloned()
+            } else {
+                trees.remove(operand)
+            };
+            tree.expect("operands are visited before the node")
+        }));
+        let repeated = uses.get(&node).is_some_and(|uses| *uses > 1);
+        let tree = if repeated && node.inner().arity() > 0 {
+            let variable = VariableLengthEnum::from(next_variable);
+            next_variable += 1;
+            definitions.push(Definition {
+                variable: variable.clone(),
+                tree,
+            });
+            ExprTree::from(Expr::Variable(ExprVariable::from(variable)))
+        } else {
+            tree
+        };
+        trees.insert(node, tree);
+    }
+    Elimination {
+        tree: trees.remove(&root).expect("the root is visited last"),
+        definitions,
+    }
+}
+
+/// Returns the smallest identifier larger than the identifiers of all variables of the nodes.
+fn first_free_variable(nodes: &[ExprDagNode]) -> usize {
+    nodes
+        .iter()
+        .filter_map(|node| match node.inner() {
+            Expr::Variable(variable) => TryInto::<usize>::try_into(variable.as_ref().clone()).ok(),
+            _ => None,
+        })
+        .map(|identifier| identifier.saturating_add(1))
+        .max()
+        .unwrap_or(0)
+}
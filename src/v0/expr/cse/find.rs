@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+use crate::v0::expr::{
+    dag::{ExprDag, ExprDagNode},
+    ExprPath, ExprTree,
+};
+
+use super::CommonSubexpression;
+
+/// Finds all operations occurring more than once in the expression.
+///
+/// The subexpressions are ordered by their [savings](CommonSubexpression::savings), the largest first.
+/// Subexpressions with equal savings are ordered by their first occurrence in post-order.
+/// Subexpressions are equal if they are structurally equal, floats compared by their bits.
+///
+/// For an example, see the [module documentation](super).
+///
+/// The expression is traversed without recursion and only the paths of repeated subexpressions are recorded,
+/// so deeply nested expressions, which can be read from untrusted input, are searched without overflowing the stack:
+/// ```rust
+/// # use fef::v0::read::read_expression_into_tree;
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # use fef::v0::expr::cse::common_subexpressions;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut bytes = vec![0x17; 100_000]; // Negations
+/// bytes.extend([0x12, 0x10, 0x04, 0x00, 0x04, 0x01]); // Multiplication, Addition, Variable 0, Variable 1
+/// bytes.extend([0x10, 0x04, 0x00, 0x04, 0x01]); // Addition, Variable 0, Variable 1
+/// let tree = read_expression_into_tree(&mut &bytes[..], &DEFAULT_CONFIG)?;
+///
+/// let report = common_subexpressions(&tree);
+/// assert_eq!(report.len(), 1);
+/// assert_eq!(report[0].occurrences(), 2);
+/// assert_eq!(report[0].paths()[1].indices().len(), 100_001);
+/// # Ok(())
+/// # }
+/// ```
+pub fn common_subexpressions(tree: &ExprTree) -> Vec<CommonSubexpression> {
+    let root = ExprDag::new().add_tree(tree);
+    let order = root.post_order();
+    let occurrences = count_occurrences(&order);
+    let mut paths = repeated_paths(&root, &occurrences);
+    let mut report: Vec<CommonSubexpression> = order
+        .into_iter()
+        .filter_map(|node| {
+            let paths = paths.remove(&node)?;
+            Some(CommonSubexpression {
+                tree: node.to_tree(),
+                paths,
+                size: node.tree_size(),
+            })
+        })
+        .collect();
+    report.sort_by_key(|subexpression| std::cmp::Reverse(subexpression.savings()));
+    report
+}
+
+/// Counts the occurrences of the distinct nodes, given in post-order, in the expression as a tree.
+fn count_occurrences(order: &[ExprDagNode]) -> HashMap<ExprDagNode, usize> {
+    let mut occurrences: HashMap<ExprDagNode, usize> = HashMap::new();
+    // In reverse post-order, every node comes before its operands, so its count is final when it is added to them.
+    for (index, node) in order.iter().rev().enumerate() {
+        // The root is first and occurs once.
+        let count = if index == 0 { 1 } else { occurrences[node] };
+        for operand in node.inner().children() {
+            *occurrences.entry(operand.clone()).or_default() += count;
+        }
+    }
+    occurrences
+}
+
+/// Step of the search for repeated subexpressions, kept on a heap stack instead of the call stack.
+enum Step<'a> {
+    /// Records the path of the node if it is repeated and schedules its operands. `operand` is the index of the node in its parent.
+    Enter {
+        node: &'a ExprDagNode,
+        operand: Option<usize>,
+    },
+    /// Leaves the node.
+    Exit,
+}
+
+/// Records the paths of all occurrences of repeated operations, from left to right.
+fn repeated_paths(
+    root: &ExprDagNode,
+    occurrences: &HashMap<ExprDagNode, usize>,
+) -> HashMap<ExprDagNode, Vec<ExprPath>> {
+    let mut paths: HashMap<ExprDagNode, Vec<ExprPath>> = HashMap::new();
+    let mut path = Vec::new();
+    let mut steps = vec![Step::Enter {
+        node: root,
+        operand: None,
+    }];
+    while let Some(step) = steps.pop() {
+        match step {
+            Step::Enter { node, operand } => {
+                if let Some(operand) = operand {
+                    path.push(operand);
+                }
+                let repeated = occurrences.get(node).is_some_and(|count| *count > 1);
+                if repeated && node.inner().arity() > 0 {
+                    paths
+                        .entry(node.clone())
+                        .or_default()
+                        .push(ExprPath::from(path.clone()));
+                }
+                steps.push(Step::Exit);
+                // Operands are pushed in reverse, so that lhs is visited first.
+                let operands_start = steps.len();
+                for (operand, node) in node.inner().children().enumerate() {
+                    steps.push(Step::Enter {
+                        node,
+                        operand: Some(operand),
+                    });
+                }
+                steps[operands_start..].reverse();
+            }
+            Step::Exit => {
+                // The path of the root is empty.
+                path.pop();
+            }
+        }
+    }
+    paths
+}
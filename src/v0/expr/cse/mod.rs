@@ -0,0 +1,139 @@
+//! Detection and elimination of common subexpressions.
+//!
+//! Generated formulas often contain the same subexpression many times. [`common_subexpressions`] lists every operation,
+//! which occurs more than once in an expression, ranked by how many expressions would be saved by evaluating it only once.
+//! Literals and variables are never listed, as there is nothing to save by reusing them.
+//!
+//! FEF has no way of naming a subexpression inside an expression, so [`eliminate_common_subexpressions`] instead replaces
+//! repeated subexpressions by new variables. Each of these variables is [defined](Definition) by an expression, which may use
+//! the variables defined before it. Evaluating the definitions in order and binding their values evaluates every repeated
+//! subexpression only once.
+//!
+//! # Examples
+//! ```rust
+//! # use fef::v0::expr::cse::{common_subexpressions, eliminate_common_subexpressions};
+//! # use fef::v0::expr::eval::{evaluate_tree, FloatEvaluator, VariableBindings};
+//! # use fef::v0::text::{infix::{parse_infix, InfixPrinter, InfixStyle}, VariableNames};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut names = VariableNames::new();
+//! let tree = parse_infix("(x + y) * (x + y) + sqrt((x + y) * (x + y))", &mut names)?;
+//! let printer = InfixPrinter::new(InfixStyle::Ascii, Some(&names));
+//!
+//! let report = common_subexpressions(&tree);
+//! assert_eq!(printer.print(report[0].tree()), "x + y");
+//! assert_eq!(report[0].occurrences(), 4);
+//! assert_eq!(report[0].savings(), 9);
+//! assert_eq!(report[0].paths()[0].to_string(), "/0/0");
+//! assert_eq!(printer.print(report[1].tree()), "(x + y) * (x + y)");
+//!
+//! let elimination = eliminate_common_subexpressions(&tree);
+//! let definitions: Vec<_> = elimination
+//!     .definitions()
+//!     .iter()
+//!     .map(|definition| format!("x{} = {}", definition.variable(), printer.print(definition.tree())))
+//!     .collect();
+//! assert_eq!(definitions, ["x2 = x + y", "x3 = x2 * x2"]);
+//! assert_eq!(printer.print(elimination.tree()), "x3 + sqrt(x3)");
+//!
+//! let mut bindings: VariableBindings<f64> = [(0.into(), 1.0), (1.into(), 2.0)].into_iter().collect();
+//! for definition in elimination.definitions() {
+//!     let value = evaluate_tree(definition.tree(), &mut FloatEvaluator::new(&bindings))?;
+//!     bindings.bind(definition.variable().clone(), value);
+//! }
+//! assert_eq!(evaluate_tree(elimination.tree(), &mut FloatEvaluator::new(&bindings))?, 12.0);
+//! # Ok(())
+//! # }
+//! ```
+
+mod eliminate;
+mod find;
+
+pub use eliminate::eliminate_common_subexpressions;
+pub use find::common_subexpressions;
+
+use crate::v0::{
+    expr::{ExprPath, ExprTree},
+    raw::VariableLengthEnum,
+};
+
+/// Subexpression occurring more than once in an expression, as reported by [`common_subexpressions`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommonSubexpression {
+    tree: ExprTree,
+    paths: Vec<ExprPath>,
+    size: usize,
+}
+
+impl CommonSubexpression {
+    /// Returns the repeated subexpression.
+    pub fn tree(&self) -> &ExprTree {
+        &self.tree
+    }
+
+    /// Returns the paths of all occurrences of the subexpression, from left to right.
+    ///
+    /// Occurrences inside of other repeated subexpressions are included.
+    pub fn paths(&self) -> &[ExprPath] {
+        &self.paths
+    }
+
+    /// Returns the number of occurrences of the subexpression.
+    pub fn occurrences(&self) -> usize {
+        self.paths.len()
+    }
+
+    /// Returns the number of expressions of the subexpression.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of expressions, which are not evaluated, if the subexpression is evaluated only once.
+    pub fn savings(&self) -> usize {
+        (self.occurrences() - 1).saturating_mul(self.size)
+    }
+}
+
+/// Variable introduced by [`eliminate_common_subexpressions`] with the expression it stands for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Definition {
+    variable: VariableLengthEnum,
+    tree: ExprTree,
+}
+
+impl Definition {
+    /// Returns the introduced variable.
+    pub fn variable(&self) -> &VariableLengthEnum {
+        &self.variable
+    }
+
+    /// Returns the expression the variable stands for.
+    ///
+    /// It may use variables of the definitions before it.
+    pub fn tree(&self) -> &ExprTree {
+        &self.tree
+    }
+}
+
+/// Expression with its common subexpressions replaced by variables, as returned by [`eliminate_common_subexpressions`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Elimination {
+    tree: ExprTree,
+    definitions: Vec<Definition>,
+}
+
+impl Elimination {
+    /// Returns the expression using the introduced variables.
+    pub fn tree(&self) -> &ExprTree {
+        &self.tree
+    }
+
+    /// Returns the definitions of the introduced variables in the order they have to be evaluated.
+    pub fn definitions(&self) -> &[Definition] {
+        &self.definitions
+    }
+
+    /// Converts the elimination into the expression and the definitions.
+    pub fn into_parts(self) -> (ExprTree, Vec<Definition>) {
+        (self.tree, self.definitions)
+    }
+}
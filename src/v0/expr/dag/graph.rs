@@ -0,0 +1,136 @@
+use std::{collections::HashSet, convert::Infallible};
+
+use crate::v0::expr::{
+    error::{ComposeError, DecomposeError},
+    traits::{Composer, Decomposer, DecompositionRefContainer, ExprObj},
+    Expr, ExprTree,
+};
+
+use super::node::ExprDagNode;
+
+/// Storage of expressions, in which structurally identical expressions are stored only once.
+///
+/// For more information, see the [module documentation](super).
+#[derive(Debug, Clone, Default)]
+pub struct ExprDag {
+    nodes: HashSet<ExprDagNode>,
+}
+
+impl ExprDag {
+    /// Creates an empty DAG.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of distinct nodes stored in the DAG.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the DAG stores no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns `true` if the node is stored in the DAG.
+    pub fn contains(&self, node: &ExprDagNode) -> bool {
+        self.nodes
+            .get(node)
+            .is_some_and(|stored| stored.ptr_eq(node))
+    }
+
+    /// Returns the node of the expression, inserting it if the DAG doesn't store it yet.
+    ///
+    /// Operands are expected to be nodes of this DAG. Nodes of another DAG are shared as well, but the DAG
+    /// can then store the same expression more than once.
+    pub fn insert(&mut self, expr: Expr<ExprDagNode>) -> ExprDagNode {
+        let node = ExprDagNode::new(expr);
+        match self.nodes.get(&node) {
+            Some(stored) => stored.clone(),
+            None => {
+                self.nodes.insert(node.clone());
+                node
+            }
+        }
+    }
+
+    /// Inserts all expressions of the tree and returns the node of the whole expression.
+    ///
+    /// The tree is traversed without recursion, so it works for arbitrarily deep trees.
+    pub fn add_tree(&mut self, tree: &ExprTree) -> ExprDagNode {
+        // Trees waiting to be inserted, `true` if their operands are already inserted.
+        let mut stack = vec![(tree, false)];
+        // Nodes of inserted trees, whose parents are not inserted yet.
+        let mut inserted: Vec<ExprDagNode> = Vec::new();
+        while let Some((tree, operands_inserted)) = stack.pop() {
+            if operands_inserted {
+                let mut operands = inserted.split_off(inserted.len() - tree.inner().arity());
+                let mut operands = operands.drain(..);
+                let expr = tree
+                    .inner()
+                    .map(|_| operands.next().expect("all operands are inserted"));
+                inserted.push(self.insert(expr));
+            } else {
+                stack.push((tree, true));
+                stack.extend(
+                    tree.inner()
+                        .children()
+                        .rev()
+                        .map(|operand| (operand, false)),
+                );
+            }
+        }
+        inserted.pop().expect("the tree is inserted last")
+    }
+
+    /// Removes all nodes that are not used outside of the DAG, neither directly nor as operands of used nodes.
+    ///
+    /// Returns the number of removed nodes.
+    pub fn remove_unused(&mut self) -> usize {
+        let len = self.nodes.len();
+        loop {
+            let before = self.nodes.len();
+            self.nodes.retain(|node| !node.is_unique());
+            if self.nodes.len() == before {
+                break;
+            }
+        }
+        len - self.nodes.len()
+    }
+}
+
+/// Inserts every read expression into the DAG, so that repeated subexpressions are stored only once.
+impl Composer<ExprDagNode> for ExprDag {
+    type Error = Infallible;
+
+    fn compose_default<E: ExprObj<ExprDagNode>>(
+        &mut self,
+        expr: E,
+    ) -> Result<ExprDagNode, ComposeError<Self::Error>> {
+        Ok(self.insert(expr.into_expr()))
+    }
+}
+
+/// Decomposer of [`ExprDagNode`]s, used to write them by [`write_expression`](crate::v0::write::write_expression).
+///
+/// The FEF format has no way of sharing expressions, so every repeated node is written again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ExprDagDecomposer {}
+
+impl ExprDagDecomposer {
+    /// Creates a decomposer of [`ExprDagNode`]s.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Decomposer<ExprDagNode> for ExprDagDecomposer {
+    type Error = Infallible;
+
+    fn decompose_as_ref<'a>(
+        &mut self,
+        storage_ref: &'a ExprDagNode,
+    ) -> Result<impl DecompositionRefContainer<'a, ExprDagNode>, DecomposeError<Self::Error>> {
+        Ok(storage_ref.inner())
+    }
+}
@@ -0,0 +1,53 @@
+//! Storage of expressions as a directed acyclic graph with shared subexpressions.
+//!
+//! Generated formulas often repeat the same subexpressions many times. An [`ExprTree`](super::ExprTree) stores every repetition
+//! separately, while an [`ExprDag`] stores every distinct expression only once (hash consing): inserting an expression, which is already stored,
+//! returns the stored [`ExprDagNode`] instead. Nodes are reference counted and refer to their operands, so they can be used as the storage type
+//! of [`Expr`](super::Expr) like an [`ExprTree`](super::ExprTree).
+//!
+//! # Reading and writing
+//! [`ExprDag`] implements [`Composer`](super::traits::Composer), so expressions can be [read](crate::v0::read::read_expression)
+//! directly into the DAG, without ever allocating the repeated subexpressions. Nodes are written by
+//! [`write_expression`](crate::v0::write::write_expression) using an [`ExprDagDecomposer`].
+//!
+//! # Traversal
+//! [`ExprDagNode::fold`] and [`ExprDagNode::compose`] visit every distinct node only once and reuse its value for all of its repetitions.
+//! Functions working with [`Decomposer`](super::traits::Decomposer)s, such as [`evaluate`](super::eval::evaluate), work with nodes as well,
+//! but they visit every repetition again.
+//!
+//! # Examples
+//! ```rust
+//! # use fef::v0::expr::dag::{ExprDag, ExprDagDecomposer};
+//! # use fef::v0::expr::eval::{FloatEvaluator, VariableBindings};
+//! # use fef::v0::config::DEFAULT_CONFIG;
+//! # use fef::v0::read::{read_expression, EvaluatingComposer};
+//! # use fef::v0::write::write_expression;
+//! # use fef::v0::text::{infix::parse_infix, VariableNames};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let tree = parse_infix("(x + y) * (x + y) + sqrt((x + y) * (x + y))", &mut VariableNames::new())?;
+//!
+//! let mut dag = ExprDag::new();
+//! let root = dag.add_tree(&tree);
+//! assert_eq!(root.tree_size(), 16);
+//! assert_eq!(dag.len(), 6);
+//!
+//! let mut bytes = Vec::new();
+//! write_expression(&mut bytes, &root, &DEFAULT_CONFIG, &mut ExprDagDecomposer::new())?;
+//!
+//! let mut read = ExprDag::new();
+//! let read_root = read_expression(&mut bytes.as_slice(), &DEFAULT_CONFIG, &mut read)?;
+//! assert_eq!(read.len(), 6);
+//! assert_eq!(read_root.to_tree(), tree);
+//!
+//! let bindings: VariableBindings<f64> = [(0.into(), 1.0), (1.into(), 2.0)].into_iter().collect();
+//! let value = read_root.compose(&mut EvaluatingComposer::new(FloatEvaluator::new(&bindings)))?;
+//! assert_eq!(value, 12.0);
+//! # Ok(())
+//! # }
+//! ```
+
+mod graph;
+mod node;
+
+pub use graph::{ExprDag, ExprDagDecomposer};
+pub use node::ExprDagNode;
@@ -0,0 +1,265 @@
+use std::{
+    collections::{HashMap, HashSet},
+    convert::Infallible,
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
+
+use crate::v0::expr::{
+    error::ComposeError,
+    traits::{Composer, ExprObj, ExprStorage},
+    Expr, ExprTree, ExprTrueLiteral,
+};
+
+/// Shared expression stored in an [`ExprDag`](super::ExprDag).
+///
+/// Cloning a node is cheap, as it only increments a reference count. Dropping the last reference to a node does not recurse,
+/// so even very deep expressions can be dropped without overflowing the stack.
+///
+/// # Equality
+/// Two nodes are equal if they have the same operation, the same value and the same operand nodes (not just equal operands).
+/// Floats are compared by their bits, so every node is equal to itself. For nodes of the same [`ExprDag`](super::ExprDag),
+/// this means that two nodes are equal exactly if they are the same node.
+#[derive(Debug, Clone)]
+pub struct ExprDagNode {
+    inner: Rc<Expr<ExprDagNode>>,
+}
+
+impl ExprDagNode {
+    pub(super) fn new(expr: Expr<ExprDagNode>) -> Self {
+        Self {
+            inner: Rc::new(expr),
+        }
+    }
+
+    /// Returns the expression of the node.
+    pub fn inner(&self) -> &Expr<ExprDagNode> {
+        &self.inner
+    }
+
+    /// Returns `true` if both nodes are the same node.
+    pub fn ptr_eq(&self, other: &ExprDagNode) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+
+    /// Returns `true` if no other node or [`ExprDag`](super::ExprDag) refers to this node.
+    pub(super) fn is_unique(&self) -> bool {
+        Rc::strong_count(&self.inner) == 1
+    }
+
+    fn key(&self) -> *const Expr<ExprDagNode> {
+        Rc::as_ptr(&self.inner)
+    }
+
+    /// Returns all distinct nodes of the expression, every node after its operands, this node last.
+    ///
+    /// Lhs is visited before rhs, so the order is the same as in a post-order traversal of the expression tree
+    /// with repeated nodes left out.
+    pub fn post_order(&self) -> Vec<ExprDagNode> {
+        let mut visited = HashSet::new();
+        let mut order = Vec::new();
+        let mut stack = vec![(self.clone(), false)];
+        while let Some((node, expanded)) = stack.pop() {
+            if expanded {
+                order.push(node);
+                continue;
+            }
+            if !visited.insert(node.key()) {
+                continue;
+            }
+            stack.push((node.clone(), true));
+            stack.extend(
+                node.inner()
+                    .children()
+                    .rev()
+                    .filter(|operand| !visited.contains(&operand.key()))
+                    .map(|operand| (operand.clone(), false)),
+            );
+        }
+        order
+    }
+
+    /// Returns the number of distinct nodes of the expression.
+    pub fn node_count(&self) -> usize {
+        self.post_order().len()
+    }
+
+    /// Returns the number of expressions of the expression as a tree, i.e. counting every repetition of a node.
+    ///
+    /// The size saturates at [`usize::MAX`], as it may grow exponentially with the number of nodes.
+    pub fn tree_size(&self) -> usize {
+        self.fold(|expr: Expr<usize>| {
+            expr.children()
+                .fold(1usize, |size, operand| size.saturating_add(*operand))
+        })
+    }
+
+    /// Computes a value of the expression bottom-up, calling `f` exactly once for every distinct node.
+    ///
+    /// `f` gets the expression of the node with the operands replaced by their already computed values.
+    /// The traversal doesn't recurse, so it works for arbitrarily deep expressions.
+    ///
+    /// # Examples
+    /// Counting the variables of an expression, repeated ones included:
+    /// ```rust
+    /// # use fef::v0::expr::{Expr, dag::ExprDag, traits::BinaryOperationExpr};
+    /// # use fef::v0::text::{infix::parse_infix, VariableNames};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let tree = parse_infix("(x + y) * (x + y)", &mut VariableNames::new())?;
+    /// let root = ExprDag::new().add_tree(&tree);
+    ///
+    /// let variables = root.fold(|expr: Expr<u64>| match expr {
+    ///     Expr::Variable(_) => 1,
+    ///     Expr::Addition(addition) => addition.lhs() + addition.rhs(),
+    ///     Expr::Multiplication(multiplication) => multiplication.lhs() + multiplication.rhs(),
+    ///     _ => 0,
+    /// });
+    /// assert_eq!(variables, 4);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fold<T: Clone>(&self, mut f: impl FnMut(Expr<T>) -> T) -> T {
+        match self.try_fold(|expr| Ok::<T, Infallible>(f(expr))) {
+            Ok(value) => value,
+            Err(error) => match error {},
+        }
+    }
+
+    /// Computes a value of the expression bottom-up like [`fold`](Self::fold), stopping at the first error.
+    pub fn try_fold<T: Clone, E>(
+        &self,
+        mut f: impl FnMut(Expr<T>) -> Result<T, E>,
+    ) -> Result<T, E> {
+        let order = self.post_order();
+        // Values are moved out instead of cloned, when they are used for the last time.
+        let mut uses: HashMap<*const Expr<ExprDagNode>, usize> = HashMap::new();
+        for node in &order {
+            for operand in node.inner().children() {
+                *uses.entry(operand.key()).or_default() += 1;
+            }
+        }
+        let mut values: HashMap<*const Expr<ExprDagNode>, T> = HashMap::new();
+        for node in order {
+            let expr = node.inner().map(|operand| {
+                let uses = uses.get_mut(&operand.key()).expect("operands are counted");
+                *uses -= 1;
+                let value = if *uses == 0 {
+                    values.remove(&operand.key())
+                } else {
+                    values.get(&operand.key()).cloned()
+                };
+                value.expect("operands are visited before the node")
+            });
+            let value = f(expr)?;
+            values.insert(node.key(), value);
+        }
+        Ok(values
+            .remove(&self.key())
+            .expect("the node itself is visited last"))
+    }
+
+    /// Composes the expression using a [`Composer`], composing every distinct node only once.
+    ///
+    /// This can be used, for example, with an [`EvaluatingComposer`](crate::v0::read::EvaluatingComposer) to evaluate
+    /// an expression with many repeated subexpressions efficiently.
+    pub fn compose<S: Clone, CP: ?Sized + Composer<S>>(
+        &self,
+        composer: &mut CP,
+    ) -> Result<S, ComposeError<CP::Error>> {
+        self.try_fold(|expr| compose_expr(composer, expr))
+    }
+
+    /// Converts the expression into an [`ExprTree`], copying every repeated node.
+    pub fn to_tree(&self) -> ExprTree {
+        self.fold(ExprTree::from)
+    }
+}
+
+/// Operands of a node dropped for the last time are moved to a heap stack, so the compiler generated drop glue
+/// only ever drops operands, which are still referenced by the stack.
+impl Drop for ExprDagNode {
+    fn drop(&mut self) {
+        let mut stack = Vec::new();
+        take_operands(self, &mut stack);
+        while let Some(mut node) = stack.pop() {
+            take_operands(&mut node, &mut stack);
+        }
+    }
+}
+
+/// Moves the operands of the node to the stack, if this is the last reference to the node.
+///
+/// The expression of the node is replaced by a leaf, so dropping the node afterwards does nothing more.
+fn take_operands(node: &mut ExprDagNode, stack: &mut Vec<ExprDagNode>) {
+    if let Some(expr) = Rc::get_mut(&mut node.inner) {
+        if expr.arity() > 0 {
+            let expr = std::mem::replace(expr, Expr::TrueLiteral(ExprTrueLiteral::default()));
+            stack.extend(expr.children().cloned());
+        }
+    }
+}
+
+impl ExprStorage for ExprDagNode {
+    fn expr(&self) -> &Expr<ExprDagNode> {
+        &self.inner
+    }
+}
+
+impl PartialEq for ExprDagNode {
+    fn eq(&self, other: &Self) -> bool {
+        if self.ptr_eq(other) {
+            return true;
+        }
+        let (lhs, rhs) = (self.inner(), other.inner());
+        ExprObj::<ExprDagNode>::token(lhs) == ExprObj::<ExprDagNode>::token(rhs)
+            && lhs.leaf() == rhs.leaf()
+            && lhs
+                .children()
+                .zip(rhs.children())
+                .all(|(lhs, rhs)| lhs.ptr_eq(rhs))
+    }
+}
+
+impl Eq for ExprDagNode {}
+
+impl Hash for ExprDagNode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let expr = self.inner();
+        ExprObj::<ExprDagNode>::token(expr).hash(state);
+        expr.leaf().hash(state);
+        for operand in expr.children() {
+            operand.key().hash(state);
+        }
+    }
+}
+
+/// Calls the compose method of the composer for the type of the expression.
+fn compose_expr<S: Sized, CP: ?Sized + Composer<S>>(
+    composer: &mut CP,
+    expr: Expr<S>,
+) -> Result<S, ComposeError<CP::Error>> {
+    match expr {
+        Expr::Variable(expr) => composer.compose_variable(expr),
+        Expr::SignedIntLiteral(expr) => composer.compose_signed_int_literal(expr),
+        Expr::UnsignedIntLiteral(expr) => composer.compose_unsigned_int_literal(expr),
+        Expr::BinaryFloat32Literal(expr) => composer.compose_binary_float_32_literal(expr),
+        Expr::BinaryFloat64Literal(expr) => composer.compose_binary_float_64_literal(expr),
+        Expr::TrueLiteral(expr) => composer.compose_true_literal(expr),
+        Expr::FalseLiteral(expr) => composer.compose_false_literal(expr),
+        Expr::Addition(expr) => composer.compose_addition(expr),
+        Expr::Subtraction(expr) => composer.compose_subtraction(expr),
+        Expr::Multiplication(expr) => composer.compose_multiplication(expr),
+        Expr::Division(expr) => composer.compose_division(expr),
+        Expr::IntDivision(expr) => composer.compose_int_division(expr),
+        Expr::Modulo(expr) => composer.compose_modulo(expr),
+        Expr::Power(expr) => composer.compose_power(expr),
+        Expr::Negation(expr) => composer.compose_negation(expr),
+        Expr::Root(expr) => composer.compose_root(expr),
+        Expr::IntRoot(expr) => composer.compose_int_root(expr),
+        Expr::Square(expr) => composer.compose_square(expr),
+        Expr::Cube(expr) => composer.compose_cube(expr),
+        Expr::SquareRoot(expr) => composer.compose_square_root(expr),
+        Expr::CubeRoot(expr) => composer.compose_cube_root(expr),
+        Expr::Reciprocal(expr) => composer.compose_reciprocal(expr),
+    }
+}
@@ -0,0 +1,332 @@
+use crate::v0::{
+    expr::{
+        is_value,
+        traits::{BinaryOperationExpr, UnaryOperationExpr},
+        tree, Expr, ExprAddition, ExprBinaryFloat32Literal, ExprBinaryFloat64Literal, ExprCubeRoot,
+        ExprDivision, ExprMultiplication, ExprNegation, ExprPath, ExprPower, ExprSignedIntLiteral,
+        ExprSquare, ExprSquareRoot, ExprSubtraction, ExprTree, ExprUnsignedIntLiteral,
+    },
+    raw::VariableLengthEnum,
+    tokens::ExprToken,
+};
+
+use super::error::DifferentiationError;
+
+/// Differentiates an [`ExprTree`] with respect to `variable`.
+///
+/// Returns the derivative built by the [differentiation rules](super#differentiation-rules),
+/// or the first expression, in the order they are stored in a file, which can't be differentiated.
+///
+/// For an example, see the [module documentation](super).
+///
+/// The expression is traversed without recursion, so deeply nested expressions, which can be read from untrusted input,
+/// are differentiated without overflowing the stack:
+/// ```rust
+/// # use fef::v0::read::read_expression_into_tree;
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # use fef::v0::expr::{derivative::differentiate, eval::{evaluate_tree, FloatEvaluator, VariableBindings}};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut bytes = vec![0x20]; // Square
+/// bytes.extend([0x17; 100_000]); // Negations
+/// bytes.extend([0x04, 0x00]); // Variable 0
+/// let tree = read_expression_into_tree(&mut &bytes[..], &DEFAULT_CONFIG)?;
+///
+/// let derivative = differentiate(&tree, &0.into())?;
+/// let bindings: VariableBindings<f64> = [(0.into(), 3.0)].into_iter().collect();
+/// assert_eq!(evaluate_tree(&derivative, &mut FloatEvaluator::new(&bindings))?, 6.0);
+/// # Ok(())
+/// # }
+/// ```
+pub fn differentiate(
+    tree: &ExprTree,
+    variable: &VariableLengthEnum,
+) -> Result<ExprTree, DifferentiationError> {
+    Differentiator {
+        variable,
+        path: Vec::new(),
+    }
+    .derive(tree)
+}
+
+fn integer(value: u64) -> ExprTree {
+    tree(ExprUnsignedIntLiteral::from(value))
+}
+
+fn add(lhs: ExprTree, rhs: ExprTree) -> ExprTree {
+    if is_value(&lhs, 0.0) {
+        rhs
+    } else if is_value(&rhs, 0.0) {
+        lhs
+    } else {
+        tree(ExprAddition::from((lhs, rhs)))
+    }
+}
+
+fn sub(lhs: ExprTree, rhs: ExprTree) -> ExprTree {
+    if is_value(&rhs, 0.0) {
+        lhs
+    } else if is_value(&lhs, 0.0) {
+        neg(rhs)
+    } else {
+        tree(ExprSubtraction::from((lhs, rhs)))
+    }
+}
+
+fn mul(lhs: ExprTree, rhs: ExprTree) -> ExprTree {
+    if is_value(&lhs, 0.0) || is_value(&rhs, 0.0) {
+        integer(0)
+    } else if is_value(&lhs, 1.0) {
+        rhs
+    } else if is_value(&rhs, 1.0) {
+        lhs
+    } else {
+        tree(ExprMultiplication::from((lhs, rhs)))
+    }
+}
+
+fn div(lhs: ExprTree, rhs: ExprTree) -> ExprTree {
+    if is_value(&lhs, 0.0) {
+        integer(0)
+    } else if is_value(&rhs, 1.0) {
+        lhs
+    } else {
+        tree(ExprDivision::from((lhs, rhs)))
+    }
+}
+
+/// Returns `-inner`, negating number literals in place.
+fn neg(inner: ExprTree) -> ExprTree {
+    match inner.inner() {
+        _ if is_value(&inner, 0.0) => integer(0),
+        Expr::UnsignedIntLiteral(literal) if literal.value <= i64::MAX as u64 => {
+            tree(ExprSignedIntLiteral::from(-(literal.value as i64)))
+        }
+        Expr::BinaryFloat32Literal(literal) => tree(ExprBinaryFloat32Literal::from(-literal.value)),
+        Expr::BinaryFloat64Literal(literal) => tree(ExprBinaryFloat64Literal::from(-literal.value)),
+        _ => tree(ExprNegation::from(inner)),
+    }
+}
+
+fn square(inner: ExprTree) -> ExprTree {
+    tree(ExprSquare::from(inner))
+}
+
+/// Returns `exponent - 1`, computed in place if the exponent is a literal.
+fn decrement(exponent: ExprTree) -> ExprTree {
+    match exponent.inner() {
+        Expr::UnsignedIntLiteral(literal) if literal.value > 0 => integer(literal.value - 1),
+        Expr::UnsignedIntLiteral(_) => tree(ExprSignedIntLiteral::from(-1i64)),
+        Expr::SignedIntLiteral(literal) if literal.value > i64::MIN => {
+            let value = literal.value - 1;
+            match u64::try_from(value) {
+                Ok(value) => integer(value),
+                Err(_) => tree(ExprSignedIntLiteral::from(value)),
+            }
+        }
+        Expr::BinaryFloat32Literal(literal) => {
+            tree(ExprBinaryFloat32Literal::from(literal.value - 1.0))
+        }
+        Expr::BinaryFloat64Literal(literal) => {
+            tree(ExprBinaryFloat64Literal::from(literal.value - 1.0))
+        }
+        _ => sub(exponent, integer(1)),
+    }
+}
+
+/// Returns `base^exponent`, leaving out exponents of zero and one.
+fn pow(base: ExprTree, exponent: ExprTree) -> ExprTree {
+    if is_value(&exponent, 0.0) {
+        integer(1)
+    } else if is_value(&exponent, 1.0) {
+        base
+    } else {
+        tree(ExprPower::from((base, exponent)))
+    }
+}
+
+struct Differentiator<'a> {
+    variable: &'a VariableLengthEnum,
+    path: Vec<usize>,
+}
+
+/// Step of the differentiation, kept on a heap stack instead of the call stack.
+enum Step<'a> {
+    /// Schedules differentiation of the operands of the expression. `operand` is the index of the expression in its parent.
+    Enter {
+        expression: &'a ExprTree,
+        operand: Option<usize>,
+    },
+    /// Differentiates the expression from the derivatives of its operands.
+    Exit { expression: &'a ExprTree },
+}
+
+impl Differentiator<'_> {
+    /// Differentiates the operands of every expression before the expression itself, without recursion.
+    ///
+    /// Steps waiting to be done are kept on a heap stack, the next one on top,
+    /// and derivatives of operands, which their parent hasn't used yet, on another one.
+    fn derive(&mut self, expression: &ExprTree) -> Result<ExprTree, DifferentiationError> {
+        let mut steps = vec![Step::Enter {
+            expression,
+            operand: None,
+        }];
+        let mut derivatives: Vec<ExprTree> = Vec::new();
+        while let Some(step) = steps.pop() {
+            match step {
+                Step::Enter {
+                    expression,
+                    operand,
+                } => {
+                    if let Some(operand) = operand {
+                        self.path.push(operand);
+                    }
+                    steps.push(Step::Exit { expression });
+                    // Operands are pushed in reverse, so that lhs is differentiated first.
+                    let operands_start = steps.len();
+                    for (operand, expression) in expression.inner().children().enumerate() {
+                        steps.push(Step::Enter {
+                            expression,
+                            operand: Some(operand),
+                        });
+                    }
+                    steps[operands_start..].reverse();
+                }
+                Step::Exit { expression } => {
+                    let arity = expression.inner().arity();
+                    let operands = derivatives.split_off(derivatives.len() - arity);
+                    derivatives.push(self.derive_expr(expression, operands)?);
+                    // The path of the root is empty.
+                    self.path.pop();
+                }
+            }
+        }
+        Ok(derivatives.pop().expect("the root is differentiated last"))
+    }
+
+    fn not_differentiable(&self, operation: ExprToken) -> DifferentiationError {
+        DifferentiationError::NotDifferentiable {
+            path: ExprPath::from(self.path.clone()),
+            operation,
+        }
+    }
+
+    fn variable_exponent(&self, operation: ExprToken) -> DifferentiationError {
+        DifferentiationError::VariableExponent {
+            path: ExprPath::from(self.path.clone()),
+            operation,
+        }
+    }
+
+    /// Differentiates `root(radicand, degree)` as `root(radicand, degree)⋅radicand' / (degree⋅radicand)`.
+    fn derive_root<B: BinaryOperationExpr<ExprTree>>(
+        &self,
+        expr: &B,
+        (radicand, degree): (ExprTree, ExprTree),
+        root: ExprTree,
+        operation: ExprToken,
+    ) -> Result<ExprTree, DifferentiationError> {
+        if !is_value(&degree, 0.0) {
+            return Err(self.variable_exponent(operation));
+        }
+        Ok(div(
+            mul(radicand, root),
+            mul(expr.rhs().clone(), expr.lhs().clone()),
+        ))
+    }
+
+    /// Differentiates an expression given the derivatives of its operands, from left to right.
+    fn derive_expr(
+        &self,
+        expression: &ExprTree,
+        operands: Vec<ExprTree>,
+    ) -> Result<ExprTree, DifferentiationError> {
+        let mut operands = operands.into_iter();
+        let mut operand = || {
+            operands
+                .next()
+                .expect("every operand is differentiated before its parent")
+        };
+        let derivative = match expression.inner() {
+            Expr::Variable(expr) => integer((expr.as_ref() == self.variable) as u64),
+            Expr::SignedIntLiteral(_)
+            | Expr::UnsignedIntLiteral(_)
+            | Expr::BinaryFloat32Literal(_)
+            | Expr::BinaryFloat64Literal(_) => integer(0),
+            Expr::TrueLiteral(_) => return Err(self.not_differentiable(ExprToken::TrueLiteral)),
+            Expr::FalseLiteral(_) => return Err(self.not_differentiable(ExprToken::FalseLiteral)),
+            Expr::Addition(_) => add(operand(), operand()),
+            Expr::Subtraction(_) => sub(operand(), operand()),
+            Expr::Multiplication(expr) => {
+                let (lhs, rhs) = (operand(), operand());
+                add(mul(lhs, expr.rhs().clone()), mul(expr.lhs().clone(), rhs))
+            }
+            Expr::Division(expr) => {
+                let (lhs, rhs) = (operand(), operand());
+                if is_value(&rhs, 0.0) {
+                    div(lhs, expr.rhs().clone())
+                } else {
+                    div(
+                        sub(mul(lhs, expr.rhs().clone()), mul(expr.lhs().clone(), rhs)),
+                        square(expr.rhs().clone()),
+                    )
+                }
+            }
+            Expr::IntDivision(_) => {
+                let (lhs, rhs) = (operand(), operand());
+                if !is_value(&lhs, 0.0) || !is_value(&rhs, 0.0) {
+                    return Err(self.not_differentiable(ExprToken::IntDivision));
+                }
+                integer(0)
+            }
+            Expr::Modulo(_) => {
+                let (lhs, rhs) = (operand(), operand());
+                if !is_value(&lhs, 0.0) || !is_value(&rhs, 0.0) {
+                    return Err(self.not_differentiable(ExprToken::Modulo));
+                }
+                integer(0)
+            }
+            Expr::Power(expr) => {
+                let (base, exponent) = (operand(), operand());
+                if !is_value(&exponent, 0.0) {
+                    return Err(self.variable_exponent(ExprToken::Power));
+                }
+                let exponent = expr.rhs().clone();
+                mul(
+                    mul(
+                        exponent.clone(),
+                        pow(expr.lhs().clone(), decrement(exponent)),
+                    ),
+                    base,
+                )
+            }
+            Expr::Root(expr) => self.derive_root(
+                expr,
+                (operand(), operand()),
+                expression.clone(),
+                ExprToken::Root,
+            )?,
+            Expr::IntRoot(expr) => self.derive_root(
+                expr,
+                (operand(), operand()),
+                expression.clone(),
+                ExprToken::IntRoot,
+            )?,
+            Expr::Negation(_) => neg(operand()),
+            Expr::Square(expr) => mul(mul(integer(2), expr.inner().clone()), operand()),
+            Expr::Cube(expr) => mul(mul(integer(3), square(expr.inner().clone())), operand()),
+            Expr::SquareRoot(expr) => div(
+                operand(),
+                mul(integer(2), tree(ExprSquareRoot::from(expr.inner().clone()))),
+            ),
+            Expr::CubeRoot(expr) => div(
+                operand(),
+                mul(
+                    integer(3),
+                    square(tree(ExprCubeRoot::from(expr.inner().clone()))),
+                ),
+            ),
+            Expr::Reciprocal(expr) => neg(div(operand(), square(expr.inner().clone()))),
+        };
+        Ok(derivative)
+    }
+}
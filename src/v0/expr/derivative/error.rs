@@ -0,0 +1,25 @@
+//! Error types for differentiation of expressions.
+
+use thiserror::Error;
+
+use crate::v0::{expr::ExprPath, tokens::ExprToken};
+
+/// Expression the [differentiation](super::differentiate) failed at.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DifferentiationError {
+    /// The expression has no derivative, e.g. a boolean literal or an integer division of the variable.
+    #[error("{operation} at {path} is not differentiable")]
+    NotDifferentiable {
+        path: ExprPath,
+        operation: ExprToken,
+    },
+    /// The exponent of a power or the degree of a root depends on the variable.
+    ///
+    /// The derivative would need a logarithm, which has no expression in FEF.
+    #[error("exponent of {operation} at {path} depends on the variable")]
+    VariableExponent {
+        path: ExprPath,
+        operation: ExprToken,
+    },
+}
@@ -0,0 +1,50 @@
+//! Symbolic differentiation of expressions.
+//!
+//! [`differentiate`] returns the derivative of an [`ExprTree`](crate::v0::expr::ExprTree) with respect to a variable.
+//! The derivative is built from the usual rules, applying the chain rule to every operand depending on the variable.
+//!
+//! # Differentiation rules
+//! * The derivative of the variable is `1`, the derivative of other variables and of number literals is `0`.
+//! * [`Addition`](crate::v0::expr::Expr::Addition), [`Subtraction`](crate::v0::expr::Expr::Subtraction) and [`Negation`](crate::v0::expr::Expr::Negation)
+//!   are differentiated term by term, [`Multiplication`](crate::v0::expr::Expr::Multiplication) and [`Division`](crate::v0::expr::Expr::Division)
+//!   by the product and quotient rules.
+//! * [`Power`](crate::v0::expr::Expr::Power) is differentiated by the power rule `(a^n)' = n⋅a^(n-1)⋅a'`, [`Square`](crate::v0::expr::Expr::Square),
+//!   [`Cube`](crate::v0::expr::Expr::Cube) and [`Reciprocal`](crate::v0::expr::Expr::Reciprocal) as the powers `2`, `3` and `-1`.
+//! * [`Root`](crate::v0::expr::Expr::Root) and [`IntRoot`](crate::v0::expr::Expr::IntRoot) are differentiated as `(root(a, n))' = root(a, n)⋅a' / (n⋅a)`,
+//!   [`SquareRoot`](crate::v0::expr::Expr::SquareRoot) and [`CubeRoot`](crate::v0::expr::Expr::CubeRoot) as `a' / (2⋅sqrt(a))` and `a' / (3⋅cbrt(a)^2)`.
+//!
+//! The exponent of a power and the degree of a root must not depend on the variable, as the derivative would need a logarithm,
+//! which has no expression in FEF. Such expressions are reported as [`VariableExponent`](error::DifferentiationError::VariableExponent).
+//!
+//! [`IntDivision`](crate::v0::expr::Expr::IntDivision) and [`Modulo`](crate::v0::expr::Expr::Modulo) are not continuous,
+//! so they are reported as [not differentiable](error::DifferentiationError::NotDifferentiable) if their operands depend on the variable.
+//! Boolean literals are never differentiable.
+//!
+//! Terms which are zero and factors which are one are left out of the derivative, and negations and exponents of powers given by literals
+//! are computed in place. Other than that, the derivative is not simplified.
+//!
+//! # Examples
+//! ```rust
+//! # use fef::v0::expr::derivative::differentiate;
+//! # use fef::v0::text::{infix::{parse_infix, InfixPrinter, InfixStyle}, VariableNames};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut names = VariableNames::new();
+//! let tree = parse_infix("x^3 + 2 * x * y", &mut names)?;
+//! let x = names.identifier("x").unwrap().clone();
+//!
+//! let derivative = differentiate(&tree, &x)?;
+//!
+//! let printer = InfixPrinter::new(InfixStyle::Ascii, Some(&names));
+//! assert_eq!(printer.print(&derivative), "3 * x^2 + 2 * y");
+//!
+//! let tree = parse_infix("x // 2", &mut names)?;
+//! assert!(differentiate(&tree, &x).is_err());
+//! # Ok(())
+//! # }
+//! ```
+
+mod differentiate;
+
+pub mod error;
+
+pub use differentiate::differentiate;
@@ -0,0 +1,82 @@
+use crate::v0::{
+    expr::{traits::ExprObj, Expr},
+    tokens::ExprToken,
+};
+
+/// Cost of expressions, used to [extract](super::EGraph::extract) the best expression of an e-class.
+pub trait CostFunction {
+    /// Cost of an expression. Lower costs are better.
+    type Cost: Ord + Clone;
+
+    /// Returns the cost of an expression, whose operands are replaced by their costs.
+    ///
+    /// The cost has to be greater than the cost of each operand.
+    fn cost(&mut self, expr: &Expr<Self::Cost>) -> Self::Cost;
+}
+
+/// Cost of an expression as the number of bytes it takes in a FEF file.
+///
+/// Expressions which are encoded the same way have the same cost, so e.g. `x * x` is more expensive than `square(x)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ByteSize;
+
+impl ByteSize {
+    /// Creates the cost function.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CostFunction for ByteSize {
+    type Cost = usize;
+
+    fn cost(&mut self, expr: &Expr<usize>) -> usize {
+        let token = ExprObj::<usize>::token(expr);
+        let payload = match expr {
+            Expr::Variable(variable) => variable.as_ref().min_byte_length(),
+            Expr::SignedIntLiteral(_) | Expr::UnsignedIntLiteral(_) => match token {
+                ExprToken::SignedIntLiteral8 | ExprToken::UnsignedIntLiteral8 => 1,
+                ExprToken::SignedIntLiteral16 | ExprToken::UnsignedIntLiteral16 => 2,
+                ExprToken::SignedIntLiteral32 | ExprToken::UnsignedIntLiteral32 => 4,
+                _ => 8,
+            },
+            Expr::BinaryFloat32Literal(_) => 4,
+            Expr::BinaryFloat64Literal(_) => 8,
+            _ => 0,
+        };
+        // Every expression token fits into a single byte.
+        1 + payload + expr.children().sum::<usize>()
+    }
+}
+
+/// Cost of an expression as the number of operations, with the number of expressions breaking ties.
+///
+/// Variables and literals are not operations, so e.g. `x * x` and `square(x)` are both a single operation,
+/// but `square(x)` is preferred, as it consists of fewer expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct OperationCount;
+
+impl OperationCount {
+    /// Creates the cost function.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl CostFunction for OperationCount {
+    /// Number of operations and number of expressions.
+    type Cost = (usize, usize);
+
+    fn cost(&mut self, expr: &Expr<(usize, usize)>) -> (usize, usize) {
+        let operations = expr
+            .children()
+            .map(|(operations, _)| operations)
+            .sum::<usize>()
+            + (expr.arity() > 0) as usize;
+        let expressions = 1 + expr
+            .children()
+            .map(|(_, expressions)| expressions)
+            .sum::<usize>();
+        (operations, expressions)
+    }
+}
@@ -0,0 +1,15 @@
+//! Error types for e-graph rewrites.
+
+use thiserror::Error;
+
+/// Error creating a [rewrite](super::Rewrite).
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RewriteError {
+    /// The left-hand side of the rewrite is a variable, so it would match every expression.
+    #[error("left-hand side of rewrite {rule:?} is a variable")]
+    VariableLeftHandSide { rule: String },
+    /// The right-hand side of the rewrite uses a variable, which the left-hand side doesn't bind.
+    #[error("variable {variable:?} of rewrite {rule:?} is not bound by the left-hand side")]
+    UnboundVariable { rule: String, variable: String },
+}
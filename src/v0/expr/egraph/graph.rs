@@ -0,0 +1,320 @@
+use std::collections::HashMap;
+
+use crate::v0::expr::{Expr, ExprTree};
+
+use super::{
+    cost::CostFunction,
+    node::ENode,
+    pattern::{Rewrite, Substitution},
+    EClassId, Saturation, SaturationLimits, StopReason,
+};
+
+/// E-graph of expressions.
+///
+/// Stores a set of expressions together with the classes of expressions known to be equal.
+/// See the [module documentation](super) for an overview.
+#[derive(Debug, Clone, Default)]
+pub struct EGraph {
+    /// Union-find of the e-classes, every class points towards its representative.
+    parents: Vec<EClassId>,
+    /// Every e-node with its e-class, in the order they were added.
+    nodes: Vec<(ENode, EClassId)>,
+    /// E-class of every e-node.
+    memo: HashMap<ENode, EClassId>,
+    /// Indices to `nodes` of the e-nodes of every representative e-class.
+    classes: Vec<Vec<usize>>,
+}
+
+impl EGraph {
+    /// Creates an empty e-graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of e-nodes in the e-graph.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the number of e-classes in the e-graph.
+    pub fn class_count(&self) -> usize {
+        (0..self.parents.len())
+            .filter(|&index| self.parents[index] == EClassId(index))
+            .count()
+    }
+
+    /// Returns the representative of the e-class.
+    ///
+    /// Two e-classes are equal, if they have the same representative.
+    pub fn find(&self, class: EClassId) -> EClassId {
+        let mut class = class;
+        while self.parents[class.0] != class {
+            class = self.parents[class.0];
+        }
+        class
+    }
+
+    /// Returns `true` if the e-classes are known to be equal.
+    pub fn equivalent(&self, lhs: EClassId, rhs: EClassId) -> bool {
+        self.find(lhs) == self.find(rhs)
+    }
+
+    /// Adds an expression to the e-graph and returns its e-class.
+    ///
+    /// The tree is traversed without recursion, so it works for arbitrarily deep trees.
+    /// Expressions are [extracted](EGraph::extract) without recursion as well:
+    /// ```rust
+    /// # use fef::v0::read::read_expression_into_tree;
+    /// # use fef::v0::config::DEFAULT_CONFIG;
+    /// # use fef::v0::expr::{Expr, egraph::{EGraph, OperationCount}};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut bytes = vec![0x17; 100_000]; // Negations
+    /// bytes.extend([0x04, 0x00]); // Variable 0
+    /// let tree = read_expression_into_tree(&mut &bytes[..], &DEFAULT_CONFIG)?;
+    ///
+    /// let mut egraph = EGraph::new();
+    /// let class = egraph.add(&tree);
+    /// assert_eq!(egraph.node_count(), 100_001);
+    ///
+    /// let (cost, extracted) = egraph.extract(class, &mut OperationCount::new());
+    /// assert_eq!(cost, (100_000, 100_001));
+    /// assert!(matches!(extracted.inner(), Expr::Negation(_)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add(&mut self, tree: &ExprTree) -> EClassId {
+        // Trees waiting to be added, `true` if their operands are already added.
+        let mut stack = vec![(tree, false)];
+        // E-classes of added trees, whose parents are not added yet.
+        let mut added: Vec<EClassId> = Vec::new();
+        while let Some((tree, operands_added)) = stack.pop() {
+            if operands_added {
+                let mut operands = added.split_off(added.len() - tree.inner().arity());
+                let mut operands = operands.drain(..);
+                let expr = tree
+                    .inner()
+                    .map(|_| operands.next().expect("all operands are added"));
+                added.push(self.add_node(ENode { expr }));
+            } else {
+                stack.push((tree, true));
+                stack.extend(
+                    tree.inner()
+                        .children()
+                        .rev()
+                        .map(|operand| (operand, false)),
+                );
+            }
+        }
+        added.pop().expect("the tree is added last")
+    }
+
+    /// Records that two e-classes are equal. Returns `false` if they were already known to be equal.
+    pub fn union(&mut self, lhs: EClassId, rhs: EClassId) -> bool {
+        let merged = self.merge(lhs, rhs);
+        if merged {
+            self.rebuild();
+        }
+        merged
+    }
+
+    /// Applies the rewrites to the e-graph until no rewrite adds new equalities or a limit is reached.
+    pub fn saturate(&mut self, rewrites: &[Rewrite], limits: &SaturationLimits) -> Saturation {
+        let mut iterations = 0;
+        let mut merges = 0;
+        let stop_reason = loop {
+            if iterations >= limits.iterations() {
+                break StopReason::IterationLimit;
+            }
+            iterations += 1;
+
+            let mut matches: Vec<(&Rewrite, EClassId, Substitution)> = Vec::new();
+            for rewrite in rewrites {
+                for class in 0..self.classes.len() {
+                    let class = EClassId(class);
+                    if self.find(class) != class {
+                        continue;
+                    }
+                    for substitution in rewrite.lhs().search(self, class) {
+                        matches.push((rewrite, class, substitution));
+                    }
+                }
+            }
+
+            let mut changed = false;
+            for (rewrite, class, substitution) in matches {
+                if self.nodes.len() > limits.nodes() {
+                    break;
+                }
+                let rewritten = rewrite.rhs().instantiate(self, &substitution);
+                if self.merge(class, rewritten) {
+                    changed = true;
+                    merges += 1;
+                }
+            }
+            self.rebuild();
+
+            if self.nodes.len() > limits.nodes() {
+                break StopReason::NodeLimit;
+            }
+            if !changed {
+                break StopReason::Saturated;
+            }
+        };
+        Saturation {
+            iterations,
+            merges,
+            stop_reason,
+        }
+    }
+
+    /// Extracts the expression of the e-class with the lowest cost.
+    ///
+    /// Returns the expression together with its cost. Of expressions with the same cost, the one added to the e-graph first is extracted.
+    /// The cost of an expression has to be greater than the cost of each of its operands, otherwise the extracted expression may be infinite.
+    pub fn extract<C: CostFunction + ?Sized>(
+        &self,
+        class: EClassId,
+        cost_function: &mut C,
+    ) -> (C::Cost, ExprTree) {
+        let mut best: HashMap<EClassId, (C::Cost, usize)> = HashMap::new();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (index, (node, class)) in self.nodes.iter().enumerate() {
+                let class = self.find(*class);
+                if node
+                    .expr
+                    .children()
+                    .any(|operand| !best.contains_key(&self.find(*operand)))
+                {
+                    continue;
+                }
+                let expr = node
+                    .expr
+                    .map(|operand| best[&self.find(*operand)].0.clone());
+                let cost = cost_function.cost(&expr);
+                // Ties are broken by the order the e-nodes were added, preferring the original forms.
+                let better = best.get(&class).is_none_or(|(current, current_index)| {
+                    cost < *current || (cost == *current && index < *current_index)
+                });
+                if better {
+                    best.insert(class, (cost, index));
+                    changed = true;
+                }
+            }
+        }
+        let class = self.find(class);
+        let (cost, _) = best[&class].clone();
+        (cost, self.build(class, &best))
+    }
+
+    /// Builds the tree of the best e-nodes of the e-class and its operands, without recursion.
+    fn build<T>(&self, class: EClassId, best: &HashMap<EClassId, (T, usize)>) -> ExprTree {
+        let node = |class: EClassId| &self.nodes[best[&self.find(class)].1].0;
+        // E-nodes waiting to be built, `true` if their operands are already built.
+        let mut stack = vec![(node(class), false)];
+        // Trees of built e-nodes, whose parents are not built yet.
+        let mut built: Vec<ExprTree> = Vec::new();
+        while let Some((current, operands_built)) = stack.pop() {
+            if operands_built {
+                let mut operands = built.split_off(built.len() - current.expr.arity());
+                let mut operands = operands.drain(..);
+                let expr: Expr<ExprTree> = current
+                    .expr
+                    .map(|_| operands.next().expect("all operands are built"));
+                built.push(ExprTree::from(expr));
+            } else {
+                stack.push((current, true));
+                stack.extend(
+                    current
+                        .expr
+                        .children()
+                        .rev()
+                        .map(|operand| (node(*operand), false)),
+                );
+            }
+        }
+        built.pop().expect("the e-class is built last")
+    }
+
+    /// Returns the e-nodes of a representative e-class.
+    pub(super) fn nodes_of(&self, class: EClassId) -> impl Iterator<Item = &ENode> {
+        self.classes[class.0]
+            .iter()
+            .map(|&index| &self.nodes[index].0)
+    }
+
+    /// Replaces the operands of the e-node by their representatives.
+    fn canonical(&self, node: &ENode) -> ENode {
+        ENode {
+            expr: node.expr.map(|operand| self.find(*operand)),
+        }
+    }
+
+    pub(super) fn add_node(&mut self, node: ENode) -> EClassId {
+        let node = self.canonical(&node);
+        if let Some(&class) = self.memo.get(&node) {
+            return self.find(class);
+        }
+        let class = EClassId(self.parents.len());
+        self.parents.push(class);
+        self.classes.push(vec![self.nodes.len()]);
+        self.memo.insert(node.clone(), class);
+        self.nodes.push((node, class));
+        class
+    }
+
+    /// Merges two e-classes without restoring the invariants of the e-graph.
+    fn merge(&mut self, lhs: EClassId, rhs: EClassId) -> bool {
+        let (lhs, rhs) = (self.find(lhs), self.find(rhs));
+        if lhs == rhs {
+            return false;
+        }
+        let (root, child) = if lhs < rhs { (lhs, rhs) } else { (rhs, lhs) };
+        self.parents[child.0] = root;
+        true
+    }
+
+    /// Restores the invariants of the e-graph after merging e-classes.
+    ///
+    /// E-nodes whose operands became equal are merged, which may make further e-nodes equal, until there are no duplicate e-nodes.
+    fn rebuild(&mut self) {
+        loop {
+            self.memo.clear();
+            let mut merges = Vec::new();
+            let mut nodes = Vec::with_capacity(self.nodes.len());
+            for (node, class) in std::mem::take(&mut self.nodes) {
+                let node = self.canonical(&node);
+                let class = self.find(class);
+                match self.memo.get(&node) {
+                    Some(&other) => merges.push((other, class)),
+                    None => {
+                        self.memo.insert(node.clone(), class);
+                        nodes.push((node, class));
+                    }
+                }
+            }
+            self.nodes = nodes;
+            let mut merged = false;
+            for (lhs, rhs) in merges {
+                merged |= self.merge(lhs, rhs);
+            }
+            if !merged {
+                break;
+            }
+        }
+        for index in 0..self.parents.len() {
+            self.parents[index] = self.find(EClassId(index));
+        }
+        for class in self.classes.iter_mut() {
+            class.clear();
+        }
+        for (index, (_, class)) in self.nodes.iter_mut().enumerate() {
+            *class = self.parents[class.0];
+            self.classes[class.0].push(index);
+        }
+        for class in self.memo.values_mut() {
+            *class = self.parents[class.0];
+        }
+    }
+}
@@ -0,0 +1,138 @@
+//! Equality saturation of expressions using an e-graph.
+//!
+//! Greedy [simplification](super::simplify) applies a rule as soon as it matches, which may prevent applying a better rule later.
+//! An [`EGraph`] instead stores all equal forms of an expression at once: every e-class is a set of equal expressions (e-nodes),
+//! whose operands are e-classes themselves. [Saturating](EGraph::saturate) the e-graph with a set of [rewrites](Rewrite) adds the
+//! rewritten forms of every matching expression, until no rewrite adds anything new or a [limit](SaturationLimits) is reached.
+//! The best form of an expression is then [extracted](EGraph::extract) according to a [cost function](CostFunction).
+//!
+//! # Rewrites
+//! A [`Rewrite`] states that expressions matching its left-hand side [pattern](Pattern) are equal to its right-hand side,
+//! with the variables of the pattern substituted by the matched operands. The [`default_rules`] relate the dedicated operations,
+//! such as [`Square`](crate::v0::expr::Expr::Square) or [`Reciprocal`](crate::v0::expr::Expr::Reciprocal), to their definitions.
+//!
+//! # Cost functions
+//! * [`ByteSize`] prefers expressions with the smallest encoding in a FEF file.
+//! * [`OperationCount`] prefers expressions with the fewest operations.
+//!
+//! Other cost functions can be used by implementing the [`CostFunction`] trait.
+//!
+//! # Examples
+//! ```rust
+//! # use fef::v0::expr::egraph::{default_rules, optimize, ByteSize, SaturationLimits};
+//! # use fef::v0::text::{infix::{parse_infix, InfixPrinter, InfixStyle}, VariableNames};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut names = VariableNames::new();
+//! let tree = parse_infix("x * x + 1 / y^(1 / 2)", &mut names)?;
+//!
+//! let optimized = optimize(&tree, &default_rules(), &SaturationLimits::default(), &mut ByteSize::new());
+//!
+//! let printer = InfixPrinter::new(InfixStyle::Ascii, Some(&names));
+//! assert_eq!(printer.print(&optimized), "square(x) + recip(sqrt(y))");
+//! # Ok(())
+//! # }
+//! ```
+
+mod cost;
+mod graph;
+mod node;
+mod pattern;
+mod rules;
+
+pub mod error;
+
+pub use cost::{ByteSize, CostFunction, OperationCount};
+pub use graph::EGraph;
+pub use pattern::{Pattern, Rewrite};
+pub use rules::default_rules;
+
+pub(crate) use node::matches_operation;
+
+use crate::v0::expr::ExprTree;
+
+/// Identifier of an e-class in an [`EGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EClassId(usize);
+
+/// Limits stopping the [saturation](EGraph::saturate) of an e-graph, which may otherwise grow without end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SaturationLimits {
+    iterations: usize,
+    nodes: usize,
+}
+
+impl SaturationLimits {
+    /// Creates limits of the number of iterations and of the number of e-nodes.
+    pub fn new(iterations: usize, nodes: usize) -> Self {
+        Self { iterations, nodes }
+    }
+
+    /// Returns the maximum number of iterations.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Returns the maximum number of e-nodes.
+    pub fn nodes(&self) -> usize {
+        self.nodes
+    }
+}
+
+/// Limits of 30 iterations and 10000 e-nodes.
+impl Default for SaturationLimits {
+    fn default() -> Self {
+        Self::new(30, 10_000)
+    }
+}
+
+/// Reason why the [saturation](EGraph::saturate) of an e-graph stopped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum StopReason {
+    /// No rewrite adds a new equality, so the e-graph contains all forms reachable by the rewrites.
+    Saturated,
+    /// The maximum number of iterations was reached.
+    IterationLimit,
+    /// The maximum number of e-nodes was exceeded.
+    NodeLimit,
+}
+
+/// Result of the [saturation](EGraph::saturate) of an e-graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Saturation {
+    iterations: usize,
+    merges: usize,
+    stop_reason: StopReason,
+}
+
+impl Saturation {
+    /// Returns the number of iterations, each applying all rewrites to all e-classes.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Returns how many times an applied rewrite merged two e-classes.
+    pub fn merges(&self) -> usize {
+        self.merges
+    }
+
+    /// Returns why the saturation stopped.
+    pub fn stop_reason(&self) -> StopReason {
+        self.stop_reason
+    }
+}
+
+/// Saturates an e-graph of the expression and extracts its form with the lowest cost.
+///
+/// For an example, see the [module documentation](self).
+pub fn optimize<C: CostFunction + ?Sized>(
+    tree: &ExprTree,
+    rewrites: &[Rewrite],
+    limits: &SaturationLimits,
+    cost_function: &mut C,
+) -> ExprTree {
+    let mut egraph = EGraph::new();
+    let root = egraph.add(tree);
+    egraph.saturate(rewrites, limits);
+    egraph.extract(root, cost_function).1
+}
@@ -0,0 +1,59 @@
+//! Helpers working with expressions independently of the storage of their operands.
+
+use std::hash::{Hash, Hasher};
+
+use crate::v0::expr::{traits::ExprObj, Expr, Leaf};
+
+use super::EClassId;
+
+/// Returns `true` if the pattern expression matches the operation of the expression, ignoring the operands.
+///
+/// Integer literals match integer literals and float literals match float literals of the same value, regardless of their encoding.
+pub(crate) fn matches_operation<P, S>(pattern: &Expr<P>, expr: &Expr<S>) -> bool {
+    let integer = |leaf: Leaf<'_>| match leaf {
+        Leaf::SignedInt(value) => Some(value as i128),
+        Leaf::UnsignedInt(value) => Some(value as i128),
+        _ => None,
+    };
+    let float = |leaf: Leaf<'_>| match leaf {
+        Leaf::Float32(bits) => Some((f32::from_bits(bits) as f64).to_bits()),
+        Leaf::Float64(bits) => Some(bits),
+        _ => None,
+    };
+    let (pattern_leaf, leaf) = (pattern.leaf(), expr.leaf());
+    if let (Some(pattern), Some(value)) = (integer(pattern_leaf), integer(leaf)) {
+        return pattern == value;
+    }
+    if let (Some(pattern), Some(value)) = (float(pattern_leaf), float(leaf)) {
+        return pattern == value;
+    }
+    ExprObj::<P>::token(pattern) == ExprObj::<S>::token(expr) && pattern_leaf == leaf
+}
+
+/// Expression whose operands are e-classes.
+///
+/// Literals are compared by their bits, so every literal is equal to itself, including NaNs.
+#[derive(Debug, Clone)]
+pub(super) struct ENode {
+    pub(super) expr: Expr<EClassId>,
+}
+
+impl PartialEq for ENode {
+    fn eq(&self, other: &Self) -> bool {
+        std::mem::discriminant(&self.expr) == std::mem::discriminant(&other.expr)
+            && self.expr.leaf() == other.expr.leaf()
+            && self.expr.children().eq(other.expr.children())
+    }
+}
+
+impl Eq for ENode {}
+
+impl Hash for ENode {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(&self.expr).hash(state);
+        self.expr.leaf().hash(state);
+        for operand in self.expr.children() {
+            operand.hash(state);
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use crate::v0::expr::{Expr, ExprTree};
+
+use super::{
+    error::RewriteError,
+    node::{matches_operation, ENode},
+    EClassId, EGraph,
+};
+
+/// Assignment of e-classes to the variables of a pattern.
+pub(super) type Substitution = HashMap<String, EClassId>;
+
+/// Expression with variables, which match any expression.
+///
+/// # Examples
+/// ```rust
+/// # use fef::v0::expr::egraph::Pattern;
+/// # use fef::v0::expr::{ExprMultiplication, ExprSquare};
+/// // x * x
+/// let lhs = Pattern::from(ExprMultiplication::from((Pattern::variable("x"), Pattern::variable("x"))));
+/// // square(x)
+/// let rhs = Pattern::from(ExprSquare::from(Pattern::variable("x")));
+/// # let _ = (lhs, rhs);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    /// Variable matching any expression. All occurrences of a variable in a pattern have to match equal expressions.
+    Variable(String),
+    /// Expression with patterns as operands.
+    ///
+    /// Integer literals match integer literals and float literals match float literals of the same value, regardless of their encoding.
+    Expr(Box<Expr<Pattern>>),
+}
+
+impl Pattern {
+    /// Creates a pattern variable.
+    pub fn variable(name: impl Into<String>) -> Self {
+        Pattern::Variable(name.into())
+    }
+
+    /// Returns the names of the variables of the pattern.
+    pub fn variables(&self) -> Vec<&str> {
+        let mut variables = Vec::new();
+        self.collect_variables(&mut variables);
+        variables
+    }
+
+    fn collect_variables<'a>(&'a self, variables: &mut Vec<&'a str>) {
+        match self {
+            Pattern::Variable(name) => {
+                if !variables.contains(&name.as_str()) {
+                    variables.push(name);
+                }
+            }
+            Pattern::Expr(expr) => {
+                for operand in expr.children() {
+                    operand.collect_variables(variables);
+                }
+            }
+        }
+    }
+
+    /// Returns all substitutions for which the pattern matches an expression of the e-class.
+    pub(super) fn search(&self, egraph: &EGraph, class: EClassId) -> Vec<Substitution> {
+        self.search_with(egraph, class, Substitution::new())
+    }
+
+    fn search_with(
+        &self,
+        egraph: &EGraph,
+        class: EClassId,
+        substitution: Substitution,
+    ) -> Vec<Substitution> {
+        let class = egraph.find(class);
+        match self {
+            Pattern::Variable(name) => match substitution.get(name) {
+                Some(&bound) if egraph.find(bound) != class => Vec::new(),
+                Some(_) => vec![substitution],
+                None => {
+                    let mut substitution = substitution;
+                    substitution.insert(name.clone(), class);
+                    vec![substitution]
+                }
+            },
+            Pattern::Expr(expr) => {
+                let mut results = Vec::new();
+                for node in egraph.nodes_of(class) {
+                    if !matches_operation(expr, &node.expr) {
+                        continue;
+                    }
+                    let mut substitutions = vec![substitution.clone()];
+                    for (pattern, operand) in expr.children().zip(node.expr.children().copied()) {
+                        substitutions = substitutions
+                            .into_iter()
+                            .flat_map(|substitution| {
+                                pattern.search_with(egraph, operand, substitution)
+                            })
+                            .collect();
+                    }
+                    results.extend(substitutions);
+                }
+                results
+            }
+        }
+    }
+
+    /// Adds the pattern with substituted variables to the e-graph.
+    pub(super) fn instantiate(&self, egraph: &mut EGraph, substitution: &Substitution) -> EClassId {
+        match self {
+            Pattern::Variable(name) => substitution[name],
+            Pattern::Expr(expr) => {
+                let expr = expr.map(|operand| operand.instantiate(egraph, substitution));
+                egraph.add_node(ENode { expr })
+            }
+        }
+    }
+}
+
+impl<E: Into<Expr<Pattern>>> From<E> for Pattern {
+    fn from(expr: E) -> Self {
+        Pattern::Expr(Box::new(expr.into()))
+    }
+}
+
+/// Creates a pattern without variables matching the expression.
+impl From<&ExprTree> for Pattern {
+    fn from(tree: &ExprTree) -> Self {
+        Pattern::Expr(Box::new(tree.inner().map(|operand| Pattern::from(operand))))
+    }
+}
+
+/// Rule stating that expressions matching the left-hand side are equal to the right-hand side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rewrite {
+    name: String,
+    lhs: Pattern,
+    rhs: Pattern,
+}
+
+impl Rewrite {
+    /// Creates a rewrite from `lhs` to `rhs`.
+    ///
+    /// # Errors
+    /// * [`RewriteError::VariableLeftHandSide`] if the left-hand side is a variable, which would match every expression.
+    /// * [`RewriteError::UnboundVariable`] if the right-hand side uses a variable, which is not used by the left-hand side.
+    pub fn new(name: impl Into<String>, lhs: Pattern, rhs: Pattern) -> Result<Self, RewriteError> {
+        let name = name.into();
+        if let Pattern::Variable(_) = lhs {
+            return Err(RewriteError::VariableLeftHandSide { rule: name });
+        }
+        let bound = lhs.variables();
+        if let Some(variable) = rhs
+            .variables()
+            .into_iter()
+            .find(|variable| !bound.contains(variable))
+        {
+            return Err(RewriteError::UnboundVariable {
+                variable: variable.to_string(),
+                rule: name,
+            });
+        }
+        Ok(Self { name, lhs, rhs })
+    }
+
+    /// Returns the name of the rewrite.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the pattern of the expressions the rewrite applies to.
+    pub fn lhs(&self) -> &Pattern {
+        &self.lhs
+    }
+
+    /// Returns the pattern of the equal expressions.
+    pub fn rhs(&self) -> &Pattern {
+        &self.rhs
+    }
+}
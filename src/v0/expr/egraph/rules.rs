@@ -0,0 +1,129 @@
+use crate::v0::expr::{
+    ExprAddition, ExprCube, ExprCubeRoot, ExprDivision, ExprMultiplication, ExprPower,
+    ExprReciprocal, ExprRoot, ExprSquare, ExprSquareRoot, ExprUnsignedIntLiteral,
+};
+
+use super::{Pattern, Rewrite};
+
+fn x() -> Pattern {
+    Pattern::variable("x")
+}
+
+fn y() -> Pattern {
+    Pattern::variable("y")
+}
+
+fn integer(value: u64) -> Pattern {
+    Pattern::from(ExprUnsignedIntLiteral::from(value))
+}
+
+/// Adds the rewrites in both directions.
+fn equality(rules: &mut Vec<Rewrite>, name: &str, lhs: Pattern, rhs: Pattern) {
+    rules.push(Rewrite::new(name, lhs.clone(), rhs.clone()).expect("built-in rewrites are valid"));
+    rules.push(
+        Rewrite::new(format!("{}-reverse", name), rhs, lhs).expect("built-in rewrites are valid"),
+    );
+}
+
+/// Returns the built-in rewrites.
+///
+/// The rewrites state equalities between the dedicated operations and their definitions, together with commutativity:
+///
+/// | Name | Equality |
+/// | --- | --- |
+/// | `square` | `x * x = square(x)` |
+/// | `square-power` | `x^2 = square(x)` |
+/// | `cube` | `square(x) * x = cube(x)` |
+/// | `cube-power` | `x^3 = cube(x)` |
+/// | `reciprocal` | `1 / x = recip(x)` |
+/// | `square-root` | `x^(1 / 2) = sqrt(x)` |
+/// | `square-root-degree` | `root(x, 2) = sqrt(x)` |
+/// | `cube-root` | `x^(1 / 3) = cbrt(x)` |
+/// | `cube-root-degree` | `root(x, 3) = cbrt(x)` |
+/// | `commute-addition` | `x + y = y + x` |
+/// | `commute-multiplication` | `x * y = y * x` |
+///
+/// Every equality is a pair of rewrites, the one from right to left is named with the `-reverse` suffix.
+/// The rewrites hold for real numbers, but some of them may change the result of floating-point evaluation,
+/// e.g. `x^(1 / 2)` is `NaN` for `x = -0.0`, while `sqrt(x)` is `-0.0`.
+pub fn default_rules() -> Vec<Rewrite> {
+    let mut rules = Vec::new();
+    let square = || Pattern::from(ExprSquare::from(x()));
+    let cube = || Pattern::from(ExprCube::from(x()));
+    let square_root = || Pattern::from(ExprSquareRoot::from(x()));
+    let cube_root = || Pattern::from(ExprCubeRoot::from(x()));
+    let fraction =
+        |denominator| Pattern::from(ExprDivision::from((integer(1), integer(denominator))));
+
+    equality(
+        &mut rules,
+        "square",
+        Pattern::from(ExprMultiplication::from((x(), x()))),
+        square(),
+    );
+    equality(
+        &mut rules,
+        "square-power",
+        Pattern::from(ExprPower::from((x(), integer(2)))),
+        square(),
+    );
+    equality(
+        &mut rules,
+        "cube",
+        Pattern::from(ExprMultiplication::from((square(), x()))),
+        cube(),
+    );
+    equality(
+        &mut rules,
+        "cube-power",
+        Pattern::from(ExprPower::from((x(), integer(3)))),
+        cube(),
+    );
+    equality(
+        &mut rules,
+        "reciprocal",
+        Pattern::from(ExprDivision::from((integer(1), x()))),
+        Pattern::from(ExprReciprocal::from(x())),
+    );
+    equality(
+        &mut rules,
+        "square-root",
+        Pattern::from(ExprPower::from((x(), fraction(2)))),
+        square_root(),
+    );
+    equality(
+        &mut rules,
+        "square-root-degree",
+        Pattern::from(ExprRoot::from((x(), integer(2)))),
+        square_root(),
+    );
+    equality(
+        &mut rules,
+        "cube-root",
+        Pattern::from(ExprPower::from((x(), fraction(3)))),
+        cube_root(),
+    );
+    equality(
+        &mut rules,
+        "cube-root-degree",
+        Pattern::from(ExprRoot::from((x(), integer(3)))),
+        cube_root(),
+    );
+    rules.push(
+        Rewrite::new(
+            "commute-addition",
+            Pattern::from(ExprAddition::from((x(), y()))),
+            Pattern::from(ExprAddition::from((y(), x()))),
+        )
+        .expect("built-in rewrites are valid"),
+    );
+    rules.push(
+        Rewrite::new(
+            "commute-multiplication",
+            Pattern::from(ExprMultiplication::from((x(), y()))),
+            Pattern::from(ExprMultiplication::from((y(), x()))),
+        )
+        .expect("built-in rewrites are valid"),
+    );
+    rules
+}
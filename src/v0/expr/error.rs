@@ -91,6 +91,18 @@ where
     CustomError(E),
 }
 
+impl DecomposeError<Infallible> {
+    /// Panics, because decomposers that can't fail, like the one of [`ExprTree`](super::ExprTree), never return this error.
+    pub(crate) fn unreachable(self) -> ! {
+        match self {
+            DecomposeError::CustomError(error) => match error {},
+            DecomposeError::DefaultError(_) => {
+                unreachable!("decomposing an ExprTree never fails")
+            }
+        }
+    }
+}
+
 #[derive(Debug, Error)]
 #[error("failed to read expression.")]
 pub enum ExprWriteWithDecomposerError<E>
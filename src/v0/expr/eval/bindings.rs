@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use crate::v0::raw::VariableLengthEnum;
+
+/// Assignment of values to [variables](crate::v0::expr::ExprVariable).
+///
+/// Variables are identified by the same [`VariableLengthEnum`] that identifies them in the expression.
+///
+/// # Examples
+/// ```rust
+/// # use fef::v0::expr::eval::VariableBindings;
+/// # use fef::v0::raw::VariableLengthEnum;
+/// let mut bindings = VariableBindings::new();
+/// bindings.bind(0.into(), 1.5);
+///
+/// assert_eq!(bindings.get(&VariableLengthEnum::from(0)), Some(&1.5));
+/// assert_eq!(bindings.get(&VariableLengthEnum::from(1)), None);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableBindings<V> {
+    values: HashMap<VariableLengthEnum, V>,
+}
+
+impl<V> VariableBindings<V> {
+    /// Creates bindings with no variables bound.
+    pub fn new() -> Self {
+        Self {
+            values: HashMap::new(),
+        }
+    }
+
+    /// Binds the variable to the value.
+    ///
+    /// Returns the value previously bound to the variable, if there was one.
+    pub fn bind(&mut self, variable: VariableLengthEnum, value: V) -> Option<V> {
+        self.values.insert(variable, value)
+    }
+
+    /// Removes the binding of the variable and returns the value it was bound to.
+    pub fn unbind(&mut self, variable: &VariableLengthEnum) -> Option<V> {
+        self.values.remove(variable)
+    }
+
+    /// Returns the value bound to the variable.
+    pub fn get(&self, variable: &VariableLengthEnum) -> Option<&V> {
+        self.values.get(variable)
+    }
+
+    /// Returns an iterator over all bound variables and their values.
+    pub fn iter(&self) -> impl Iterator<Item = (&VariableLengthEnum, &V)> {
+        self.values.iter()
+    }
+}
+
+impl<V> Default for VariableBindings<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Collects bindings from pairs of variable identifiers and values.
+///
+/// # Examples
+/// ```rust
+/// # use fef::v0::expr::eval::VariableBindings;
+/// # use fef::v0::raw::VariableLengthEnum;
+/// let bindings: VariableBindings<f64> = [(0.into(), 1.0), (1.into(), 2.0)].into_iter().collect();
+///
+/// assert_eq!(bindings.get(&VariableLengthEnum::from(1)), Some(&2.0));
+/// ```
+impl<V> FromIterator<(VariableLengthEnum, V)> for VariableBindings<V> {
+    fn from_iter<T: IntoIterator<Item = (VariableLengthEnum, V)>>(iter: T) -> Self {
+        Self {
+            values: iter.into_iter().collect(),
+        }
+    }
+}
@@ -0,0 +1,279 @@
+use num_complex::Complex64;
+use num_traits::{Inv, Zero};
+
+use crate::v0::{raw::VariableLengthEnum, tokens::ExprToken};
+
+use super::{error::EvalError, traits::Evaluator, VariableBindings};
+
+/// Evaluator computing the value of an expression as a complex number.
+///
+/// Literals are converted to [`Complex64`] with a zero imaginary part, boolean literals are evaluated as `1` for [true](crate::v0::expr::ExprTrueLiteral)
+/// and `0` for [false](crate::v0::expr::ExprFalseLiteral). Roots and powers of negative numbers have a result, so this evaluator only reports
+/// division by zero, roots of degree zero and the integer operations below.
+///
+/// This evaluator is only available with the `complex` feature.
+///
+/// # Branch cuts
+/// Multivalued operations always return their principal value. The principal logarithm `Log z = ln|z| + i Arg z` is used with
+/// `Arg z` in `(-π, π]`, so the branch cut lies along the negative real axis and numbers on the cut belong to the upper half-plane.
+/// A real number is always treated as lying on the upper side of the cut, even if its imaginary part is a negative zero
+/// (e.g. the result of [`Negation`](crate::v0::expr::Expr::Negation) of a real number).
+/// * [`SquareRoot`](crate::v0::expr::Expr::SquareRoot) - the root with a non-negative real part, `sqrt(-4) = 2i`.
+/// * [`CubeRoot`](crate::v0::expr::Expr::CubeRoot) - `exp(Log z / 3)`, so the cube root of a negative number is **not** real, `cbrt(-8) = 1 + i√3`.
+///   This differs from the [`FloatEvaluator`](super::FloatEvaluator), which returns the real cube root.
+/// * [`Root`](crate::v0::expr::Expr::Root) - `exp(Log z / n)` for any non-zero degree `n`, the same branch as the square and cube roots for `n` equal to 2 and 3.
+///   A negative integer degree is the reciprocal of the root of the opposite degree.
+/// * [`IntRoot`](crate::v0::expr::Expr::IntRoot) - the same as [`Root`](crate::v0::expr::Expr::Root), but the degree must be a real integer.
+/// * [`Power`](crate::v0::expr::Expr::Power) - `exp(w Log z)` for base `z` and exponent `w`. Integer exponents are single-valued and are computed
+///   by repeated multiplication. Zero to an exponent with a positive real part is zero, zero to a negative real exponent is a division by zero
+///   and zero to any other exponent is a domain error.
+///
+/// [`IntDivision`](crate::v0::expr::Expr::IntDivision) and [`Modulo`](crate::v0::expr::Expr::Modulo) are only defined for real operands and
+/// return [`EvalError::DomainError`] otherwise.
+///
+/// # Examples
+/// Square root of a negative number:
+/// ```rust
+/// # use fef::v0::read::read_expression_into_tree;
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # use fef::v0::expr::eval::{evaluate_tree, ComplexEvaluator, VariableBindings};
+/// # use num_complex::Complex64;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let bytes: Vec<u8> = vec![
+///     0x22, // Square root
+///         0x17, // Negation
+///             0x38, 0x04, // Number 4
+/// ];
+/// let tree = read_expression_into_tree(&mut bytes.as_slice(), &DEFAULT_CONFIG)?;
+///
+/// let bindings = VariableBindings::new();
+/// let value = evaluate_tree(&tree, &mut ComplexEvaluator::new(&bindings))?;
+///
+/// assert_eq!(value, Complex64::new(0.0, 2.0));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ComplexEvaluator<'a> {
+    bindings: &'a VariableBindings<Complex64>,
+}
+
+impl<'a> ComplexEvaluator<'a> {
+    /// Creates an evaluator using the given values of variables.
+    pub fn new(bindings: &'a VariableBindings<Complex64>) -> Self {
+        Self { bindings }
+    }
+}
+
+fn real(value: f64) -> Complex64 {
+    Complex64::new(value, 0.0)
+}
+
+/// Replaces a negative zero imaginary part with a positive one, so real numbers lie on the upper side of the branch cut.
+fn normalize(value: Complex64) -> Complex64 {
+    if value.im == 0.0 {
+        real(value.re)
+    } else {
+        value
+    }
+}
+
+fn as_real(value: Complex64) -> Option<f64> {
+    if value.im == 0.0 {
+        Some(value.re)
+    } else {
+        None
+    }
+}
+
+fn as_integer(value: Complex64) -> Option<f64> {
+    as_real(value).filter(|value| value.is_finite() && value.fract() == 0.0)
+}
+
+fn real_operands(
+    lhs: Complex64,
+    rhs: Complex64,
+    operation: ExprToken,
+) -> Result<(f64, f64), EvalError> {
+    match (as_real(lhs), as_real(rhs)) {
+        (Some(_), Some(0.0)) => Err(EvalError::DivisionByZero { operation }),
+        (Some(lhs), Some(rhs)) => Ok((lhs, rhs)),
+        _ => Err(EvalError::DomainError { operation }),
+    }
+}
+
+fn principal_power(
+    base: Complex64,
+    exponent: Complex64,
+    operation: ExprToken,
+) -> Result<Complex64, EvalError> {
+    let base = normalize(base);
+    if let Some(integer) = as_integer(exponent) {
+        if base.is_zero() && integer < 0.0 {
+            return Err(EvalError::DivisionByZero { operation });
+        }
+        if integer.abs() <= i32::MAX as f64 {
+            return Ok(base.powi(integer as i32));
+        }
+    }
+    if base.is_zero() {
+        return if exponent.re > 0.0 {
+            Ok(base)
+        } else if exponent.im == 0.0 {
+            Err(EvalError::DivisionByZero { operation })
+        } else {
+            Err(EvalError::DomainError { operation })
+        };
+    }
+    match (as_real(base), as_real(exponent)) {
+        (Some(base), Some(exponent)) if base > 0.0 => Ok(real(base.powf(exponent))),
+        _ => Ok(base.powc(exponent)),
+    }
+}
+
+fn principal_root(
+    radicand: Complex64,
+    degree: Complex64,
+    operation: ExprToken,
+) -> Result<Complex64, EvalError> {
+    if degree.is_zero() {
+        return Err(EvalError::DomainError { operation });
+    }
+    let radicand = normalize(radicand);
+    let integer = match as_integer(degree) {
+        Some(integer) => integer,
+        None => return principal_power(radicand, degree.inv(), operation),
+    };
+    let magnitude = integer.abs();
+    let root = if magnitude == 1.0 {
+        radicand
+    } else if magnitude == 2.0 {
+        radicand.sqrt()
+    } else if magnitude == 3.0 {
+        radicand.cbrt()
+    } else {
+        radicand.powf(magnitude.recip())
+    };
+    if integer > 0.0 {
+        Ok(root)
+    } else if root.is_zero() {
+        Err(EvalError::DivisionByZero { operation })
+    } else {
+        Ok(root.inv())
+    }
+}
+
+impl Evaluator for ComplexEvaluator<'_> {
+    type Value = Complex64;
+    type Error = EvalError;
+
+    fn variable(&mut self, variable: &VariableLengthEnum) -> Result<Complex64, EvalError> {
+        self.bindings
+            .get(variable)
+            .copied()
+            .ok_or_else(|| EvalError::UnboundVariable {
+                variable: variable.clone(),
+            })
+    }
+
+    fn true_literal(&mut self) -> Result<Complex64, EvalError> {
+        Ok(real(1.0))
+    }
+
+    fn false_literal(&mut self) -> Result<Complex64, EvalError> {
+        Ok(real(0.0))
+    }
+
+    fn signed_int_literal(&mut self, value: i64) -> Result<Complex64, EvalError> {
+        Ok(real(value as f64))
+    }
+
+    fn unsigned_int_literal(&mut self, value: u64) -> Result<Complex64, EvalError> {
+        Ok(real(value as f64))
+    }
+
+    fn binary_float_32_literal(&mut self, value: f32) -> Result<Complex64, EvalError> {
+        Ok(real(value as f64))
+    }
+
+    fn binary_float_64_literal(&mut self, value: f64) -> Result<Complex64, EvalError> {
+        Ok(real(value))
+    }
+
+    fn addition(&mut self, lhs: Complex64, rhs: Complex64) -> Result<Complex64, EvalError> {
+        Ok(lhs + rhs)
+    }
+
+    fn subtraction(&mut self, lhs: Complex64, rhs: Complex64) -> Result<Complex64, EvalError> {
+        Ok(lhs - rhs)
+    }
+
+    fn multiplication(&mut self, lhs: Complex64, rhs: Complex64) -> Result<Complex64, EvalError> {
+        Ok(lhs * rhs)
+    }
+
+    fn division(&mut self, lhs: Complex64, rhs: Complex64) -> Result<Complex64, EvalError> {
+        if rhs.is_zero() {
+            return Err(EvalError::DivisionByZero {
+                operation: ExprToken::Division,
+            });
+        }
+        Ok(lhs / rhs)
+    }
+
+    fn int_division(&mut self, lhs: Complex64, rhs: Complex64) -> Result<Complex64, EvalError> {
+        let (lhs, rhs) = real_operands(lhs, rhs, ExprToken::IntDivision)?;
+        Ok(real((lhs / rhs).floor()))
+    }
+
+    fn modulo(&mut self, lhs: Complex64, rhs: Complex64) -> Result<Complex64, EvalError> {
+        let (lhs, rhs) = real_operands(lhs, rhs, ExprToken::Modulo)?;
+        Ok(real(lhs - rhs * (lhs / rhs).floor()))
+    }
+
+    fn power(&mut self, lhs: Complex64, rhs: Complex64) -> Result<Complex64, EvalError> {
+        principal_power(lhs, rhs, ExprToken::Power)
+    }
+
+    fn root(&mut self, lhs: Complex64, rhs: Complex64) -> Result<Complex64, EvalError> {
+        principal_root(lhs, rhs, ExprToken::Root)
+    }
+
+    fn int_root(&mut self, lhs: Complex64, rhs: Complex64) -> Result<Complex64, EvalError> {
+        if as_integer(rhs).is_none() {
+            return Err(EvalError::NonIntegerOperand {
+                operation: ExprToken::IntRoot,
+            });
+        }
+        principal_root(lhs, rhs, ExprToken::IntRoot)
+    }
+
+    fn negation(&mut self, value: Complex64) -> Result<Complex64, EvalError> {
+        Ok(-value)
+    }
+
+    fn square(&mut self, value: Complex64) -> Result<Complex64, EvalError> {
+        Ok(value * value)
+    }
+
+    fn cube(&mut self, value: Complex64) -> Result<Complex64, EvalError> {
+        Ok(value * value * value)
+    }
+
+    fn square_root(&mut self, value: Complex64) -> Result<Complex64, EvalError> {
+        Ok(normalize(value).sqrt())
+    }
+
+    fn cube_root(&mut self, value: Complex64) -> Result<Complex64, EvalError> {
+        Ok(normalize(value).cbrt())
+    }
+
+    fn reciprocal(&mut self, value: Complex64) -> Result<Complex64, EvalError> {
+        if value.is_zero() {
+            return Err(EvalError::DivisionByZero {
+                operation: ExprToken::Reciprocal,
+            });
+        }
+        Ok(value.inv())
+    }
+}
@@ -0,0 +1,34 @@
+//! Error types for evaluation of expressions.
+
+use thiserror::Error;
+
+use crate::v0::{expr::error::DecomposeError, raw::VariableLengthEnum, tokens::ExprToken};
+
+/// Errors of the evaluators provided by this crate.
+#[derive(Debug, Error, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum EvalError {
+    #[error("variable {variable} is not bound to a value")]
+    UnboundVariable { variable: VariableLengthEnum },
+    #[error("division by zero in {operation}")]
+    DivisionByZero { operation: ExprToken },
+    #[error("operands are outside of the domain of {operation}")]
+    DomainError { operation: ExprToken },
+    #[error("{operation} requires an integer operand")]
+    NonIntegerOperand { operation: ExprToken },
+    #[error("result of {operation} cannot be represented exactly")]
+    Inexact { operation: ExprToken },
+    #[error("result of {operation} is too large to be represented")]
+    Overflow { operation: ExprToken },
+}
+
+#[derive(Debug, Error)]
+#[error("failed to evaluate expression.")]
+pub enum EvalWithDecomposerError<E, D>
+where
+    E: std::error::Error,
+    D: std::error::Error,
+{
+    EvalError(E),
+    DecomposeError(#[from] DecomposeError<D>),
+}
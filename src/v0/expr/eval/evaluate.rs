@@ -0,0 +1,164 @@
+use crate::v0::{
+    expr::{
+        error::DecomposeError,
+        traits::{Decomposer, DecompositionRefContainer},
+        Expr, ExprTree,
+    },
+    write::ExprTreeDecomposer,
+};
+
+use super::{error::EvalWithDecomposerError, traits::Evaluator};
+
+type Value<E> = <E as Evaluator>::Value;
+type Error<E> = <E as Evaluator>::Error;
+type BinaryOperation<E> = fn(&mut E, Value<E>, Value<E>) -> Result<Value<E>, Error<E>>;
+type UnaryOperation<E> = fn(&mut E, Value<E>) -> Result<Value<E>, Error<E>>;
+
+/// Evaluates an expression using a [`Decomposer`] to access child expressions.
+///
+/// Walks the expression stored in `storage` and calls the appropriate method of the `evaluator` for every expression.
+/// Children are always evaluated before their parent, `lhs` before `rhs`. This is the most general way of evaluating an expression,
+/// if the expression is an [`ExprTree`], consider using [`evaluate_tree`] instead.
+///
+/// The expression is evaluated without recursion, so deeply nested expressions cannot overflow the stack.
+///
+/// # Type parameters
+///
+/// - `S`: The storage type of the expression.
+/// - `DP`: The type of the decomposer.
+/// - `E`: The type of the evaluator.
+pub fn evaluate<S: Sized, DP: ?Sized + Decomposer<S>, E: ?Sized + Evaluator>(
+    storage: &S,
+    decomposer: &mut DP,
+    evaluator: &mut E,
+) -> Result<E::Value, EvalWithDecomposerError<E::Error, DP::Error>> {
+    let expr = decomposer.decompose_as_ref(storage)?.inner_as_ref();
+    evaluate_expr(expr, evaluator, |operand| {
+        Ok(decomposer.decompose_as_ref(operand)?.inner_as_ref())
+    })
+}
+
+/// Evaluates an [`ExprTree`].
+///
+/// This function is a convenience function that simplifies calling [`evaluate`] with a decomposer for [`ExprTree`].
+/// Decomposing an [`ExprTree`] never fails, so the only errors returned are the errors of the evaluator.
+///
+/// For an example, see the [module documentation](super).
+///
+/// Deeply nested trees, which can be read from untrusted input, are evaluated without overflowing the stack:
+/// ```rust
+/// # use fef::v0::read::read_expression_into_tree;
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # use fef::v0::expr::eval::{evaluate_tree, FloatEvaluator, VariableBindings};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut bytes = vec![0x17; 100_000]; // 100 000 nested negations
+/// bytes.extend([0x38, 0x02]); // Number 2
+///
+/// let tree = read_expression_into_tree(&mut &bytes[..], &DEFAULT_CONFIG)?;
+/// let bindings = VariableBindings::new();
+///
+/// assert_eq!(evaluate_tree(&tree, &mut FloatEvaluator::new(&bindings))?, 2.0);
+/// # Ok(())
+/// # }
+/// ```
+pub fn evaluate_tree<E: ?Sized + Evaluator>(
+    tree: &ExprTree,
+    evaluator: &mut E,
+) -> Result<E::Value, E::Error> {
+    let mut decomposer = ExprTreeDecomposer {};
+    evaluate(tree, &mut decomposer, evaluator).map_err(|error| match error {
+        EvalWithDecomposerError::EvalError(error) => error,
+        EvalWithDecomposerError::DecomposeError(error) => error.unreachable(),
+    })
+}
+
+/// Step of the evaluation, kept on a heap stack instead of the call stack.
+enum Step<'a, S: Sized> {
+    /// Schedules evaluation of the operands of the expression, followed by the expression itself.
+    Enter(&'a Expr<S>),
+    /// Evaluates the expression from the values of its operands.
+    Exit(&'a Expr<S>),
+}
+
+/// Evaluates an expression without recursion, expressions of operands are obtained by `decompose`.
+///
+/// Steps waiting to be done are kept on a heap stack, the next one on top,
+/// and values of evaluated operands, which their parent hasn't used yet, on another one.
+fn evaluate_expr<'a, S: Sized, E: ?Sized + Evaluator, D: std::error::Error>(
+    expr: &'a Expr<S>,
+    evaluator: &mut E,
+    mut decompose: impl FnMut(&'a S) -> Result<&'a Expr<S>, DecomposeError<D>>,
+) -> Result<E::Value, EvalWithDecomposerError<E::Error, D>> {
+    let mut steps = vec![Step::Enter(expr)];
+    let mut values: Vec<E::Value> = Vec::new();
+    while let Some(step) = steps.pop() {
+        match step {
+            Step::Enter(expr) => {
+                steps.push(Step::Exit(expr));
+                // Operands are decomposed in order, but pushed in reverse, so that lhs is evaluated first.
+                let operands_start = steps.len();
+                for operand in expr.children() {
+                    steps.push(Step::Enter(decompose(operand)?));
+                }
+                steps[operands_start..].reverse();
+            }
+            Step::Exit(expr) => {
+                let value = apply(expr, evaluator, &mut values)
+                    .map_err(EvalWithDecomposerError::EvalError)?;
+                values.push(value);
+            }
+        }
+    }
+    Ok(values.pop().expect("the root expression is evaluated"))
+}
+
+fn apply_binary<E: ?Sized + Evaluator>(
+    evaluator: &mut E,
+    values: &mut Vec<E::Value>,
+    operation: BinaryOperation<E>,
+) -> Result<E::Value, E::Error> {
+    let rhs = values.pop().expect("operands are evaluated");
+    let lhs = values.pop().expect("operands are evaluated");
+    operation(evaluator, lhs, rhs)
+}
+
+fn apply_unary<E: ?Sized + Evaluator>(
+    evaluator: &mut E,
+    values: &mut Vec<E::Value>,
+    operation: UnaryOperation<E>,
+) -> Result<E::Value, E::Error> {
+    let value = values.pop().expect("operands are evaluated");
+    operation(evaluator, value)
+}
+
+/// Evaluates an expression, the values of its operands are taken from the top of `values`.
+fn apply<S: Sized, E: ?Sized + Evaluator>(
+    expr: &Expr<S>,
+    evaluator: &mut E,
+    values: &mut Vec<E::Value>,
+) -> Result<E::Value, E::Error> {
+    match expr {
+        Expr::Variable(expr) => evaluator.variable(expr.as_ref()),
+        Expr::TrueLiteral(_) => evaluator.true_literal(),
+        Expr::FalseLiteral(_) => evaluator.false_literal(),
+        Expr::SignedIntLiteral(expr) => evaluator.signed_int_literal(expr.value),
+        Expr::UnsignedIntLiteral(expr) => evaluator.unsigned_int_literal(expr.value),
+        Expr::BinaryFloat32Literal(expr) => evaluator.binary_float_32_literal(expr.value),
+        Expr::BinaryFloat64Literal(expr) => evaluator.binary_float_64_literal(expr.value),
+        Expr::Addition(_) => apply_binary(evaluator, values, E::addition),
+        Expr::Subtraction(_) => apply_binary(evaluator, values, E::subtraction),
+        Expr::Multiplication(_) => apply_binary(evaluator, values, E::multiplication),
+        Expr::Division(_) => apply_binary(evaluator, values, E::division),
+        Expr::IntDivision(_) => apply_binary(evaluator, values, E::int_division),
+        Expr::Modulo(_) => apply_binary(evaluator, values, E::modulo),
+        Expr::Power(_) => apply_binary(evaluator, values, E::power),
+        Expr::Root(_) => apply_binary(evaluator, values, E::root),
+        Expr::IntRoot(_) => apply_binary(evaluator, values, E::int_root),
+        Expr::Negation(_) => apply_unary(evaluator, values, E::negation),
+        Expr::Square(_) => apply_unary(evaluator, values, E::square),
+        Expr::Cube(_) => apply_unary(evaluator, values, E::cube),
+        Expr::SquareRoot(_) => apply_unary(evaluator, values, E::square_root),
+        Expr::CubeRoot(_) => apply_unary(evaluator, values, E::cube_root),
+        Expr::Reciprocal(_) => apply_unary(evaluator, values, E::reciprocal),
+    }
+}
@@ -0,0 +1,370 @@
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+
+use crate::v0::{raw::VariableLengthEnum, tokens::ExprToken};
+
+use super::{error::EvalError, traits::Evaluator, VariableBindings};
+
+/// Evaluator computing the exact value of an expression as an arbitrary-precision rational number.
+///
+/// Values are [`BigRational`]s, so integer literals of any width, float literals (every finite float is a rational number)
+/// and the results of [`IntDivision`](crate::v0::expr::Expr::IntDivision) and [`Modulo`](crate::v0::expr::Expr::Modulo)
+/// are represented exactly. Boolean literals are evaluated as `1` for [true](crate::v0::expr::ExprTrueLiteral) and `0` for [false](crate::v0::expr::ExprFalseLiteral).
+///
+/// This evaluator never falls back to floating-point arithmetic. Roots and powers with non-integer exponents are computed only if
+/// their result is rational (e.g. the square root of `9/4`), otherwise [`EvalError::Inexact`] is returned. Non-finite float literals
+/// are reported the same way. The domain of all operations is the same as with the [`FloatEvaluator`](super::FloatEvaluator).
+///
+/// The size of a power grows with its exponent, so e.g. `10 ^ 1000000000` alone would take hundreds of megabytes, and nested squares or cubes
+/// of a small literal grow doubly exponentially. Powers, squares, cubes and products whose numerator or denominator could exceed
+/// 2<sup>20</sup> bits (about 315 thousand decimal digits) are not computed and [`EvalError::Overflow`] is returned instead.
+///
+/// This evaluator is only available with the `exact` feature.
+///
+/// # Examples
+/// Integer literals above `2^53` are not rounded:
+/// ```rust
+/// # use fef::v0::read::read_expression_into_tree;
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # use fef::v0::expr::eval::{evaluate_tree, ExactEvaluator, VariableBindings};
+/// # use num_rational::BigRational;
+/// # use num_bigint::BigInt;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let bytes: Vec<u8> = vec![
+///     0x11, // Subtraction
+///         0x10, // Addition
+///             0x3C, 0x00, 0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // Number 2^53
+///             0x38, 0x01, // Number 1
+///         0x3C, 0x00, 0x20, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // Number 2^53
+/// ];
+/// let tree = read_expression_into_tree(&mut bytes.as_slice(), &DEFAULT_CONFIG)?;
+///
+/// let bindings = VariableBindings::new();
+/// let value = evaluate_tree(&tree, &mut ExactEvaluator::new(&bindings))?;
+///
+/// assert_eq!(value, BigRational::from_integer(BigInt::from(1)));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Irrational results are reported:
+/// ```rust
+/// # use fef::v0::read::read_expression_into_tree;
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # use fef::v0::expr::eval::{evaluate_tree, ExactEvaluator, VariableBindings, error::EvalError};
+/// # use fef::v0::tokens::ExprToken;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let bytes: Vec<u8> = vec![
+///     0x22, // Square root
+///         0x38, 0x02, // Number 2
+/// ];
+/// let tree = read_expression_into_tree(&mut bytes.as_slice(), &DEFAULT_CONFIG)?;
+///
+/// let bindings = VariableBindings::new();
+/// let result = evaluate_tree(&tree, &mut ExactEvaluator::new(&bindings));
+///
+/// assert_eq!(result, Err(EvalError::Inexact { operation: ExprToken::SquareRoot }));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Too large powers are reported:
+/// ```rust
+/// # use fef::v0::expr::ExprTree;
+/// # use fef::v0::expr::eval::{evaluate_tree, ExactEvaluator, VariableBindings, error::EvalError};
+/// # use fef::v0::tokens::ExprToken;
+/// let tree = ExprTree::lit(10u8).pow(1_000_000_000u32);
+///
+/// let bindings = VariableBindings::new();
+/// let result = evaluate_tree(&tree, &mut ExactEvaluator::new(&bindings));
+///
+/// assert_eq!(result, Err(EvalError::Overflow { operation: ExprToken::Power }));
+///
+/// let tree = (0..40).fold(ExprTree::lit(2u8), |tree, _| tree.square());
+/// let result = evaluate_tree(&tree, &mut ExactEvaluator::new(&bindings));
+///
+/// assert_eq!(result, Err(EvalError::Overflow { operation: ExprToken::Square }));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ExactEvaluator<'a> {
+    bindings: &'a VariableBindings<BigRational>,
+}
+
+impl<'a> ExactEvaluator<'a> {
+    /// Creates an evaluator using the given values of variables.
+    pub fn new(bindings: &'a VariableBindings<BigRational>) -> Self {
+        Self { bindings }
+    }
+}
+
+fn integer(value: impl Into<BigInt>) -> BigRational {
+    BigRational::from_integer(value.into())
+}
+
+fn float(value: f64, operation: ExprToken) -> Result<BigRational, EvalError> {
+    BigRational::from_float(value).ok_or(EvalError::Inexact { operation })
+}
+
+/// Returns the `degree`-th root of a non-negative integer, if it is an integer.
+fn exact_integer_root(value: &BigInt, degree: u32) -> Option<BigInt> {
+    let root = value.nth_root(degree);
+    if root.pow(degree) == *value {
+        Some(root)
+    } else {
+        None
+    }
+}
+
+/// Computes the `degree`-th root of a non-negative rational number with a positive degree.
+fn exact_root(
+    radicand: &BigRational,
+    degree: &BigInt,
+    operation: ExprToken,
+) -> Result<BigRational, EvalError> {
+    if radicand.is_zero() || radicand.is_one() {
+        return Ok(radicand.clone());
+    }
+    let degree = degree.to_u32().ok_or(EvalError::Inexact { operation })?;
+    let numer = exact_integer_root(radicand.numer(), degree);
+    let denom = exact_integer_root(radicand.denom(), degree);
+    match (numer, denom) {
+        (Some(numer), Some(denom)) => Ok(BigRational::new(numer, denom)),
+        _ => Err(EvalError::Inexact { operation }),
+    }
+}
+
+/// Largest number of bits of the numerator or denominator of a power or a product, see [`ExactEvaluator`].
+const MAX_BITS: u64 = 1 << 20;
+
+/// Returns the number of bits of the numerator or denominator, whichever is larger.
+fn bits(value: &BigRational) -> u64 {
+    value.numer().bits().max(value.denom().bits())
+}
+
+/// Multiplies the factors.
+///
+/// Results, whose numerator or denominator could have more than [`MAX_BITS`] bits, are reported as [`EvalError::Overflow`].
+fn product(factors: &[&BigRational], operation: ExprToken) -> Result<BigRational, EvalError> {
+    let bits = factors
+        .iter()
+        .fold(0u64, |sum, factor| sum.saturating_add(bits(factor)));
+    if bits > MAX_BITS {
+        return Err(EvalError::Overflow { operation });
+    }
+    Ok(factors
+        .iter()
+        .fold(BigRational::one(), |product, factor| product * *factor))
+}
+
+/// Raises a rational number to an integer power.
+///
+/// Results, whose numerator or denominator could have more than [`MAX_BITS`] bits, are reported as [`EvalError::Overflow`].
+fn integer_power(
+    base: &BigRational,
+    exponent: &BigInt,
+    operation: ExprToken,
+) -> Result<BigRational, EvalError> {
+    if base.is_zero() {
+        return match exponent.sign() {
+            num_bigint::Sign::Minus => Err(EvalError::DivisionByZero { operation }),
+            num_bigint::Sign::NoSign => Ok(BigRational::one()),
+            num_bigint::Sign::Plus => Ok(BigRational::zero()),
+        };
+    }
+    if base.is_one() {
+        return Ok(BigRational::one());
+    }
+    if *base == -BigRational::one() {
+        return Ok(if (exponent % 2u32).is_zero() {
+            BigRational::one()
+        } else {
+            -BigRational::one()
+        });
+    }
+    let bits = bits(base);
+    let exponent = exponent
+        .to_i32()
+        .filter(|exponent| bits.saturating_mul(exponent.unsigned_abs() as u64) <= MAX_BITS)
+        .ok_or(EvalError::Overflow { operation })?;
+    Ok(base.pow(exponent))
+}
+
+/// Raises a rational number to a rational power.
+fn rational_power(
+    base: &BigRational,
+    exponent: &BigRational,
+    operation: ExprToken,
+) -> Result<BigRational, EvalError> {
+    if exponent.is_integer() {
+        return integer_power(base, exponent.numer(), operation);
+    }
+    if base.is_negative() {
+        return Err(EvalError::DomainError { operation });
+    }
+    let root = exact_root(base, exponent.denom(), operation)?;
+    integer_power(&root, exponent.numer(), operation)
+}
+
+/// Computes the `degree`-th root of a rational number.
+fn root(
+    radicand: &BigRational,
+    degree: &BigRational,
+    operation: ExprToken,
+) -> Result<BigRational, EvalError> {
+    if degree.is_zero() {
+        return Err(EvalError::DomainError { operation });
+    }
+    if !degree.is_integer() {
+        return rational_power(radicand, &degree.recip(), operation);
+    }
+    let degree = degree.numer();
+    if radicand.is_negative() && (degree % 2u32).is_zero() {
+        return Err(EvalError::DomainError { operation });
+    }
+    let root = exact_root(&radicand.abs(), &degree.abs(), operation)?;
+    let root = if radicand.is_negative() { -root } else { root };
+    if degree.is_negative() {
+        if root.is_zero() {
+            return Err(EvalError::DivisionByZero { operation });
+        }
+        return Ok(root.recip());
+    }
+    Ok(root)
+}
+
+impl Evaluator for ExactEvaluator<'_> {
+    type Value = BigRational;
+    type Error = EvalError;
+
+    fn variable(&mut self, variable: &VariableLengthEnum) -> Result<BigRational, EvalError> {
+        self.bindings
+            .get(variable)
+            .cloned()
+            .ok_or_else(|| EvalError::UnboundVariable {
+                variable: variable.clone(),
+            })
+    }
+
+    fn true_literal(&mut self) -> Result<BigRational, EvalError> {
+        Ok(BigRational::one())
+    }
+
+    fn false_literal(&mut self) -> Result<BigRational, EvalError> {
+        Ok(BigRational::zero())
+    }
+
+    fn signed_int_literal(&mut self, value: i64) -> Result<BigRational, EvalError> {
+        Ok(integer(value))
+    }
+
+    fn unsigned_int_literal(&mut self, value: u64) -> Result<BigRational, EvalError> {
+        Ok(integer(value))
+    }
+
+    fn binary_float_32_literal(&mut self, value: f32) -> Result<BigRational, EvalError> {
+        float(value as f64, ExprToken::BinaryFloatLiteral32)
+    }
+
+    fn binary_float_64_literal(&mut self, value: f64) -> Result<BigRational, EvalError> {
+        float(value, ExprToken::BinaryFloatLiteral64)
+    }
+
+    fn addition(&mut self, lhs: BigRational, rhs: BigRational) -> Result<BigRational, EvalError> {
+        Ok(lhs + rhs)
+    }
+
+    fn subtraction(
+        &mut self,
+        lhs: BigRational,
+        rhs: BigRational,
+    ) -> Result<BigRational, EvalError> {
+        Ok(lhs - rhs)
+    }
+
+    fn multiplication(
+        &mut self,
+        lhs: BigRational,
+        rhs: BigRational,
+    ) -> Result<BigRational, EvalError> {
+        product(&[&lhs, &rhs], ExprToken::Multiplication)
+    }
+
+    fn division(&mut self, lhs: BigRational, rhs: BigRational) -> Result<BigRational, EvalError> {
+        if rhs.is_zero() {
+            return Err(EvalError::DivisionByZero {
+                operation: ExprToken::Division,
+            });
+        }
+        Ok(lhs / rhs)
+    }
+
+    fn int_division(
+        &mut self,
+        lhs: BigRational,
+        rhs: BigRational,
+    ) -> Result<BigRational, EvalError> {
+        if rhs.is_zero() {
+            return Err(EvalError::DivisionByZero {
+                operation: ExprToken::IntDivision,
+            });
+        }
+        Ok((lhs / rhs).floor())
+    }
+
+    fn modulo(&mut self, lhs: BigRational, rhs: BigRational) -> Result<BigRational, EvalError> {
+        if rhs.is_zero() {
+            return Err(EvalError::DivisionByZero {
+                operation: ExprToken::Modulo,
+            });
+        }
+        let quotient = (&lhs / &rhs).floor();
+        Ok(lhs - rhs * quotient)
+    }
+
+    fn power(&mut self, lhs: BigRational, rhs: BigRational) -> Result<BigRational, EvalError> {
+        rational_power(&lhs, &rhs, ExprToken::Power)
+    }
+
+    fn root(&mut self, lhs: BigRational, rhs: BigRational) -> Result<BigRational, EvalError> {
+        root(&lhs, &rhs, ExprToken::Root)
+    }
+
+    fn int_root(&mut self, lhs: BigRational, rhs: BigRational) -> Result<BigRational, EvalError> {
+        if !rhs.is_integer() {
+            return Err(EvalError::NonIntegerOperand {
+                operation: ExprToken::IntRoot,
+            });
+        }
+        root(&lhs, &rhs, ExprToken::IntRoot)
+    }
+
+    fn negation(&mut self, value: BigRational) -> Result<BigRational, EvalError> {
+        Ok(-value)
+    }
+
+    fn square(&mut self, value: BigRational) -> Result<BigRational, EvalError> {
+        product(&[&value, &value], ExprToken::Square)
+    }
+
+    fn cube(&mut self, value: BigRational) -> Result<BigRational, EvalError> {
+        product(&[&value, &value, &value], ExprToken::Cube)
+    }
+
+    fn square_root(&mut self, value: BigRational) -> Result<BigRational, EvalError> {
+        root(&value, &integer(2), ExprToken::SquareRoot)
+    }
+
+    fn cube_root(&mut self, value: BigRational) -> Result<BigRational, EvalError> {
+        root(&value, &integer(3), ExprToken::CubeRoot)
+    }
+
+    fn reciprocal(&mut self, value: BigRational) -> Result<BigRational, EvalError> {
+        if value.is_zero() {
+            return Err(EvalError::DivisionByZero {
+                operation: ExprToken::Reciprocal,
+            });
+        }
+        Ok(value.recip())
+    }
+}
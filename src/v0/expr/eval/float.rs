@@ -0,0 +1,213 @@
+use crate::v0::{raw::VariableLengthEnum, tokens::ExprToken};
+
+use super::{error::EvalError, traits::Evaluator, VariableBindings};
+
+/// Evaluator computing the value of an expression as a [`f64`].
+///
+/// All literals are converted to [`f64`], which means integer literals with an absolute value larger than `2^53` may lose precision.
+/// Boolean literals are evaluated as `1.0` for [true](crate::v0::expr::ExprTrueLiteral) and `0.0` for [false](crate::v0::expr::ExprFalseLiteral).
+///
+/// Instead of producing infinities or NaNs, operations outside of their domain return an [`EvalError`]:
+/// * [`Division`](crate::v0::expr::Expr::Division), [`IntDivision`](crate::v0::expr::Expr::IntDivision), [`Modulo`](crate::v0::expr::Expr::Modulo)
+///   and [`Reciprocal`](crate::v0::expr::Expr::Reciprocal) of zero and [`Power`](crate::v0::expr::Expr::Power) of zero to a negative exponent
+///   return [`EvalError::DivisionByZero`].
+/// * Even roots of negative numbers, roots of degree zero and powers of negative numbers to non-integer exponents return [`EvalError::DomainError`].
+/// * [`IntRoot`](crate::v0::expr::Expr::IntRoot) with a non-integer degree returns [`EvalError::NonIntegerOperand`].
+///
+/// Odd roots ([`CubeRoot`](crate::v0::expr::Expr::CubeRoot) and [`Root`](crate::v0::expr::Expr::Root) with an odd integer degree) of negative numbers are real and negative.
+///
+/// # Examples
+/// Division by zero:
+/// ```rust
+/// # use fef::v0::read::read_expression_into_tree;
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # use fef::v0::expr::eval::{evaluate_tree, FloatEvaluator, VariableBindings, error::EvalError};
+/// # use fef::v0::tokens::ExprToken;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let bytes: Vec<u8> = vec![
+///     0x13, // Division
+///         0x38, 0x01, // Number 1
+///         0x04, 0x00, // Variable 0
+/// ];
+/// let tree = read_expression_into_tree(&mut bytes.as_slice(), &DEFAULT_CONFIG)?;
+///
+/// let bindings: VariableBindings<f64> = [(0.into(), 0.0)].into_iter().collect();
+/// let result = evaluate_tree(&tree, &mut FloatEvaluator::new(&bindings));
+///
+/// assert_eq!(result, Err(EvalError::DivisionByZero { operation: ExprToken::Division }));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FloatEvaluator<'a> {
+    bindings: &'a VariableBindings<f64>,
+}
+
+impl<'a> FloatEvaluator<'a> {
+    /// Creates an evaluator using the given values of variables.
+    pub fn new(bindings: &'a VariableBindings<f64>) -> Self {
+        Self { bindings }
+    }
+}
+
+fn is_integer(value: f64) -> bool {
+    value.is_finite() && value.fract() == 0.0
+}
+
+fn is_odd_integer(value: f64) -> bool {
+    is_integer(value) && value % 2.0 != 0.0
+}
+
+fn real_root(radicand: f64, degree: f64, operation: ExprToken) -> Result<f64, EvalError> {
+    if degree == 0.0 {
+        return Err(EvalError::DomainError { operation });
+    }
+    if radicand < 0.0 {
+        if is_odd_integer(degree) {
+            return Ok(-(-radicand).powf(1.0 / degree));
+        }
+        return Err(EvalError::DomainError { operation });
+    }
+    if radicand == 0.0 && degree < 0.0 {
+        return Err(EvalError::DivisionByZero { operation });
+    }
+    Ok(radicand.powf(1.0 / degree))
+}
+
+impl Evaluator for FloatEvaluator<'_> {
+    type Value = f64;
+    type Error = EvalError;
+
+    fn variable(&mut self, variable: &VariableLengthEnum) -> Result<f64, EvalError> {
+        self.bindings
+            .get(variable)
+            .copied()
+            .ok_or_else(|| EvalError::UnboundVariable {
+                variable: variable.clone(),
+            })
+    }
+
+    fn true_literal(&mut self) -> Result<f64, EvalError> {
+        Ok(1.0)
+    }
+
+    fn false_literal(&mut self) -> Result<f64, EvalError> {
+        Ok(0.0)
+    }
+
+    fn signed_int_literal(&mut self, value: i64) -> Result<f64, EvalError> {
+        Ok(value as f64)
+    }
+
+    fn unsigned_int_literal(&mut self, value: u64) -> Result<f64, EvalError> {
+        Ok(value as f64)
+    }
+
+    fn binary_float_32_literal(&mut self, value: f32) -> Result<f64, EvalError> {
+        Ok(value as f64)
+    }
+
+    fn binary_float_64_literal(&mut self, value: f64) -> Result<f64, EvalError> {
+        Ok(value)
+    }
+
+    fn addition(&mut self, lhs: f64, rhs: f64) -> Result<f64, EvalError> {
+        Ok(lhs + rhs)
+    }
+
+    fn subtraction(&mut self, lhs: f64, rhs: f64) -> Result<f64, EvalError> {
+        Ok(lhs - rhs)
+    }
+
+    fn multiplication(&mut self, lhs: f64, rhs: f64) -> Result<f64, EvalError> {
+        Ok(lhs * rhs)
+    }
+
+    fn division(&mut self, lhs: f64, rhs: f64) -> Result<f64, EvalError> {
+        if rhs == 0.0 {
+            return Err(EvalError::DivisionByZero {
+                operation: ExprToken::Division,
+            });
+        }
+        Ok(lhs / rhs)
+    }
+
+    fn int_division(&mut self, lhs: f64, rhs: f64) -> Result<f64, EvalError> {
+        if rhs == 0.0 {
+            return Err(EvalError::DivisionByZero {
+                operation: ExprToken::IntDivision,
+            });
+        }
+        Ok((lhs / rhs).floor())
+    }
+
+    fn modulo(&mut self, lhs: f64, rhs: f64) -> Result<f64, EvalError> {
+        if rhs == 0.0 {
+            return Err(EvalError::DivisionByZero {
+                operation: ExprToken::Modulo,
+            });
+        }
+        Ok(lhs - rhs * (lhs / rhs).floor())
+    }
+
+    fn power(&mut self, lhs: f64, rhs: f64) -> Result<f64, EvalError> {
+        if lhs == 0.0 && rhs < 0.0 {
+            return Err(EvalError::DivisionByZero {
+                operation: ExprToken::Power,
+            });
+        }
+        if lhs < 0.0 && !is_integer(rhs) {
+            return Err(EvalError::DomainError {
+                operation: ExprToken::Power,
+            });
+        }
+        Ok(lhs.powf(rhs))
+    }
+
+    fn root(&mut self, lhs: f64, rhs: f64) -> Result<f64, EvalError> {
+        real_root(lhs, rhs, ExprToken::Root)
+    }
+
+    fn int_root(&mut self, lhs: f64, rhs: f64) -> Result<f64, EvalError> {
+        if !is_integer(rhs) {
+            return Err(EvalError::NonIntegerOperand {
+                operation: ExprToken::IntRoot,
+            });
+        }
+        real_root(lhs, rhs, ExprToken::IntRoot)
+    }
+
+    fn negation(&mut self, value: f64) -> Result<f64, EvalError> {
+        Ok(-value)
+    }
+
+    fn square(&mut self, value: f64) -> Result<f64, EvalError> {
+        Ok(value * value)
+    }
+
+    fn cube(&mut self, value: f64) -> Result<f64, EvalError> {
+        Ok(value * value * value)
+    }
+
+    fn square_root(&mut self, value: f64) -> Result<f64, EvalError> {
+        if value < 0.0 {
+            return Err(EvalError::DomainError {
+                operation: ExprToken::SquareRoot,
+            });
+        }
+        Ok(value.sqrt())
+    }
+
+    fn cube_root(&mut self, value: f64) -> Result<f64, EvalError> {
+        Ok(value.cbrt())
+    }
+
+    fn reciprocal(&mut self, value: f64) -> Result<f64, EvalError> {
+        if value == 0.0 {
+            return Err(EvalError::DivisionByZero {
+                operation: ExprToken::Reciprocal,
+            });
+        }
+        Ok(1.0 / value)
+    }
+}
@@ -0,0 +1,743 @@
+use crate::v0::{raw::VariableLengthEnum, tokens::ExprToken};
+
+use super::{error::EvalError, traits::Evaluator, VariableBindings};
+
+/// Closed interval of real numbers.
+///
+/// The bounds may be infinite, which represents unbounded intervals (e.g. `[1, ∞)`), but an interval always contains at least one real number.
+///
+/// # Examples
+/// ```rust
+/// # use fef::v0::expr::eval::Interval;
+/// let interval = Interval::new(-1.0, 2.0);
+///
+/// assert!(interval.contains(0.5));
+/// assert!(!interval.contains(3.0));
+/// assert_eq!(interval.width(), 3.0);
+/// assert_eq!(format!("{}", interval), "[-1, 2]");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval {
+    lower: f64,
+    upper: f64,
+}
+
+impl Interval {
+    /// Creates an interval with the given bounds.
+    ///
+    /// # Panics
+    /// Panics if either bound is NaN, if `lower` is greater than `upper` or if the interval contains no real number
+    /// (both bounds are the same infinity).
+    pub fn new(lower: f64, upper: f64) -> Self {
+        assert!(
+            lower <= upper && lower != f64::INFINITY && upper != f64::NEG_INFINITY,
+            "Invalid interval bounds: [{}, {}]",
+            lower,
+            upper
+        );
+        Self { lower, upper }
+    }
+
+    /// Creates an interval containing only the given value.
+    ///
+    /// # Panics
+    /// Panics if the value is not finite.
+    pub fn point(value: f64) -> Self {
+        Self::new(value, value)
+    }
+
+    /// Creates the interval of all real numbers.
+    pub fn entire() -> Self {
+        Self::new(f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    /// Returns the lower bound of the interval.
+    pub fn lower(&self) -> f64 {
+        self.lower
+    }
+
+    /// Returns the upper bound of the interval.
+    pub fn upper(&self) -> f64 {
+        self.upper
+    }
+
+    /// Returns `true` if the value is in the interval.
+    pub fn contains(&self, value: f64) -> bool {
+        self.lower <= value && value <= self.upper
+    }
+
+    /// Returns the difference between the upper and the lower bound.
+    pub fn width(&self) -> f64 {
+        self.upper - self.lower
+    }
+
+    /// Returns `true` if the interval contains only a single value.
+    pub fn is_point(&self) -> bool {
+        self.lower == self.upper
+    }
+
+    fn is_zero(&self) -> bool {
+        self.lower == 0.0 && self.upper == 0.0
+    }
+
+    fn as_integer(&self) -> Option<f64> {
+        if self.is_point() && self.lower.fract() == 0.0 {
+            Some(self.lower)
+        } else {
+            None
+        }
+    }
+
+    fn intersection(&self, other: &Interval) -> Option<Interval> {
+        let lower = self.lower.max(other.lower);
+        let upper = self.upper.min(other.upper);
+        if lower <= upper {
+            Some(Interval::new(lower, upper))
+        } else {
+            None
+        }
+    }
+}
+
+/// Formats the interval as `[lower, upper]`.
+impl std::fmt::Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[{}, {}]", self.lower, self.upper)
+    }
+}
+
+/// Directed rounding of the basic operations.
+///
+/// Floating-point operations round to the nearest representable number. Error-free transformations tell, on which side of the
+/// exact result the rounded result lies, so the rounded result can be moved one step outwards only if necessary.
+mod rounding {
+    /// Results that overflowed to an infinity are pulled back to the largest finite value, if the infinity is on the wrong side.
+    fn overflow_down(result: f64, a: f64, b: f64) -> f64 {
+        if result == f64::INFINITY && a.is_finite() && b.is_finite() {
+            f64::MAX
+        } else {
+            result
+        }
+    }
+
+    fn overflow_up(result: f64, a: f64, b: f64) -> f64 {
+        if result == f64::NEG_INFINITY && a.is_finite() && b.is_finite() {
+            f64::MIN
+        } else {
+            result
+        }
+    }
+
+    /// Returns the rounding error of `a + b`, the exact sum is `(a + b) + error`.
+    fn sum_error(a: f64, b: f64, sum: f64) -> f64 {
+        let b_virtual = sum - a;
+        (a - (sum - b_virtual)) + (b - b_virtual)
+    }
+
+    pub(super) fn add_down(a: f64, b: f64) -> f64 {
+        let sum = a + b;
+        if !sum.is_finite() {
+            return overflow_down(sum, a, b);
+        }
+        if sum_error(a, b, sum) < 0.0 {
+            sum.next_down()
+        } else {
+            sum
+        }
+    }
+
+    pub(super) fn add_up(a: f64, b: f64) -> f64 {
+        let sum = a + b;
+        if !sum.is_finite() {
+            return overflow_up(sum, a, b);
+        }
+        if sum_error(a, b, sum) > 0.0 {
+            sum.next_up()
+        } else {
+            sum
+        }
+    }
+
+    /// Multiplication with the interval arithmetic convention `0 * ∞ = 0`.
+    pub(super) fn mul_down(a: f64, b: f64) -> f64 {
+        if a == 0.0 || b == 0.0 {
+            return 0.0;
+        }
+        let product = a * b;
+        if !product.is_finite() {
+            return overflow_down(product, a, b);
+        }
+        if a.mul_add(b, -product) < 0.0 || (product == 0.0 && (a < 0.0) != (b < 0.0)) {
+            product.next_down()
+        } else {
+            product
+        }
+    }
+
+    pub(super) fn mul_up(a: f64, b: f64) -> f64 {
+        if a == 0.0 || b == 0.0 {
+            return 0.0;
+        }
+        let product = a * b;
+        if !product.is_finite() {
+            return overflow_up(product, a, b);
+        }
+        if a.mul_add(b, -product) > 0.0 || (product == 0.0 && (a < 0.0) == (b < 0.0)) {
+            product.next_up()
+        } else {
+            product
+        }
+    }
+
+    /// Returns the sign of the rounding error of `a / b`, the exact quotient is greater than the rounded one, if the result is positive.
+    fn quotient_error(a: f64, b: f64, quotient: f64) -> f64 {
+        let remainder = (-quotient).mul_add(b, a);
+        if b < 0.0 {
+            -remainder
+        } else {
+            remainder
+        }
+    }
+
+    /// Division of a number by a non-zero number.
+    pub(super) fn div_down(a: f64, b: f64) -> f64 {
+        let quotient = a / b;
+        if !quotient.is_finite() {
+            return overflow_down(quotient, a, b);
+        }
+        if !a.is_finite() || !b.is_finite() {
+            return quotient;
+        }
+        if quotient_error(a, b, quotient) < 0.0 {
+            quotient.next_down()
+        } else {
+            quotient
+        }
+    }
+
+    pub(super) fn div_up(a: f64, b: f64) -> f64 {
+        let quotient = a / b;
+        if !quotient.is_finite() {
+            return overflow_up(quotient, a, b);
+        }
+        if !a.is_finite() || !b.is_finite() {
+            return quotient;
+        }
+        if quotient_error(a, b, quotient) > 0.0 {
+            quotient.next_up()
+        } else {
+            quotient
+        }
+    }
+
+    /// Square root of a non-negative number.
+    pub(super) fn sqrt_down(a: f64) -> f64 {
+        let root = a.sqrt();
+        if root.is_finite() && (-root).mul_add(root, a) < 0.0 {
+            root.next_down()
+        } else {
+            root
+        }
+    }
+
+    pub(super) fn sqrt_up(a: f64) -> f64 {
+        let root = a.sqrt();
+        if root.is_finite() && (-root).mul_add(root, a) > 0.0 {
+            root.next_up()
+        } else {
+            root
+        }
+    }
+
+    /// Number of units in the last place the results of functions, that are not correctly rounded, are moved outwards.
+    const LIBM_ULPS: usize = 2;
+
+    /// Moves the result of a function, that is not correctly rounded, downwards.
+    pub(super) fn widen_down(value: f64) -> f64 {
+        (0..LIBM_ULPS).fold(value, |value, _| value.next_down())
+    }
+
+    /// Moves the result of a function, that is not correctly rounded, upwards.
+    pub(super) fn widen_up(value: f64) -> f64 {
+        (0..LIBM_ULPS).fold(value, |value, _| value.next_up())
+    }
+}
+
+use rounding::*;
+
+/// Evaluator computing guaranteed enclosures of the value of an expression.
+///
+/// Every variable is bound to an [`Interval`] and the result is an interval containing the value of the expression for all
+/// values of the variables in their intervals. The result is not necessarily the smallest such interval, especially if a variable
+/// occurs in the expression multiple times.
+///
+/// # Rounding
+/// All bounds are rounded outwards, so the enclosure holds despite the rounding of floating-point arithmetic. Addition, subtraction,
+/// multiplication, division and square root are rounded to the nearest enclosing floating-point numbers. The remaining functions of
+/// the standard library (powers with non-integer exponents, cube roots and roots) are not guaranteed to be correctly rounded,
+/// so their results are moved outwards by two units in the last place.
+/// Integer literals, that cannot be represented as a [`f64`] exactly, are enclosed by the two nearest floating-point numbers.
+///
+/// # Domains
+/// Operations are evaluated over the part of the interval where they are defined, following the set-based interval arithmetic.
+/// For example, the square root of `[-1, 4]` is `[0, 2]`. An error is returned only if the operation is not defined for any value
+/// of the interval, e.g. for the square root of `[-4, -1]` or division by `[0, 0]`.
+///
+/// Division by an interval containing zero results in an unbounded interval. If zero is in the interior of the divisor,
+/// the exact result is a union of two unbounded intervals and the smallest interval containing both of them is returned.
+/// The degree of [`IntRoot`](crate::v0::expr::Expr::IntRoot) must be a single integer.
+///
+/// # Examples
+/// ```rust
+/// # use fef::v0::read::read_expression_into_tree;
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # use fef::v0::expr::eval::{evaluate_tree, Interval, IntervalEvaluator, VariableBindings};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let bytes: Vec<u8> = vec![
+///     0x13, // Division
+///         0x38, 0x01, // Number 1
+///         0x20, // Square
+///             0x04, 0x00, // Variable 0
+/// ];
+/// let tree = read_expression_into_tree(&mut bytes.as_slice(), &DEFAULT_CONFIG)?;
+///
+/// let mut bindings = VariableBindings::new();
+/// bindings.bind(0.into(), Interval::new(-2.0, 4.0));
+///
+/// let value = evaluate_tree(&tree, &mut IntervalEvaluator::new(&bindings))?;
+/// assert_eq!(value, Interval::new(1.0 / 16.0, f64::INFINITY));
+///
+/// bindings.bind(0.into(), Interval::new(2.0, 4.0));
+/// let value = evaluate_tree(&tree, &mut IntervalEvaluator::new(&bindings))?;
+/// assert_eq!(value, Interval::new(1.0 / 16.0, 1.0 / 4.0));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Integer exponents too large for a [`u64`] are still even:
+/// ```rust
+/// # use fef::v0::read::read_expression_into_tree;
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # use fef::v0::expr::eval::{evaluate_tree, Interval, IntervalEvaluator, VariableBindings};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut bytes: Vec<u8> = vec![
+///     0x16, // Power
+///         0x17, // Negation
+///             0x38, 0x01, // Number 1
+///         0x43, // Float64
+/// ];
+/// bytes.extend(18446744073709551616.0f64.to_be_bytes()); // 2^64
+/// let tree = read_expression_into_tree(&mut bytes.as_slice(), &DEFAULT_CONFIG)?;
+///
+/// let bindings = VariableBindings::new();
+/// let value = evaluate_tree(&tree, &mut IntervalEvaluator::new(&bindings))?;
+/// assert_eq!(value, Interval::point(1.0));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct IntervalEvaluator<'a> {
+    bindings: &'a VariableBindings<Interval>,
+}
+
+impl<'a> IntervalEvaluator<'a> {
+    /// Creates an evaluator using the given intervals of variables.
+    pub fn new(bindings: &'a VariableBindings<Interval>) -> Self {
+        Self { bindings }
+    }
+}
+
+fn literal(value: f64, operation: ExprToken) -> Result<Interval, EvalError> {
+    if value.is_finite() {
+        Ok(Interval::point(value))
+    } else {
+        Err(EvalError::DomainError { operation })
+    }
+}
+
+/// Encloses an integer, that may not be representable as a [`f64`].
+fn integer_literal(value: f64, is_exact: bool) -> Interval {
+    if is_exact {
+        Interval::point(value)
+    } else {
+        Interval::new(value.next_down(), value.next_up())
+    }
+}
+
+fn add(lhs: Interval, rhs: Interval) -> Interval {
+    Interval::new(add_down(lhs.lower, rhs.lower), add_up(lhs.upper, rhs.upper))
+}
+
+fn negate(value: Interval) -> Interval {
+    Interval::new(-value.upper, -value.lower)
+}
+
+fn multiply(lhs: Interval, rhs: Interval) -> Interval {
+    let corners = [
+        (lhs.lower, rhs.lower),
+        (lhs.lower, rhs.upper),
+        (lhs.upper, rhs.lower),
+        (lhs.upper, rhs.upper),
+    ];
+    let lower = corners
+        .iter()
+        .map(|&(a, b)| mul_down(a, b))
+        .fold(f64::INFINITY, f64::min);
+    let upper = corners
+        .iter()
+        .map(|&(a, b)| mul_up(a, b))
+        .fold(f64::NEG_INFINITY, f64::max);
+    Interval::new(lower, upper)
+}
+
+fn divide(lhs: Interval, rhs: Interval, operation: ExprToken) -> Result<Interval, EvalError> {
+    if rhs.is_zero() {
+        return Err(EvalError::DivisionByZero { operation });
+    }
+    if !rhs.contains(0.0) {
+        let corners = [
+            (lhs.lower, rhs.lower),
+            (lhs.lower, rhs.upper),
+            (lhs.upper, rhs.lower),
+            (lhs.upper, rhs.upper),
+        ];
+        let lower = corners
+            .iter()
+            .map(|&(a, b)| div_down(a, b))
+            .fold(f64::INFINITY, f64::min);
+        let upper = corners
+            .iter()
+            .map(|&(a, b)| div_up(a, b))
+            .fold(f64::NEG_INFINITY, f64::max);
+        return Ok(Interval::new(lower, upper));
+    }
+    if lhs.is_zero() {
+        return Ok(lhs);
+    }
+    if lhs.contains(0.0) || (rhs.lower < 0.0 && rhs.upper > 0.0) {
+        return Ok(Interval::entire());
+    }
+    Ok(if lhs.upper < 0.0 {
+        if rhs.lower == 0.0 {
+            Interval::new(f64::NEG_INFINITY, div_up(lhs.upper, rhs.upper))
+        } else {
+            Interval::new(div_down(lhs.upper, rhs.lower), f64::INFINITY)
+        }
+    } else if rhs.lower == 0.0 {
+        Interval::new(div_down(lhs.lower, rhs.upper), f64::INFINITY)
+    } else {
+        Interval::new(f64::NEG_INFINITY, div_up(lhs.lower, rhs.lower))
+    })
+}
+
+/// Raises a non-negative number to a non-negative integer power with directed rounding.
+fn power_of_magnitude(base: f64, exponent: u64, round_up: bool) -> f64 {
+    let multiply = if round_up { mul_up } else { mul_down };
+    let mut result = 1.0;
+    let mut base = base;
+    let mut exponent = exponent;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = multiply(result, base);
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            base = multiply(base, base);
+        }
+    }
+    result
+}
+
+/// Raises an interval to a non-negative integer power.
+fn natural_power(base: Interval, exponent: u64) -> Interval {
+    if exponent == 0 {
+        return Interval::point(1.0);
+    }
+    let power_down = |value: f64| {
+        let magnitude = value.abs();
+        if value < 0.0 && exponent % 2 == 1 {
+            -power_of_magnitude(magnitude, exponent, true)
+        } else {
+            power_of_magnitude(magnitude, exponent, false)
+        }
+    };
+    let power_up = |value: f64| {
+        let magnitude = value.abs();
+        if value < 0.0 && exponent % 2 == 1 {
+            -power_of_magnitude(magnitude, exponent, false)
+        } else {
+            power_of_magnitude(magnitude, exponent, true)
+        }
+    };
+    if exponent % 2 == 1 || base.lower >= 0.0 {
+        Interval::new(power_down(base.lower), power_up(base.upper))
+    } else if base.upper <= 0.0 {
+        Interval::new(power_down(base.upper), power_up(base.lower))
+    } else {
+        let magnitude = base.lower.abs().max(base.upper);
+        Interval::new(0.0, power_up(magnitude))
+    }
+}
+
+fn integer_power(
+    base: Interval,
+    exponent: f64,
+    operation: ExprToken,
+) -> Result<Interval, EvalError> {
+    let magnitude = exponent.abs();
+    // Every float from 2^64 up is even, and such powers behave like the largest even `u64` power (0, 1 or infinite magnitude).
+    let natural = if magnitude >= u64::MAX as f64 {
+        u64::MAX - 1
+    } else {
+        magnitude as u64
+    };
+    let power = natural_power(base, natural);
+    if exponent < 0.0 {
+        divide(Interval::point(1.0), power, operation)
+    } else {
+        Ok(power)
+    }
+}
+
+/// Raises the non-negative part of the base to a power, using the monotonicity of `x^y` in both arguments.
+fn real_power(
+    base: Interval,
+    exponent: Interval,
+    operation: ExprToken,
+) -> Result<Interval, EvalError> {
+    let base = base
+        .intersection(&Interval::new(0.0, f64::INFINITY))
+        .ok_or(EvalError::DomainError { operation })?;
+    if base.is_zero() {
+        if exponent.upper <= 0.0 {
+            return Err(EvalError::DivisionByZero { operation });
+        }
+        return Ok(base);
+    }
+    let corners = [
+        (base.lower, exponent.lower),
+        (base.lower, exponent.upper),
+        (base.upper, exponent.lower),
+        (base.upper, exponent.upper),
+    ];
+    let mut lower = f64::INFINITY;
+    let mut upper = f64::NEG_INFINITY;
+    for (x, y) in corners {
+        let value = x.powf(y);
+        if value.is_nan() {
+            continue;
+        }
+        lower = lower.min(widen_down(value).max(0.0));
+        upper = upper.max(widen_up(value));
+    }
+    if base.lower == 0.0 && exponent.lower < 0.0 {
+        upper = f64::INFINITY;
+    }
+    if base.lower == 0.0 && exponent.upper > 0.0 {
+        lower = 0.0;
+    }
+    if exponent.contains(0.0) {
+        lower = lower.min(1.0);
+        upper = upper.max(1.0);
+    }
+    Ok(Interval::new(lower, upper))
+}
+
+fn power(base: Interval, exponent: Interval, operation: ExprToken) -> Result<Interval, EvalError> {
+    match exponent.as_integer() {
+        Some(exponent) => integer_power(base, exponent, operation),
+        None => real_power(base, exponent, operation),
+    }
+}
+
+/// Monotonic function of the magnitude `|x|^(1/degree)` with outward rounding.
+fn magnitude_root(value: f64, degree: f64, round_up: bool) -> f64 {
+    if value == 0.0 || value.is_infinite() {
+        return value;
+    }
+    let root = if degree == 2.0 {
+        return if round_up {
+            sqrt_up(value)
+        } else {
+            sqrt_down(value)
+        };
+    } else if degree == 3.0 {
+        value.cbrt()
+    } else {
+        value.powf(1.0 / degree)
+    };
+    if round_up {
+        widen_up(root)
+    } else {
+        widen_down(root).max(0.0)
+    }
+}
+
+fn root(radicand: Interval, degree: Interval, operation: ExprToken) -> Result<Interval, EvalError> {
+    if degree.is_zero() {
+        return Err(EvalError::DomainError { operation });
+    }
+    let integer_degree = match degree.as_integer() {
+        Some(degree) => degree,
+        None => {
+            let exponent = divide(Interval::point(1.0), degree, operation)?;
+            return real_power(radicand, exponent, operation);
+        }
+    };
+    let magnitude = integer_degree.abs();
+    let odd = magnitude % 2.0 == 1.0;
+    let root = if odd {
+        let signed_root = |value: f64, round_up: bool| {
+            if value < 0.0 {
+                -magnitude_root(-value, magnitude, !round_up)
+            } else {
+                magnitude_root(value, magnitude, round_up)
+            }
+        };
+        Interval::new(
+            signed_root(radicand.lower, false),
+            signed_root(radicand.upper, true),
+        )
+    } else {
+        let radicand = radicand
+            .intersection(&Interval::new(0.0, f64::INFINITY))
+            .ok_or(EvalError::DomainError { operation })?;
+        Interval::new(
+            magnitude_root(radicand.lower, magnitude, false),
+            magnitude_root(radicand.upper, magnitude, true),
+        )
+    };
+    if integer_degree < 0.0 {
+        divide(Interval::point(1.0), root, operation)
+    } else {
+        Ok(root)
+    }
+}
+
+impl Evaluator for IntervalEvaluator<'_> {
+    type Value = Interval;
+    type Error = EvalError;
+
+    fn variable(&mut self, variable: &VariableLengthEnum) -> Result<Interval, EvalError> {
+        self.bindings
+            .get(variable)
+            .copied()
+            .ok_or_else(|| EvalError::UnboundVariable {
+                variable: variable.clone(),
+            })
+    }
+
+    fn true_literal(&mut self) -> Result<Interval, EvalError> {
+        Ok(Interval::point(1.0))
+    }
+
+    fn false_literal(&mut self) -> Result<Interval, EvalError> {
+        Ok(Interval::point(0.0))
+    }
+
+    fn signed_int_literal(&mut self, value: i64) -> Result<Interval, EvalError> {
+        let float = value as f64;
+        Ok(integer_literal(
+            float,
+            float < 9223372036854775808.0 && float as i64 == value,
+        ))
+    }
+
+    fn unsigned_int_literal(&mut self, value: u64) -> Result<Interval, EvalError> {
+        let float = value as f64;
+        Ok(integer_literal(
+            float,
+            float < 18446744073709551616.0 && float as u64 == value,
+        ))
+    }
+
+    fn binary_float_32_literal(&mut self, value: f32) -> Result<Interval, EvalError> {
+        literal(value as f64, ExprToken::BinaryFloatLiteral32)
+    }
+
+    fn binary_float_64_literal(&mut self, value: f64) -> Result<Interval, EvalError> {
+        literal(value, ExprToken::BinaryFloatLiteral64)
+    }
+
+    fn addition(&mut self, lhs: Interval, rhs: Interval) -> Result<Interval, EvalError> {
+        Ok(add(lhs, rhs))
+    }
+
+    fn subtraction(&mut self, lhs: Interval, rhs: Interval) -> Result<Interval, EvalError> {
+        Ok(add(lhs, negate(rhs)))
+    }
+
+    fn multiplication(&mut self, lhs: Interval, rhs: Interval) -> Result<Interval, EvalError> {
+        Ok(multiply(lhs, rhs))
+    }
+
+    fn division(&mut self, lhs: Interval, rhs: Interval) -> Result<Interval, EvalError> {
+        divide(lhs, rhs, ExprToken::Division)
+    }
+
+    fn int_division(&mut self, lhs: Interval, rhs: Interval) -> Result<Interval, EvalError> {
+        let quotient = divide(lhs, rhs, ExprToken::IntDivision)?;
+        Ok(Interval::new(
+            quotient.lower.floor(),
+            quotient.upper.floor(),
+        ))
+    }
+
+    fn modulo(&mut self, lhs: Interval, rhs: Interval) -> Result<Interval, EvalError> {
+        let quotient = self
+            .int_division(lhs, rhs)
+            .map_err(|_| EvalError::DivisionByZero {
+                operation: ExprToken::Modulo,
+            })?;
+        let remainder = add(lhs, negate(multiply(rhs, quotient)));
+        let bound = if rhs.lower >= 0.0 {
+            Interval::new(0.0, rhs.upper)
+        } else if rhs.upper <= 0.0 {
+            Interval::new(rhs.lower, 0.0)
+        } else {
+            Interval::new(rhs.lower, rhs.upper)
+        };
+        Ok(remainder.intersection(&bound).unwrap_or(bound))
+    }
+
+    fn power(&mut self, lhs: Interval, rhs: Interval) -> Result<Interval, EvalError> {
+        power(lhs, rhs, ExprToken::Power)
+    }
+
+    fn root(&mut self, lhs: Interval, rhs: Interval) -> Result<Interval, EvalError> {
+        root(lhs, rhs, ExprToken::Root)
+    }
+
+    fn int_root(&mut self, lhs: Interval, rhs: Interval) -> Result<Interval, EvalError> {
+        if rhs.as_integer().is_none() {
+            return Err(EvalError::NonIntegerOperand {
+                operation: ExprToken::IntRoot,
+            });
+        }
+        root(lhs, rhs, ExprToken::IntRoot)
+    }
+
+    fn negation(&mut self, value: Interval) -> Result<Interval, EvalError> {
+        Ok(negate(value))
+    }
+
+    fn square(&mut self, value: Interval) -> Result<Interval, EvalError> {
+        Ok(natural_power(value, 2))
+    }
+
+    fn cube(&mut self, value: Interval) -> Result<Interval, EvalError> {
+        Ok(natural_power(value, 3))
+    }
+
+    fn square_root(&mut self, value: Interval) -> Result<Interval, EvalError> {
+        root(value, Interval::point(2.0), ExprToken::SquareRoot)
+    }
+
+    fn cube_root(&mut self, value: Interval) -> Result<Interval, EvalError> {
+        root(value, Interval::point(3.0), ExprToken::CubeRoot)
+    }
+
+    fn reciprocal(&mut self, value: Interval) -> Result<Interval, EvalError> {
+        divide(Interval::point(1.0), value, ExprToken::Reciprocal)
+    }
+}
@@ -0,0 +1,72 @@
+//! Evaluation of expressions into values.
+//!
+//! Evaluation is split into two parts. The [`Evaluator`](traits::Evaluator) trait defines the semantics of every expression
+//! (what it means to add two values, to take a root, ...) for a single value type, while the [`evaluate`] and [`evaluate_tree`]
+//! functions walk the expression and call the evaluator for every node. This way, one walking implementation is shared by
+//! all evaluation modes and one evaluator can be used with any storage type `S`.
+//!
+//! This module provides the following evaluators:
+//! * [`FloatEvaluator`] - evaluates expressions into [`f64`] values.
+//! * `ExactEvaluator` - evaluates expressions into arbitrary-precision rational numbers, available with the `exact` feature.
+//! * `ComplexEvaluator` - evaluates expressions into complex numbers, available with the `complex` feature.
+//! * [`IntervalEvaluator`] - evaluates expressions into [intervals](Interval) guaranteed to contain the value of the expression.
+//!
+//! Expressions can also be evaluated straight from a byte stream without building an [`ExprTree`](crate::v0::expr::ExprTree) first,
+//! using the [`EvaluatingComposer`](crate::v0::read::EvaluatingComposer).
+//!
+//! # Semantics
+//!
+//! The built-in evaluators interpret the operands of binary operations the following way:
+//! * [`Power`](crate::v0::expr::Expr::Power) - `lhs` is the base and `rhs` is the exponent.
+//! * [`Root`](crate::v0::expr::Expr::Root) and [`IntRoot`](crate::v0::expr::Expr::IntRoot) - `lhs` is the radicand and `rhs` is the degree.
+//!   The degree of an integer root must be an integer.
+//! * [`IntDivision`](crate::v0::expr::Expr::IntDivision) - the quotient is rounded towards negative infinity.
+//! * [`Modulo`](crate::v0::expr::Expr::Modulo) - the remainder of the floored division, so it has the sign of `rhs`.
+//!
+//! # Examples
+//! Evaluating the pythagorean theorem with `a = 3` and `b = 4`:
+//! ```rust
+//! # use fef::v0::read::read_expression_into_tree;
+//! # use fef::v0::config::DEFAULT_CONFIG;
+//! # use fef::v0::expr::eval::{evaluate_tree, FloatEvaluator, VariableBindings};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let bytes: Vec<u8> = vec![
+//!     0x22, // Square root
+//!         0x10, // Add
+//!             0x20, // Square
+//!                 0x04, 0x00, // Variable 0 (a)
+//!             0x20, // Square
+//!                 0x04, 0x01, // Variable 1 (b)
+//! ];
+//! let tree = read_expression_into_tree(&mut bytes.as_slice(), &DEFAULT_CONFIG)?;
+//!
+//! let mut bindings = VariableBindings::new();
+//! bindings.bind(0.into(), 3.0);
+//! bindings.bind(1.into(), 4.0);
+//!
+//! let value = evaluate_tree(&tree, &mut FloatEvaluator::new(&bindings))?;
+//! assert_eq!(value, 5.0);
+//! # Ok(())
+//! # }
+//! ```
+
+mod bindings;
+#[cfg(feature = "complex")]
+mod complex;
+mod evaluate;
+#[cfg(feature = "exact")]
+mod exact;
+mod float;
+mod interval;
+
+pub mod error;
+pub mod traits;
+
+pub use bindings::VariableBindings;
+#[cfg(feature = "complex")]
+pub use complex::ComplexEvaluator;
+pub use evaluate::{evaluate, evaluate_tree};
+#[cfg(feature = "exact")]
+pub use exact::ExactEvaluator;
+pub use float::FloatEvaluator;
+pub use interval::{Interval, IntervalEvaluator};
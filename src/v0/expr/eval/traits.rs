@@ -0,0 +1,138 @@
+//! Traits for evaluation of expressions.
+
+use crate::v0::raw::VariableLengthEnum;
+
+/// Semantics of expressions for a single value type.
+///
+/// An evaluator gets the already evaluated values of the children of an expression and computes the value of the expression itself.
+/// It doesn't need to know how the expression is stored, walking the expression is done by [`evaluate`](super::evaluate) and
+/// [`evaluate_tree`](super::evaluate_tree).
+///
+/// # Type Parameters
+/// * `Value`: The type of the values the evaluator computes.
+///
+/// # Implementing an Evaluator
+/// Every expression has a corresponding method. Literals and variables receive the values they hold, operations receive the values
+/// of their operands. For the meaning of the operands, see the [semantics](super#semantics) used by this crate.
+///
+/// # Examples
+/// Evaluator counting the number of nodes of an expression:
+/// ```rust
+/// # use std::convert::Infallible;
+/// # use fef::v0::expr::eval::traits::Evaluator;
+/// # use fef::v0::raw::VariableLengthEnum;
+/// struct NodeCounter;
+///
+/// impl Evaluator for NodeCounter {
+///     type Value = usize;
+///     type Error = Infallible;
+///
+///     fn variable(&mut self, _: &VariableLengthEnum) -> Result<usize, Infallible> { Ok(1) }
+///     fn true_literal(&mut self) -> Result<usize, Infallible> { Ok(1) }
+///     fn false_literal(&mut self) -> Result<usize, Infallible> { Ok(1) }
+///     fn signed_int_literal(&mut self, _: i64) -> Result<usize, Infallible> { Ok(1) }
+///     fn unsigned_int_literal(&mut self, _: u64) -> Result<usize, Infallible> { Ok(1) }
+///     fn binary_float_32_literal(&mut self, _: f32) -> Result<usize, Infallible> { Ok(1) }
+///     fn binary_float_64_literal(&mut self, _: f64) -> Result<usize, Infallible> { Ok(1) }
+///     fn addition(&mut self, l: usize, r: usize) -> Result<usize, Infallible> { Ok(l + r + 1) }
+///     fn subtraction(&mut self, l: usize, r: usize) -> Result<usize, Infallible> { Ok(l + r + 1) }
+///     fn multiplication(&mut self, l: usize, r: usize) -> Result<usize, Infallible> { Ok(l + r + 1) }
+///     fn division(&mut self, l: usize, r: usize) -> Result<usize, Infallible> { Ok(l + r + 1) }
+///     fn int_division(&mut self, l: usize, r: usize) -> Result<usize, Infallible> { Ok(l + r + 1) }
+///     fn modulo(&mut self, l: usize, r: usize) -> Result<usize, Infallible> { Ok(l + r + 1) }
+///     fn power(&mut self, l: usize, r: usize) -> Result<usize, Infallible> { Ok(l + r + 1) }
+///     fn root(&mut self, l: usize, r: usize) -> Result<usize, Infallible> { Ok(l + r + 1) }
+///     fn int_root(&mut self, l: usize, r: usize) -> Result<usize, Infallible> { Ok(l + r + 1) }
+///     fn negation(&mut self, v: usize) -> Result<usize, Infallible> { Ok(v + 1) }
+///     fn square(&mut self, v: usize) -> Result<usize, Infallible> { Ok(v + 1) }
+///     fn cube(&mut self, v: usize) -> Result<usize, Infallible> { Ok(v + 1) }
+///     fn square_root(&mut self, v: usize) -> Result<usize, Infallible> { Ok(v + 1) }
+///     fn cube_root(&mut self, v: usize) -> Result<usize, Infallible> { Ok(v + 1) }
+///     fn reciprocal(&mut self, v: usize) -> Result<usize, Infallible> { Ok(v + 1) }
+/// }
+/// ```
+pub trait Evaluator {
+    /// The type of the computed values.
+    type Value;
+
+    /// The error returned when an expression cannot be evaluated.
+    type Error: std::error::Error;
+
+    /// Evaluates a [variable](crate::v0::expr::ExprVariable) with the given identifier.
+    fn variable(&mut self, variable: &VariableLengthEnum) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates a [true literal](crate::v0::expr::ExprTrueLiteral).
+    fn true_literal(&mut self) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates a [false literal](crate::v0::expr::ExprFalseLiteral).
+    fn false_literal(&mut self) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates a [signed integer literal](crate::v0::expr::ExprSignedIntLiteral).
+    fn signed_int_literal(&mut self, value: i64) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates an [unsigned integer literal](crate::v0::expr::ExprUnsignedIntLiteral).
+    fn unsigned_int_literal(&mut self, value: u64) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates a [32-bit float literal](crate::v0::expr::ExprBinaryFloat32Literal).
+    fn binary_float_32_literal(&mut self, value: f32) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates a [64-bit float literal](crate::v0::expr::ExprBinaryFloat64Literal).
+    fn binary_float_64_literal(&mut self, value: f64) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates an [addition](crate::v0::expr::ExprAddition).
+    fn addition(&mut self, lhs: Self::Value, rhs: Self::Value) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates a [subtraction](crate::v0::expr::ExprSubtraction).
+    fn subtraction(
+        &mut self,
+        lhs: Self::Value,
+        rhs: Self::Value,
+    ) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates a [multiplication](crate::v0::expr::ExprMultiplication).
+    fn multiplication(
+        &mut self,
+        lhs: Self::Value,
+        rhs: Self::Value,
+    ) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates a [division](crate::v0::expr::ExprDivision).
+    fn division(&mut self, lhs: Self::Value, rhs: Self::Value) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates an [integer division](crate::v0::expr::ExprIntDivision).
+    fn int_division(
+        &mut self,
+        lhs: Self::Value,
+        rhs: Self::Value,
+    ) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates a [modulo](crate::v0::expr::ExprModulo).
+    fn modulo(&mut self, lhs: Self::Value, rhs: Self::Value) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates a [power](crate::v0::expr::ExprPower) with base `lhs` and exponent `rhs`.
+    fn power(&mut self, lhs: Self::Value, rhs: Self::Value) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates a [root](crate::v0::expr::ExprRoot) with radicand `lhs` and degree `rhs`.
+    fn root(&mut self, lhs: Self::Value, rhs: Self::Value) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates an [integer root](crate::v0::expr::ExprIntRoot) with radicand `lhs` and degree `rhs`.
+    fn int_root(&mut self, lhs: Self::Value, rhs: Self::Value) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates a [negation](crate::v0::expr::ExprNegation).
+    fn negation(&mut self, value: Self::Value) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates a [square](crate::v0::expr::ExprSquare).
+    fn square(&mut self, value: Self::Value) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates a [cube](crate::v0::expr::ExprCube).
+    fn cube(&mut self, value: Self::Value) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates a [square root](crate::v0::expr::ExprSquareRoot).
+    fn square_root(&mut self, value: Self::Value) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates a [cube root](crate::v0::expr::ExprCubeRoot).
+    fn cube_root(&mut self, value: Self::Value) -> Result<Self::Value, Self::Error>;
+
+    /// Evaluates a [reciprocal](crate::v0::expr::ExprReciprocal).
+    fn reciprocal(&mut self, value: Self::Value) -> Result<Self::Value, Self::Error>;
+}
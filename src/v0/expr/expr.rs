@@ -1,6 +1,12 @@
-use crate::{common::traits::private::Sealed, v0::tokens::ExprToken};
+use crate::{
+    common::traits::private::Sealed,
+    v0::{raw::VariableLengthEnum, tokens::ExprToken},
+};
 
-use super::{traits::ExprObj, *};
+use super::{
+    traits::{BinaryOperationExpr, ExprObj, ExprStorage, ExprStorageMut, UnaryOperationExpr},
+    *,
+};
 
 /// Represents any expression in the FEF specification.
 ///
@@ -124,12 +130,193 @@ impl<S: Sized> ExprObj<S> for Expr<S> {
     }
 }
 
+impl<S: Sized> Expr<S> {
+    /// Returns the number of child expressions of the expression.
+    ///
+    /// Literals and variables have no child expressions, unary operations have one and binary operations have two.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use fef::v0::expr::{Expr, ExprAddition, ExprNegation, ExprTrueLiteral};
+    /// assert_eq!(Expr::Addition(ExprAddition::from((1, 2))).arity(), 2);
+    /// assert_eq!(Expr::Negation(ExprNegation::from(1)).arity(), 1);
+    /// assert_eq!(Expr::<i32>::TrueLiteral(ExprTrueLiteral::default()).arity(), 0);
+    /// ```
+    pub fn arity(&self) -> usize {
+        self.children().count()
+    }
+
+    /// Returns an iterator over the child expressions of the expression, lhs before rhs.
+    ///
+    /// Unlike matching on all variants, this keeps working when new expressions are added.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use fef::v0::expr::{Expr, ExprSubtraction};
+    /// let expr = Expr::Subtraction(ExprSubtraction::from((1, 2)));
+    ///
+    /// assert_eq!(expr.children().collect::<Vec<_>>(), [&1, &2]);
+    /// ```
+    pub fn children(&self) -> impl DoubleEndedIterator<Item = &S> {
+        let children: [Option<&S>; 2] = match self {
+            Expr::Variable(_)
+            | Expr::SignedIntLiteral(_)
+            | Expr::UnsignedIntLiteral(_)
+            | Expr::BinaryFloat32Literal(_)
+            | Expr::BinaryFloat64Literal(_)
+            | Expr::TrueLiteral(_)
+            | Expr::FalseLiteral(_) => [None, None],
+            Expr::Addition(inner) => [Some(inner.lhs()), Some(inner.rhs())],
+            Expr::Subtraction(inner) => [Some(inner.lhs()), Some(inner.rhs())],
+            Expr::Multiplication(inner) => [Some(inner.lhs()), Some(inner.rhs())],
+            Expr::Division(inner) => [Some(inner.lhs()), Some(inner.rhs())],
+            Expr::IntDivision(inner) => [Some(inner.lhs()), Some(inner.rhs())],
+            Expr::Modulo(inner) => [Some(inner.lhs()), Some(inner.rhs())],
+            Expr::Power(inner) => [Some(inner.lhs()), Some(inner.rhs())],
+            Expr::Root(inner) => [Some(inner.lhs()), Some(inner.rhs())],
+            Expr::IntRoot(inner) => [Some(inner.lhs()), Some(inner.rhs())],
+            Expr::Negation(inner) => [Some(inner.inner()), None],
+            Expr::Square(inner) => [Some(inner.inner()), None],
+            Expr::Cube(inner) => [Some(inner.inner()), None],
+            Expr::SquareRoot(inner) => [Some(inner.inner()), None],
+            Expr::CubeRoot(inner) => [Some(inner.inner()), None],
+            Expr::Reciprocal(inner) => [Some(inner.inner()), None],
+        };
+        children.into_iter().flatten()
+    }
+
+    /// Returns an iterator over mutable references to the child expressions of the expression, lhs before rhs.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use fef::v0::expr::{Expr, ExprSubtraction};
+    /// let mut expr = Expr::Subtraction(ExprSubtraction::from((1, 2)));
+    /// for child in expr.children_mut() {
+    ///     *child *= 10;
+    /// }
+    ///
+    /// assert_eq!(expr.children().collect::<Vec<_>>(), [&10, &20]);
+    /// ```
+    pub fn children_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut S> {
+        let children: [Option<&mut S>; 2] = match self {
+            Expr::Variable(_)
+            | Expr::SignedIntLiteral(_)
+            | Expr::UnsignedIntLiteral(_)
+            | Expr::BinaryFloat32Literal(_)
+            | Expr::BinaryFloat64Literal(_)
+            | Expr::TrueLiteral(_)
+            | Expr::FalseLiteral(_) => [None, None],
+            Expr::Addition(inner) => binary_mut(inner),
+            Expr::Subtraction(inner) => binary_mut(inner),
+            Expr::Multiplication(inner) => binary_mut(inner),
+            Expr::Division(inner) => binary_mut(inner),
+            Expr::IntDivision(inner) => binary_mut(inner),
+            Expr::Modulo(inner) => binary_mut(inner),
+            Expr::Power(inner) => binary_mut(inner),
+            Expr::Root(inner) => binary_mut(inner),
+            Expr::IntRoot(inner) => binary_mut(inner),
+            Expr::Negation(inner) => [Some(inner.inner_mut()), None],
+            Expr::Square(inner) => [Some(inner.inner_mut()), None],
+            Expr::Cube(inner) => [Some(inner.inner_mut()), None],
+            Expr::SquareRoot(inner) => [Some(inner.inner_mut()), None],
+            Expr::CubeRoot(inner) => [Some(inner.inner_mut()), None],
+            Expr::Reciprocal(inner) => [Some(inner.inner_mut()), None],
+        };
+        children.into_iter().flatten()
+    }
+
+    /// Returns the value of the expression, if it is a variable or a number literal.
+    pub(crate) fn leaf(&self) -> Leaf<'_> {
+        match self {
+            Expr::Variable(variable) => Leaf::Variable(variable.as_ref()),
+            Expr::SignedIntLiteral(literal) => Leaf::SignedInt(literal.value),
+            Expr::UnsignedIntLiteral(literal) => Leaf::UnsignedInt(literal.value),
+            Expr::BinaryFloat32Literal(literal) => Leaf::Float32(literal.value.to_bits()),
+            Expr::BinaryFloat64Literal(literal) => Leaf::Float64(literal.value.to_bits()),
+            _ => Leaf::None,
+        }
+    }
+
+    /// Creates an expression with the same operation and values, and the child expressions converted by `f`, lhs before rhs.
+    ///
+    /// This converts between storages of the child expressions, e.g. from [`ExprTree`] to the nodes of another storage.
+    ///
+    /// # Examples
+    /// ```rust
+    /// # use fef::v0::expr::{Expr, ExprAddition};
+    /// let expr = Expr::Addition(ExprAddition::from((1, 2)));
+    /// let mapped = expr.map(|child| child.to_string());
+    ///
+    /// assert_eq!(mapped.children().collect::<Vec<_>>(), ["1", "2"]);
+    /// ```
+    pub fn map<T: Sized>(&self, mut f: impl FnMut(&S) -> T) -> Expr<T> {
+        macro_rules! binary {
+            ($expr:expr, $type:ident) => {{
+                let lhs = f($expr.lhs());
+                let rhs = f($expr.rhs());
+                $type::from((lhs, rhs)).into()
+            }};
+        }
+        macro_rules! unary {
+            ($expr:expr, $type:ident) => {
+                $type::from(f($expr.inner())).into()
+            };
+        }
+        match self {
+            Expr::Variable(variable) => ExprVariable::from(variable.as_ref().clone()).into(),
+            Expr::SignedIntLiteral(literal) => ExprSignedIntLiteral::from(literal.value).into(),
+            Expr::UnsignedIntLiteral(literal) => ExprUnsignedIntLiteral::from(literal.value).into(),
+            Expr::BinaryFloat32Literal(literal) => {
+                ExprBinaryFloat32Literal::from(literal.value).into()
+            }
+            Expr::BinaryFloat64Literal(literal) => {
+                ExprBinaryFloat64Literal::from(literal.value).into()
+            }
+            Expr::TrueLiteral(_) => ExprTrueLiteral::default().into(),
+            Expr::FalseLiteral(_) => ExprFalseLiteral::default().into(),
+            Expr::Addition(expr) => binary!(expr, ExprAddition),
+            Expr::Subtraction(expr) => binary!(expr, ExprSubtraction),
+            Expr::Multiplication(expr) => binary!(expr, ExprMultiplication),
+            Expr::Division(expr) => binary!(expr, ExprDivision),
+            Expr::IntDivision(expr) => binary!(expr, ExprIntDivision),
+            Expr::Modulo(expr) => binary!(expr, ExprModulo),
+            Expr::Power(expr) => binary!(expr, ExprPower),
+            Expr::Root(expr) => binary!(expr, ExprRoot),
+            Expr::IntRoot(expr) => binary!(expr, ExprIntRoot),
+            Expr::Negation(expr) => unary!(expr, ExprNegation),
+            Expr::Square(expr) => unary!(expr, ExprSquare),
+            Expr::Cube(expr) => unary!(expr, ExprCube),
+            Expr::SquareRoot(expr) => unary!(expr, ExprSquareRoot),
+            Expr::CubeRoot(expr) => unary!(expr, ExprCubeRoot),
+            Expr::Reciprocal(expr) => unary!(expr, ExprReciprocal),
+        }
+    }
+}
+
+/// Value of a leaf expression, floats compared by their bits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Leaf<'a> {
+    Variable(&'a VariableLengthEnum),
+    SignedInt(i64),
+    UnsignedInt(u64),
+    Float32(u32),
+    Float64(u64),
+    None,
+}
+
+fn binary_mut<S: Sized, E: BinaryOperationExpr<S>>(expr: &mut E) -> [Option<&mut S>; 2] {
+    let (lhs, rhs) = expr.operands_mut();
+    [Some(lhs), Some(rhs)]
+}
+
 /// A helper new-type-like struct to allow expression trees to be treated as expressions.
 ///
 /// This struct is equivalent to the infinitely recursive type `Expr<Expr<Expr<Expr<...>>>>`, which is due to current limitations
 /// of Rust's type system not possible to express directly. This struct is a direct mapping to this type however and so
 /// can be freely converted to and from the `Expr` enum using the `Into` and `From` traits.
 ///
+/// Cloning and dropping a tree does not recurse, so even very deep trees can be cloned and dropped without overflowing the stack.
+///
 /// # Examples
 /// ```
 /// # use fef::v0::expr::{Expr, ExprTree, ExprVariable};
@@ -141,14 +328,31 @@ impl<S: Sized> ExprObj<S> for Expr<S> {
 /// // Example of converting an ExprTree back to an Expr
 /// let expr: Expr<ExprTree> = expr_tree.into();
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+///
+/// # Building
+/// Trees can be built using the operators `+`, `-`, `*`, `/`, `%` and unary `-`, constructors [`var`](ExprTree::var)
+/// and [`lit`](ExprTree::lit) and methods like [`pow`](ExprTree::pow) or [`sqrt`](ExprTree::sqrt).
+/// Rust numbers and booleans used as operands are converted to literals of the matching type, see [`LiteralValue`](crate::v0::expr::traits::LiteralValue).
+/// ```
+/// # use fef::v0::expr::{Expr, ExprTree, traits::BinaryOperationExpr};
+/// let x = ExprTree::var(0);
+/// let tree = x.clone() * x.clone() + 1.0;
+///
+/// let Expr::Addition(addition) = tree.inner() else { panic!() };
+/// assert!(matches!(addition.lhs().inner(), Expr::Multiplication(_)));
+/// assert!(matches!(addition.rhs().inner(), Expr::BinaryFloat64Literal(_)));
+///
+/// let hypotenuse = (x.square() + ExprTree::var(1).square()).sqrt();
+/// let reciprocal = (2u8 * ExprTree::var(2)).recip();
+/// ```
+#[derive(Debug, PartialEq)]
 pub struct ExprTree {
     inner: Box<Expr<ExprTree>>,
 }
 
 impl ExprTree {
-    pub fn into_inner(self) -> Expr<ExprTree> {
-        *self.inner
+    pub fn into_inner(mut self) -> Expr<ExprTree> {
+        self.take_inner()
     }
     pub fn inner(&self) -> &Expr<ExprTree> {
         &self.inner
@@ -156,6 +360,16 @@ impl ExprTree {
     pub fn inner_mut(&mut self) -> &mut Expr<ExprTree> {
         &mut self.inner
     }
+
+    /// Moves the expression out of the tree, leaving a leaf in its place.
+    ///
+    /// Moving out of `self.inner` directly is not possible, because [`ExprTree`] implements [`Drop`].
+    fn take_inner(&mut self) -> Expr<ExprTree> {
+        std::mem::replace(
+            &mut self.inner,
+            Expr::TrueLiteral(ExprTrueLiteral::from(())),
+        )
+    }
 }
 
 impl From<Expr<ExprTree>> for ExprTree {
@@ -167,7 +381,67 @@ impl From<Expr<ExprTree>> for ExprTree {
 }
 
 impl Into<Expr<ExprTree>> for ExprTree {
-    fn into(self) -> Expr<ExprTree> {
-        *self.inner
+    fn into(mut self) -> Expr<ExprTree> {
+        self.take_inner()
+    }
+}
+
+/// Clones the tree without recursion, so that deeply nested trees cannot overflow the stack.
+impl Clone for ExprTree {
+    fn clone(&self) -> Self {
+        // Trees waiting to be cloned, `true` if their operands are already cloned.
+        let mut stack = vec![(self, false)];
+        // Clones of trees, whose parents are not cloned yet.
+        let mut cloned: Vec<ExprTree> = Vec::new();
+        while let Some((tree, operands_cloned)) = stack.pop() {
+            if operands_cloned {
+                let mut operands = cloned
+                    .split_off(cloned.len() - tree.inner.arity())
+                    .into_iter();
+                let expr = tree
+                    .inner
+                    .map(|_| operands.next().expect("all operands are cloned"));
+                cloned.push(ExprTree::from(expr));
+            } else {
+                stack.push((tree, true));
+                stack.extend(tree.inner.children().rev().map(|operand| (operand, false)));
+            }
+        }
+        cloned.pop().expect("the tree is cloned last")
+    }
+}
+
+/// Drops the tree without recursion, so that deeply nested trees cannot overflow the stack.
+///
+/// Operations are moved out of their parents to a heap stack, so every subtree dropped by the compiler generated
+/// drop glue has only leaves as its operands.
+impl Drop for ExprTree {
+    fn drop(&mut self) {
+        let mut stack = Vec::new();
+        take_operations(&mut self.inner, &mut stack);
+        while let Some(mut expr) = stack.pop() {
+            take_operations(&mut expr, &mut stack);
+        }
+    }
+}
+
+/// Moves all operands of the expression, which are operations, to the stack.
+fn take_operations(expr: &mut Expr<ExprTree>, stack: &mut Vec<Expr<ExprTree>>) {
+    for operand in expr.children_mut() {
+        if operand.inner.arity() > 0 {
+            stack.push(operand.take_inner());
+        }
+    }
+}
+
+impl ExprStorage for ExprTree {
+    fn expr(&self) -> &Expr<ExprTree> {
+        &self.inner
+    }
+}
+
+impl ExprStorageMut for ExprTree {
+    fn expr_mut(&mut self) -> &mut Expr<ExprTree> {
+        &mut self.inner
     }
 }
@@ -12,6 +12,25 @@ use crate::{
 /// [Unsigned integer literal expression](https://github.com/jiricekcz/fef-specification/blob/main/expressions/Integer%20Literal.md) in FEF.
 ///
 /// Represents all unsigned integer literals in FEF.
+///
+/// # Examples
+/// Values are written with the smallest width that fits them:
+/// ```rust
+/// # use fef::v0::read::read_expression_into_tree;
+/// # use fef::v0::write::write_expression_tree;
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # use fef::v0::expr::ExprTree;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let tree = ExprTree::lit(u64::MAX);
+///
+/// let mut bytes = Vec::new();
+/// write_expression_tree(&mut bytes, &tree, &DEFAULT_CONFIG)?;
+/// assert_eq!(bytes, [0x3C, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+///
+/// assert_eq!(read_expression_into_tree(&mut bytes.as_slice(), &DEFAULT_CONFIG)?, tree);
+/// # Ok(())
+/// # }
+/// ```
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ExprUnsignedIntLiteral<S: Sized> {
     _marker: std::marker::PhantomData<S>,
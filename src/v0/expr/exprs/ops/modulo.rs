@@ -75,4 +75,8 @@ impl<S: Sized> BinaryOperationExpr<S> for ExprModulo<S> {
     fn rhs_mut(&mut self) -> &mut S {
         &mut self.rhs
     }
+
+    fn operands_mut(&mut self) -> (&mut S, &mut S) {
+        (&mut self.lhs, &mut self.rhs)
+    }
 }
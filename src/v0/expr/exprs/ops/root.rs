@@ -72,6 +72,10 @@ impl<S: Sized> BinaryOperationExpr<S> for ExprRoot<S> {
         &mut self.rhs
     }
 
+    fn operands_mut(&mut self) -> (&mut S, &mut S) {
+        (&mut self.lhs, &mut self.rhs)
+    }
+
     fn lhs_mut(&mut self) -> &mut S {
         &mut self.lhs
     }
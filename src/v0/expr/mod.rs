@@ -6,14 +6,39 @@
 //! That's why this library provides the [`ExprTree`] wrapper, which represents children as `Box<ExprTree>` - the most intuitive way to store a tree structure in memory.
 //! Unless you have a special use case, [`ExprTree`] is probably the type you want to use.
 
+mod build;
 mod expr;
 mod exprs;
+mod path;
 mod read_from;
 mod write_to;
 
+pub mod arena;
+pub mod canonical;
+pub mod cse;
+pub mod dag;
+pub mod derivative;
+pub mod egraph;
+pub mod eval;
+pub mod rewrite;
+pub mod simplify;
+pub mod typing;
+pub mod visit;
+
 pub mod error;
 pub mod traits;
 
 pub use expr::Expr;
 pub use expr::ExprTree;
 pub use exprs::*;
+pub use path::ExprPath;
+
+/// Items used by the expansion of the [`fef!`](crate::fef) macro, not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use super::build::{negated_literal, variable_id};
+}
+
+pub(crate) use build::{float_literal, is_value, literal_value, negate_literal, tree};
+pub(crate) use expr::Leaf;
+pub(crate) use write_to::write_expr;
@@ -0,0 +1,64 @@
+/// Position of an expression in an expression tree.
+///
+/// The path is the sequence of child indices leading from the root expression to the expression.
+/// The `lhs` of a binary operation is the child `0`, the `rhs` is the child `1` and the operand of a unary operation is the child `0`.
+/// The root expression has an empty path.
+///
+/// Paths are ordered lexicographically, so sorting paths orders the expressions the same way they are stored in a file.
+///
+/// # Examples
+/// ```rust
+/// # use fef::v0::expr::ExprPath;
+/// let path = ExprPath::root().child(1).child(0);
+///
+/// assert_eq!(path.indices(), &[1, 0]);
+/// assert_eq!(format!("{}", path), "/1/0");
+/// assert_eq!(format!("{}", ExprPath::root()), "/");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct ExprPath {
+    indices: Vec<usize>,
+}
+
+impl ExprPath {
+    /// Returns the path of the root expression.
+    pub fn root() -> Self {
+        Self::default()
+    }
+
+    /// Returns the path of the child with the given index of the expression at this path.
+    pub fn child(&self, index: usize) -> Self {
+        let mut indices = self.indices.clone();
+        indices.push(index);
+        Self { indices }
+    }
+
+    /// Returns the child indices leading from the root expression.
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Returns `true` if this is the path of the root expression.
+    pub fn is_root(&self) -> bool {
+        self.indices.is_empty()
+    }
+}
+
+impl From<Vec<usize>> for ExprPath {
+    fn from(indices: Vec<usize>) -> Self {
+        Self { indices }
+    }
+}
+
+/// Formats the path as the child indices separated by slashes, the root expression is `/`.
+impl std::fmt::Display for ExprPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.indices.is_empty() {
+            return write!(f, "/");
+        }
+        for index in &self.indices {
+            write!(f, "/{}", index)?;
+        }
+        Ok(())
+    }
+}
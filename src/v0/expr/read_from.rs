@@ -18,11 +18,8 @@ use crate::v0::{
 };
 
 use super::{
-    error::ExprReadWithComposerError,
-    traits::{
-        BinaryOperationExpr, Composer, PureExpr, TryReadFromWithComposer,
-        TryReadFromWithComposerAndLength, UnaryOperationExpr,
-    },
+    error::{ComposeError, ExprReadWithComposerError},
+    traits::{Composer, PureExpr, TryReadFromWithComposer, TryReadFromWithComposerAndLength},
     Expr, ExprAddition, ExprBinaryFloat32Literal, ExprBinaryFloat64Literal, ExprCube, ExprCubeRoot,
     ExprDivision, ExprFalseLiteral, ExprIntDivision, ExprIntRoot, ExprModulo, ExprMultiplication,
     ExprNegation, ExprPower, ExprReciprocal, ExprRoot, ExprSignedIntLiteral, ExprSquare,
@@ -76,53 +73,39 @@ macro_rules! impl_read_from_enum_expr {
     };
 }
 
-macro_rules! impl_read_from_unary_expr {
-    ($compose_function_name:ident, $compose_type:ty) => {
-        impl<R: ?Sized + Read, S: Sized, C: ?Sized + Config, CP: ?Sized + Composer<S>>
-            TryReadFromWithComposer<R, S, C, CP> for $compose_type
-        where
-            $compose_type: UnaryOperationExpr<S>,
-        {
-            fn try_read_with_composer(
-                byte_stream: &mut R,
-                config: &C,
-                composer: &mut CP,
-            ) -> Result<S, ExprReadWithComposerError<CP::Error>> {
-                let inner_expr = Expr::<S>::try_read_with_composer(byte_stream, config, composer)?;
-                let expr = <$compose_type>::from(inner_expr);
-                let composed = composer.$compose_function_name(expr);
-                match composed {
-                    Ok(value) => Ok(value),
-                    Err(error) => Err(ExprReadWithComposerError::ComposeError(error)),
-                }
-            }
-        }
-    };
+/// Composes a unary operation from its read operand.
+type ComposeUnary<S, CP> = fn(&mut CP, S) -> Result<S, ComposeError<<CP as Composer<S>>::Error>>;
+
+/// Composes a binary operation from its read operands.
+type ComposeBinary<S, CP> =
+    fn(&mut CP, S, S) -> Result<S, ComposeError<<CP as Composer<S>>::Error>>;
+
+/// An operation, whose operands are being read.
+enum PendingOperation<S: Sized, CP: ?Sized + Composer<S>> {
+    Unary(ComposeUnary<S, CP>),
+    /// Binary operation and its lhs, if it has already been read.
+    Binary(ComposeBinary<S, CP>, Option<S>),
 }
 
-macro_rules! impl_read_from_binary_expr {
-    ($compose_function_name:ident, $compose_type:ty) => {
-        impl<R: ?Sized + Read, S: Sized, C: ?Sized + Config, CP: ?Sized + Composer<S>>
-            TryReadFromWithComposer<R, S, C, CP> for $compose_type
-        where
-            $compose_type: BinaryOperationExpr<S>,
-        {
-            fn try_read_with_composer(
-                byte_stream: &mut R,
-                config: &C,
-                composer: &mut CP,
-            ) -> Result<S, ExprReadWithComposerError<CP::Error>> {
-                let lhs = Expr::<S>::try_read_with_composer(byte_stream, config, composer)?;
-                let rhs = Expr::<S>::try_read_with_composer(byte_stream, config, composer)?;
-                let expr = <$compose_type>::from((lhs, rhs));
-                let composed = composer.$compose_function_name(expr);
-                match composed {
-                    Ok(value) => Ok(value),
-                    Err(error) => Err(ExprReadWithComposerError::ComposeError(error)),
-                }
-            }
-        }
-    };
+/// Pushes a unary operation to the pending operations and continues with reading its operand.
+macro_rules! push_unary {
+    ($pending:ident, $compose_function_name:ident, $compose_type:ident) => {{
+        $pending.push(PendingOperation::Unary(|composer, inner| {
+            composer.$compose_function_name($compose_type::from(inner))
+        }));
+        continue;
+    }};
+}
+
+/// Pushes a binary operation to the pending operations and continues with reading its operands.
+macro_rules! push_binary {
+    ($pending:ident, $compose_function_name:ident, $compose_type:ident) => {{
+        $pending.push(PendingOperation::Binary(
+            |composer, lhs, rhs| composer.$compose_function_name($compose_type::from((lhs, rhs))),
+            None,
+        ));
+        continue;
+    }};
 }
 
 impl_read_from_pure_expr!(compose_true_literal, ExprTrueLiteral<S>);
@@ -130,23 +113,6 @@ impl_read_from_pure_expr!(compose_false_literal, ExprFalseLiteral<S>);
 
 impl_read_from_enum_expr!(compose_variable, ExprVariable<S>);
 
-impl_read_from_unary_expr!(compose_negation, ExprNegation<S>);
-impl_read_from_unary_expr!(compose_square_root, ExprSquareRoot<S>);
-impl_read_from_unary_expr!(compose_square, ExprSquare<S>);
-impl_read_from_unary_expr!(compose_cube_root, ExprCubeRoot<S>);
-impl_read_from_unary_expr!(compose_cube, ExprCube<S>);
-impl_read_from_unary_expr!(compose_reciprocal, ExprReciprocal<S>);
-
-impl_read_from_binary_expr!(compose_addition, ExprAddition<S>);
-impl_read_from_binary_expr!(compose_subtraction, ExprSubtraction<S>);
-impl_read_from_binary_expr!(compose_multiplication, ExprMultiplication<S>);
-impl_read_from_binary_expr!(compose_division, ExprDivision<S>);
-impl_read_from_binary_expr!(compose_int_division, ExprIntDivision<S>);
-impl_read_from_binary_expr!(compose_int_root, ExprIntRoot<S>);
-impl_read_from_binary_expr!(compose_root, ExprRoot<S>);
-impl_read_from_binary_expr!(compose_power, ExprPower<S>);
-impl_read_from_binary_expr!(compose_modulo, ExprModulo<S>);
-
 impl<R: ?Sized + Read, S: Sized, C: ?Sized + Config, CP: ?Sized + Composer<S>>
     TryReadFromWithComposerAndLength<R, S, C, CP> for ExprUnsignedIntLiteral<S>
 {
@@ -287,6 +253,9 @@ impl<R: ?Sized + Read, S: Sized, C: ?Sized + Config, CP: ?Sized + Composer<S>>
     }
 }
 
+/// Reads an expression without recursion, so that deeply nested expressions cannot overflow the stack.
+///
+/// Operations are kept on a heap stack until all of their operands are read and composed.
 impl<R: ?Sized + Read, S: Sized, C: ?Sized + Config, CP: ?Sized + Composer<S>>
     TryReadFromWithComposer<R, S, C, CP> for Expr<S>
 {
@@ -295,126 +264,129 @@ impl<R: ?Sized + Read, S: Sized, C: ?Sized + Config, CP: ?Sized + Composer<S>>
         config: &C,
         composer: &mut CP,
     ) -> Result<S, ExprReadWithComposerError<CP::Error>> {
-        let token = ExprToken::read_from(byte_stream, config)
-            .map_err(|error| ExprReadError::from(error))?;
-        Ok(match token {
-            ExprToken::Addition => {
-                ExprAddition::<S>::try_read_with_composer(byte_stream, config, composer)?.into()
-            }
-            ExprToken::BinaryFloatLiteral32 => {
-                ExprBinaryFloat32Literal::<S>::try_read_with_composer(
+        let mut pending: Vec<PendingOperation<S, CP>> = Vec::new();
+        loop {
+            let token = ExprToken::read_from(byte_stream, config)
+                .map_err(|error| ExprReadError::from(error))?;
+            let mut value = match token {
+                ExprToken::Addition => push_binary!(pending, compose_addition, ExprAddition),
+                ExprToken::Subtraction => {
+                    push_binary!(pending, compose_subtraction, ExprSubtraction)
+                }
+                ExprToken::Multiplication => {
+                    push_binary!(pending, compose_multiplication, ExprMultiplication)
+                }
+                ExprToken::Division => push_binary!(pending, compose_division, ExprDivision),
+                ExprToken::IntDivision => {
+                    push_binary!(pending, compose_int_division, ExprIntDivision)
+                }
+                ExprToken::Modulo => push_binary!(pending, compose_modulo, ExprModulo),
+                ExprToken::Power => push_binary!(pending, compose_power, ExprPower),
+                ExprToken::Root => push_binary!(pending, compose_root, ExprRoot),
+                ExprToken::IntRoot => push_binary!(pending, compose_int_root, ExprIntRoot),
+                ExprToken::Negation => push_unary!(pending, compose_negation, ExprNegation),
+                ExprToken::Square => push_unary!(pending, compose_square, ExprSquare),
+                ExprToken::Cube => push_unary!(pending, compose_cube, ExprCube),
+                ExprToken::SquareRoot => push_unary!(pending, compose_square_root, ExprSquareRoot),
+                ExprToken::CubeRoot => push_unary!(pending, compose_cube_root, ExprCubeRoot),
+                ExprToken::Reciprocal => push_unary!(pending, compose_reciprocal, ExprReciprocal),
+
+                ExprToken::BinaryFloatLiteral32 => {
+                    ExprBinaryFloat32Literal::<S>::try_read_with_composer(
+                        byte_stream,
+                        config,
+                        composer,
+                    )?
+                }
+                ExprToken::BinaryFloatLiteral64 => {
+                    ExprBinaryFloat64Literal::<S>::try_read_with_composer(
+                        byte_stream,
+                        config,
+                        composer,
+                    )?
+                }
+                ExprToken::TrueLiteral => {
+                    ExprTrueLiteral::<S>::try_read_with_composer(byte_stream, config, composer)?
+                }
+                ExprToken::FalseLiteral => {
+                    ExprFalseLiteral::<S>::try_read_with_composer(byte_stream, config, composer)?
+                }
+                ExprToken::Variable => {
+                    ExprVariable::<S>::try_read_with_composer(byte_stream, config, composer)?
+                }
+
+                ExprToken::SignedIntLiteral8 => ExprSignedIntLiteral::<S>::try_read_with_composer(
                     byte_stream,
                     config,
                     composer,
-                )?
-                .into()
-            }
-            ExprToken::BinaryFloatLiteral64 => {
-                ExprBinaryFloat64Literal::<S>::try_read_with_composer(
+                    1,
+                )?,
+                ExprToken::SignedIntLiteral16 => ExprSignedIntLiteral::<S>::try_read_with_composer(
                     byte_stream,
                     config,
                     composer,
-                )?
-                .into()
-            }
-            ExprToken::Cube => {
-                ExprCube::<S>::try_read_with_composer(byte_stream, config, composer)?.into()
-            }
-            ExprToken::CubeRoot => {
-                ExprCubeRoot::<S>::try_read_with_composer(byte_stream, config, composer)?.into()
-            }
-            ExprToken::Division => {
-                ExprDivision::<S>::try_read_with_composer(byte_stream, config, composer)?.into()
-            }
-            ExprToken::FalseLiteral => {
-                ExprFalseLiteral::<S>::try_read_with_composer(byte_stream, config, composer)?.into()
-            }
-            ExprToken::IntDivision => {
-                ExprIntDivision::<S>::try_read_with_composer(byte_stream, config, composer)?.into()
-            }
-            ExprToken::IntRoot => {
-                ExprIntRoot::<S>::try_read_with_composer(byte_stream, config, composer)?.into()
-            }
-            ExprToken::Modulo => {
-                ExprModulo::<S>::try_read_with_composer(byte_stream, config, composer)?.into()
-            }
-            ExprToken::Multiplication => {
-                ExprMultiplication::<S>::try_read_with_composer(byte_stream, config, composer)?
-                    .into()
-            }
-            ExprToken::Negation => {
-                ExprNegation::<S>::try_read_with_composer(byte_stream, config, composer)?.into()
-            }
-            ExprToken::Power => {
-                ExprPower::<S>::try_read_with_composer(byte_stream, config, composer)?.into()
-            }
-            ExprToken::Reciprocal => {
-                ExprReciprocal::<S>::try_read_with_composer(byte_stream, config, composer)?.into()
-            }
-            ExprToken::Root => {
-                ExprRoot::<S>::try_read_with_composer(byte_stream, config, composer)?.into()
-            }
-            ExprToken::Square => {
-                ExprSquare::<S>::try_read_with_composer(byte_stream, config, composer)?.into()
-            }
-            ExprToken::SquareRoot => {
-                ExprSquareRoot::<S>::try_read_with_composer(byte_stream, config, composer)?.into()
-            }
-            ExprToken::Subtraction => {
-                ExprSubtraction::<S>::try_read_with_composer(byte_stream, config, composer)?.into()
-            }
-            ExprToken::TrueLiteral => {
-                ExprTrueLiteral::<S>::try_read_with_composer(byte_stream, config, composer)?.into()
-            }
-            ExprToken::Variable => {
-                ExprVariable::<S>::try_read_with_composer(byte_stream, config, composer)?.into()
-            }
+                    2,
+                )?,
+                ExprToken::SignedIntLiteral32 => ExprSignedIntLiteral::<S>::try_read_with_composer(
+                    byte_stream,
+                    config,
+                    composer,
+                    4,
+                )?,
+                ExprToken::SignedIntLiteral64 => ExprSignedIntLiteral::<S>::try_read_with_composer(
+                    byte_stream,
+                    config,
+                    composer,
+                    8,
+                )?,
 
-            ExprToken::SignedIntLiteral8 => {
-                ExprSignedIntLiteral::<S>::try_read_with_composer(byte_stream, config, composer, 1)?
-                    .into()
-            }
-            ExprToken::SignedIntLiteral16 => {
-                ExprSignedIntLiteral::<S>::try_read_with_composer(byte_stream, config, composer, 2)?
-                    .into()
-            }
-            ExprToken::SignedIntLiteral32 => {
-                ExprSignedIntLiteral::<S>::try_read_with_composer(byte_stream, config, composer, 4)?
-                    .into()
-            }
-            ExprToken::SignedIntLiteral64 => {
-                ExprSignedIntLiteral::<S>::try_read_with_composer(byte_stream, config, composer, 8)?
-                    .into()
+                ExprToken::UnsignedIntLiteral8 => {
+                    ExprUnsignedIntLiteral::<S>::try_read_with_composer(
+                        byte_stream,
+                        config,
+                        composer,
+                        1,
+                    )?
+                }
+                ExprToken::UnsignedIntLiteral16 => {
+                    ExprUnsignedIntLiteral::<S>::try_read_with_composer(
+                        byte_stream,
+                        config,
+                        composer,
+                        2,
+                    )?
+                }
+                ExprToken::UnsignedIntLiteral32 => {
+                    ExprUnsignedIntLiteral::<S>::try_read_with_composer(
+                        byte_stream,
+                        config,
+                        composer,
+                        4,
+                    )?
+                }
+                ExprToken::UnsignedIntLiteral64 => {
+                    ExprUnsignedIntLiteral::<S>::try_read_with_composer(
+                        byte_stream,
+                        config,
+                        composer,
+                        8,
+                    )?
+                }
+            };
+            // Compose all operations, whose last operand has just been read.
+            loop {
+                match pending.pop() {
+                    None => return Ok(value),
+                    Some(PendingOperation::Unary(compose)) => value = compose(composer, value)?,
+                    Some(PendingOperation::Binary(compose, None)) => {
+                        pending.push(PendingOperation::Binary(compose, Some(value)));
+                        break;
+                    }
+                    Some(PendingOperation::Binary(compose, Some(lhs))) => {
+                        value = compose(composer, lhs, value)?
+                    }
+                }
             }
-
-            ExprToken::UnsignedIntLiteral8 => ExprUnsignedIntLiteral::<S>::try_read_with_composer(
-                byte_stream,
-                config,
-                composer,
-                1,
-            )?
-            .into(),
-            ExprToken::UnsignedIntLiteral16 => ExprUnsignedIntLiteral::<S>::try_read_with_composer(
-                byte_stream,
-                config,
-                composer,
-                2,
-            )?
-            .into(),
-            ExprToken::UnsignedIntLiteral32 => ExprUnsignedIntLiteral::<S>::try_read_with_composer(
-                byte_stream,
-                config,
-                composer,
-                4,
-            )?
-            .into(),
-            ExprToken::UnsignedIntLiteral64 => ExprUnsignedIntLiteral::<S>::try_read_with_composer(
-                byte_stream,
-                config,
-                composer,
-                8,
-            )?
-            .into(),
-        })
+        }
     }
 }
@@ -0,0 +1,92 @@
+use crate::v0::expr::{ExprPath, ExprTree};
+
+use super::{AppliedRewrite, RewriteOptions, Rewriting, Rule, RuleSet, Traversal};
+
+impl RuleSet {
+    /// Rewrites the expression by the rules until no rule applies or the iteration limit is reached.
+    ///
+    /// Every iteration is a single [traversal](Traversal) of the expression, which applies at most one rule to every expression.
+    /// The first rule in the order of the rule set which applies is used.
+    pub fn rewrite(&self, tree: &ExprTree, options: &RewriteOptions) -> Rewriting {
+        let mut rewriter = Rewriter {
+            rules: &self.rules,
+            path: Vec::new(),
+            applied: Vec::new(),
+            iteration: 0,
+        };
+        let mut tree = tree.clone();
+        let mut fixpoint = false;
+        while rewriter.iteration < options.max_iterations() {
+            rewriter.iteration += 1;
+            let applied = rewriter.applied.len();
+            tree = match options.traversal() {
+                Traversal::BottomUp => rewriter.bottom_up(&tree),
+                Traversal::TopDown => rewriter.top_down(&tree),
+            };
+            if rewriter.applied.len() == applied {
+                fixpoint = true;
+                break;
+            }
+        }
+        Rewriting {
+            tree,
+            applied: rewriter.applied,
+            iterations: rewriter.iteration,
+            fixpoint,
+        }
+    }
+}
+
+struct Rewriter<'a> {
+    rules: &'a [Rule],
+    path: Vec<usize>,
+    applied: Vec<AppliedRewrite>,
+    iteration: usize,
+}
+
+impl Rewriter<'_> {
+    /// Applies the first applicable rule to the expression.
+    fn apply(&mut self, tree: &ExprTree) -> Option<ExprTree> {
+        self.rules
+            .iter()
+            .enumerate()
+            .find_map(|(index, rule)| Some((index, rule.apply(tree)?)))
+            .map(|(rule, rewritten)| {
+                self.applied.push(AppliedRewrite {
+                    rule,
+                    path: ExprPath::from(self.path.clone()),
+                    iteration: self.iteration,
+                });
+                rewritten
+            })
+    }
+
+    /// Rebuilds the expression with its operands rewritten by `rewrite`.
+    fn rewrite_operands(
+        &mut self,
+        tree: &ExprTree,
+        rewrite: fn(&mut Self, &ExprTree) -> ExprTree,
+    ) -> ExprTree {
+        if tree.inner().arity() == 0 {
+            return tree.clone();
+        }
+        let mut index = 0;
+        ExprTree::from(tree.inner().map(|operand| {
+            self.path.push(index);
+            let rewritten = rewrite(self, operand);
+            self.path.pop();
+            index += 1;
+            rewritten
+        }))
+    }
+
+    fn bottom_up(&mut self, tree: &ExprTree) -> ExprTree {
+        let tree = self.rewrite_operands(tree, Self::bottom_up);
+        self.apply(&tree).unwrap_or(tree)
+    }
+
+    fn top_down(&mut self, tree: &ExprTree) -> ExprTree {
+        let tree = self.apply(tree).unwrap_or_else(|| tree.clone());
+        self.rewrite_operands(&tree, Self::top_down)
+    }
+}
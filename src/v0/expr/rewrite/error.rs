@@ -0,0 +1,50 @@
+//! Error types for rewrite rules.
+
+use thiserror::Error;
+
+use crate::v0::text::Span;
+
+/// Error of the [rule parser](super::RuleSet::parse).
+#[derive(Debug, Error, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum RuleParseError {
+    #[error("unexpected character '{character}' at {span}")]
+    UnexpectedCharacter { character: char, span: Span },
+    #[error("expected {expected}, but found {found} at {span}")]
+    UnexpectedToken {
+        expected: String,
+        found: String,
+        span: Span,
+    },
+    #[error("literal {literal} at {span} is out of range")]
+    LiteralOutOfRange { literal: String, span: Span },
+    #[error("unknown operation {name} at {span}")]
+    UnknownOperation { name: String, span: Span },
+    #[error("unknown predicate {name} at {span}")]
+    UnknownPredicate { name: String, span: Span },
+    #[error("operation {operation} at {span} takes {expected} operands, but {found} were given")]
+    ArgumentCount {
+        operation: String,
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+    /// The right-hand side or a condition uses a metavariable, which the left-hand side doesn't bind.
+    #[error("metavariable ?{name} at {span} is not bound by the left-hand side")]
+    UnboundMetavariable { name: String, span: Span },
+}
+
+impl RuleParseError {
+    /// Returns the part of the text the error refers to.
+    pub fn span(&self) -> Span {
+        match self {
+            RuleParseError::UnexpectedCharacter { span, .. }
+            | RuleParseError::UnexpectedToken { span, .. }
+            | RuleParseError::LiteralOutOfRange { span, .. }
+            | RuleParseError::UnknownOperation { span, .. }
+            | RuleParseError::UnknownPredicate { span, .. }
+            | RuleParseError::ArgumentCount { span, .. }
+            | RuleParseError::UnboundMetavariable { span, .. } => *span,
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! Rewriting of expressions by rules written in a small pattern language.
+//!
+//! A [`RuleSet`] is [parsed](RuleSet::parse) from a text of rules, one rule per line or separated by `;`. Each rule rewrites expressions
+//! matching its left-hand side pattern to its right-hand side, if all of its side conditions hold:
+//!
+//! ```text
+//! # Comments start with '#'.
+//! square: Power(?a, 2) => Square(?a)
+//! Multiplication(?a, ?n:int) => ?a if ?n == 1
+//! Division(?a, ?b) => Multiplication(?a, Reciprocal(?b)) if ?b != 0 and ?b != 1
+//! ```
+//!
+//! # Patterns
+//! * Operations are written by the names of the [`Expr`](crate::v0::expr::Expr) variants with their operands in parentheses,
+//!   e.g. `Addition(?a, ?b)` or `SquareRoot(?a)`.
+//! * Metavariables `?name` match any expression. All occurrences of a metavariable have to match equal expressions.
+//!   A metavariable may be restricted by a predicate, e.g. `?n:int`:
+//!   * `int` matches integer literals, `float` matches float literals and `literal` matches both,
+//!   * `bool` matches `true` and `false`, `var` matches variables.
+//! * Number literals match literals of the same kind and value regardless of their encoding, e.g. `2` matches an unsigned
+//!   as well as a signed integer literal `2`, but not the float `2.0`. Float literals are compared including the sign of zero.
+//! * `true`, `false` and `Variable(id)` match the boolean literals and the variable with the identifier.
+//!
+//! The right-hand side is written the same way, but its metavariables have no predicates and have to be bound by the left-hand side.
+//! Integer literals are created as unsigned literals if they are not negative, otherwise as signed literals. Float literals are
+//! created as 64-bit floats, unless they have the suffix `f32`, e.g. `0.5f32`.
+//!
+//! Literals and operations are matched exactly like by the [`Pattern`](crate::v0::expr::egraph::Pattern)s of the e-graph.
+//! The rule language still compiles to patterns of its own, because the e-graph has no use for what they add: predicates of metavariables
+//! and side conditions, which test the matched expressions themselves rather than the e-classes of equal expressions.
+//!
+//! # Side conditions
+//! Rules may end with `if` and conditions joined by `and`. A condition compares two metavariables or numbers by
+//! `==`, `!=`, `<`, `<=`, `>` or `>=`:
+//! * Number literals are compared by their value, integers with floats as well.
+//! * Other expressions are only equal if they are the same expression, and are not ordered, so e.g. `?a < 1` doesn't hold for a variable `?a`.
+//!
+//! A rule may be named by a prefix `name:`, which is shown by [`Rule::name`].
+//!
+//! # Rewriting
+//! [`RuleSet::rewrite`] traverses the expression [bottom-up or top-down](Traversal) and replaces every expression
+//! by the result of the first rule in the rule set which applies to it. The traversals are repeated until no rule applies,
+//! or the [iteration limit](RewriteOptions) is reached. Every applied rewrite is reported with the path of the rewritten expression.
+//!
+//! # Examples
+//! ```rust
+//! # use fef::v0::expr::{ExprPath, rewrite::{RewriteOptions, RuleSet}};
+//! # use fef::v0::text::{infix::{parse_infix, InfixPrinter, InfixStyle}, VariableNames};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let rules = RuleSet::parse("
+//!     square: Power(?a, 2) => Square(?a)
+//!     Multiplication(?a, ?n:int) => ?a if ?n == 1
+//!     halve: Division(?a, ?n:literal) => Multiplication(?a, 0.5) if ?n == 2
+//! ")?;
+//!
+//! let mut names = VariableNames::new();
+//! let tree = parse_infix("x^2 * 1 + y / 2", &mut names)?;
+//! let rewriting = rules.rewrite(&tree, &RewriteOptions::default());
+//!
+//! let printer = InfixPrinter::new(InfixStyle::Ascii, Some(&names));
+//! assert_eq!(printer.print(rewriting.tree()), "square(x) + y * 0.5");
+//! assert!(rewriting.reached_fixpoint());
+//!
+//! let first = &rewriting.applied()[0];
+//! assert_eq!(rules.rules()[first.rule()].name(), Some("square"));
+//! assert_eq!(first.path(), &ExprPath::root().child(0).child(0));
+//! # Ok(())
+//! # }
+//! ```
+
+mod apply;
+mod parse;
+mod pattern;
+
+pub mod error;
+
+use crate::v0::expr::{ExprPath, ExprTree};
+
+use error::RuleParseError;
+use pattern::{Condition, Pattern, Template};
+
+/// Rule rewriting expressions matching a pattern, see the [module documentation](self).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    name: Option<String>,
+    lhs: Pattern,
+    rhs: Template,
+    conditions: Vec<Condition>,
+    /// Number of metavariables of the rule.
+    slots: usize,
+}
+
+impl Rule {
+    /// Returns the name of the rule, if it has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Rewrites the expression, if it matches the rule and all side conditions hold.
+    ///
+    /// Only the expression itself is matched, not its operands.
+    pub fn apply(&self, tree: &ExprTree) -> Option<ExprTree> {
+        let mut bindings = vec![None; self.slots];
+        if !self.lhs.matches(tree, &mut bindings) {
+            return None;
+        }
+        if !self
+            .conditions
+            .iter()
+            .all(|condition| condition.holds(&bindings))
+        {
+            return None;
+        }
+        Some(self.rhs.instantiate(&bindings))
+    }
+}
+
+/// Ordered set of rules.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RuleSet {
+    rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    /// Parses rules separated by line breaks or `;`.
+    ///
+    /// For the syntax of rules, see the [module documentation](self).
+    pub fn parse(text: &str) -> Result<Self, RuleParseError> {
+        Ok(Self {
+            rules: parse::parse_rules(text)?,
+        })
+    }
+
+    /// Returns the rules in the order they are tried.
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Appends the rules of another rule set.
+    pub fn extend(&mut self, other: RuleSet) {
+        self.rules.extend(other.rules);
+    }
+}
+
+/// Order in which [`RuleSet::rewrite`] visits expressions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Traversal {
+    /// Operands are rewritten before the expression, so rules see rewritten operands.
+    #[default]
+    BottomUp,
+    /// The expression is rewritten before its operands, so the operands of the rewritten expression are visited.
+    TopDown,
+}
+
+/// Options of [`RuleSet::rewrite`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RewriteOptions {
+    traversal: Traversal,
+    max_iterations: usize,
+}
+
+impl RewriteOptions {
+    /// Creates options with the traversal and the maximum number of traversals of the expression.
+    pub fn new(traversal: Traversal, max_iterations: usize) -> Self {
+        Self {
+            traversal,
+            max_iterations,
+        }
+    }
+
+    /// Returns the order in which expressions are visited.
+    pub fn traversal(&self) -> Traversal {
+        self.traversal
+    }
+
+    /// Returns the maximum number of traversals of the expression.
+    pub fn max_iterations(&self) -> usize {
+        self.max_iterations
+    }
+}
+
+/// Bottom-up traversal with at most 100 iterations.
+impl Default for RewriteOptions {
+    fn default() -> Self {
+        Self::new(Traversal::BottomUp, 100)
+    }
+}
+
+/// Rule applied by [`RuleSet::rewrite`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AppliedRewrite {
+    rule: usize,
+    path: ExprPath,
+    iteration: usize,
+}
+
+impl AppliedRewrite {
+    /// Returns the index of the applied rule in the [rule set](RuleSet::rules).
+    pub fn rule(&self) -> usize {
+        self.rule
+    }
+
+    /// Returns the path of the rewritten expression in the expression as it was when the rule was applied.
+    pub fn path(&self) -> &ExprPath {
+        &self.path
+    }
+
+    /// Returns the iteration the rule was applied in, starting at `1`.
+    pub fn iteration(&self) -> usize {
+        self.iteration
+    }
+}
+
+/// Result of [`RuleSet::rewrite`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rewriting {
+    tree: ExprTree,
+    applied: Vec<AppliedRewrite>,
+    iterations: usize,
+    fixpoint: bool,
+}
+
+impl Rewriting {
+    /// Returns the rewritten expression.
+    pub fn tree(&self) -> &ExprTree {
+        &self.tree
+    }
+
+    /// Converts the result into the rewritten expression.
+    pub fn into_tree(self) -> ExprTree {
+        self.tree
+    }
+
+    /// Returns all applied rewrites in the order they were applied.
+    pub fn applied(&self) -> &[AppliedRewrite] {
+        &self.applied
+    }
+
+    /// Returns the number of traversals of the expression.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Returns `true` if no rule applies to the rewritten expression, `false` if the iteration limit stopped the rewriting.
+    pub fn reached_fixpoint(&self) -> bool {
+        self.fixpoint
+    }
+}
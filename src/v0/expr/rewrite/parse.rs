@@ -0,0 +1,568 @@
+//! Lexer and parser of the rule language.
+
+use std::{collections::HashMap, iter::Peekable, str::Chars};
+
+use crate::v0::{
+    expr::{
+        Expr, ExprBinaryFloat32Literal, ExprBinaryFloat64Literal, ExprFalseLiteral,
+        ExprSignedIntLiteral, ExprTrueLiteral, ExprUnsignedIntLiteral, ExprVariable,
+    },
+    raw::VariableLengthEnum,
+    text::{Position, Span},
+    tokens::ExprToken,
+};
+
+use super::{
+    error::RuleParseError,
+    pattern::{operation, Comparison, Condition, Number, Operand, Pattern, Predicate, Template},
+    Rule,
+};
+
+/// Operations by their names in the rule language, with the number of their operands.
+const OPERATIONS: &[(&str, ExprToken, usize)] = &[
+    ("Addition", ExprToken::Addition, 2),
+    ("Subtraction", ExprToken::Subtraction, 2),
+    ("Multiplication", ExprToken::Multiplication, 2),
+    ("Division", ExprToken::Division, 2),
+    ("IntDivision", ExprToken::IntDivision, 2),
+    ("Modulo", ExprToken::Modulo, 2),
+    ("Power", ExprToken::Power, 2),
+    ("Root", ExprToken::Root, 2),
+    ("IntRoot", ExprToken::IntRoot, 2),
+    ("Negation", ExprToken::Negation, 1),
+    ("Square", ExprToken::Square, 1),
+    ("Cube", ExprToken::Cube, 1),
+    ("SquareRoot", ExprToken::SquareRoot, 1),
+    ("CubeRoot", ExprToken::CubeRoot, 1),
+    ("Reciprocal", ExprToken::Reciprocal, 1),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number {
+        text: String,
+        is_float: bool,
+        suffix: Option<String>,
+    },
+    Identifier(String),
+    Metavariable(String),
+    Minus,
+    LeftParen,
+    RightParen,
+    Comma,
+    Colon,
+    Arrow,
+    Comparison(Comparison),
+    /// End of a rule, a line break or `;`.
+    Separator,
+    End,
+}
+
+/// Formats the token the way it is shown in error messages.
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Number { text, suffix, .. } => {
+                write!(f, "number {}{}", text, suffix.as_deref().unwrap_or(""))
+            }
+            Token::Identifier(name) => write!(f, "identifier {}", name),
+            Token::Metavariable(name) => write!(f, "metavariable ?{}", name),
+            Token::Minus => write!(f, "'-'"),
+            Token::LeftParen => write!(f, "'('"),
+            Token::RightParen => write!(f, "')'"),
+            Token::Comma => write!(f, "','"),
+            Token::Colon => write!(f, "':'"),
+            Token::Arrow => write!(f, "'=>'"),
+            Token::Comparison(comparison) => {
+                let text = match comparison {
+                    Comparison::Equal => "==",
+                    Comparison::NotEqual => "!=",
+                    Comparison::Less => "<",
+                    Comparison::LessOrEqual => "<=",
+                    Comparison::Greater => ">",
+                    Comparison::GreaterOrEqual => ">=",
+                };
+                write!(f, "'{}'", text)
+            }
+            Token::Separator => write!(f, "end of rule"),
+            Token::End => write!(f, "end of input"),
+        }
+    }
+}
+
+fn is_identifier_character(character: char) -> bool {
+    character.is_alphanumeric() || character == '_' || character == '-'
+}
+
+struct Lexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    position: Position,
+}
+
+impl Lexer<'_> {
+    fn bump(&mut self) -> Option<char> {
+        let character = self.chars.next()?;
+        self.position = self.position.advance(character);
+        Some(character)
+    }
+
+    fn bump_while(&mut self, text: &mut String, predicate: impl Fn(char) -> bool) {
+        while let Some(&character) = self.chars.peek() {
+            if !predicate(character) {
+                break;
+            }
+            text.push(character);
+            self.bump();
+        }
+    }
+
+    fn bump_if(&mut self, expected: char) -> bool {
+        if self.chars.peek() == Some(&expected) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Lexes the whole text, the last token is always [`Token::End`].
+    fn tokenize(mut self) -> Result<Vec<(Token, Span)>, RuleParseError> {
+        let mut tokens = Vec::new();
+        loop {
+            self.bump_while(&mut String::new(), |c| c.is_whitespace() && c != '\n');
+            if self.chars.peek() == Some(&'#') {
+                self.bump_while(&mut String::new(), |c| c != '\n');
+            }
+            let start = self.position;
+            let token = match self.chars.peek().copied() {
+                None => {
+                    tokens.push((Token::End, Span::new(start, start)));
+                    return Ok(tokens);
+                }
+                Some(character) if character.is_ascii_digit() => self.number(),
+                Some(character) if character.is_alphabetic() || character == '_' => {
+                    let mut name = String::new();
+                    self.bump_while(&mut name, is_identifier_character);
+                    Token::Identifier(name)
+                }
+                Some(character) => {
+                    self.bump();
+                    match character {
+                        '\n' | ';' => Token::Separator,
+                        '?' => {
+                            let mut name = String::new();
+                            self.bump_while(&mut name, |c| c.is_alphanumeric() || c == '_');
+                            if name.is_empty() {
+                                return Err(RuleParseError::UnexpectedCharacter {
+                                    character,
+                                    span: Span::new(start, self.position),
+                                });
+                            }
+                            Token::Metavariable(name)
+                        }
+                        '-' => Token::Minus,
+                        '(' => Token::LeftParen,
+                        ')' => Token::RightParen,
+                        ',' => Token::Comma,
+                        ':' => Token::Colon,
+                        '=' if self.bump_if('>') => Token::Arrow,
+                        '=' if self.bump_if('=') => Token::Comparison(Comparison::Equal),
+                        '!' if self.bump_if('=') => Token::Comparison(Comparison::NotEqual),
+                        '<' if self.bump_if('=') => Token::Comparison(Comparison::LessOrEqual),
+                        '<' => Token::Comparison(Comparison::Less),
+                        '>' if self.bump_if('=') => Token::Comparison(Comparison::GreaterOrEqual),
+                        '>' => Token::Comparison(Comparison::Greater),
+                        _ => {
+                            return Err(RuleParseError::UnexpectedCharacter {
+                                character,
+                                span: Span::new(start, self.position),
+                            })
+                        }
+                    }
+                }
+            };
+            tokens.push((token, Span::new(start, self.position)));
+        }
+    }
+
+    /// Lexes a number with an optional fraction, exponent and suffix.
+    fn number(&mut self) -> Token {
+        let mut text = String::new();
+        let mut is_float = false;
+        self.bump_while(&mut text, |c| c.is_ascii_digit());
+        if self.chars.peek() == Some(&'.') {
+            is_float = true;
+            text.push('.');
+            self.bump();
+            self.bump_while(&mut text, |c| c.is_ascii_digit());
+        }
+        if let Some(&exponent @ ('e' | 'E')) = self.chars.peek() {
+            let mut lookahead = self.chars.clone();
+            lookahead.next();
+            let has_sign = matches!(lookahead.peek(), Some('+' | '-'));
+            if has_sign {
+                lookahead.next();
+            }
+            if lookahead.peek().is_some_and(char::is_ascii_digit) {
+                is_float = true;
+                text.push(exponent);
+                self.bump();
+                if has_sign {
+                    text.extend(self.bump());
+                }
+                self.bump_while(&mut text, |c| c.is_ascii_digit());
+            }
+        }
+        let mut suffix = String::new();
+        self.bump_while(&mut suffix, |c| c.is_alphanumeric() || c == '_');
+        Token::Number {
+            text,
+            is_float,
+            suffix: (!suffix.is_empty()).then_some(suffix),
+        }
+    }
+}
+
+/// Literal of the rule language, with the encoding it is created with.
+enum Literal {
+    Integer(i128),
+    Float32(f32),
+    Float64(f64),
+}
+
+impl Literal {
+    fn number(&self) -> Number {
+        match self {
+            Literal::Integer(value) => Number::Integer(*value),
+            Literal::Float32(value) => Number::Float(*value as f64),
+            Literal::Float64(value) => Number::Float(*value),
+        }
+    }
+
+    /// Creates the literal expression, integers as unsigned literals if they are not negative.
+    fn expr<S: Sized>(&self) -> Expr<S> {
+        match self {
+            Literal::Integer(value) => match u64::try_from(*value) {
+                Ok(value) => ExprUnsignedIntLiteral::from(value).into(),
+                Err(_) => ExprSignedIntLiteral::from(*value as i64).into(),
+            },
+            Literal::Float32(value) => ExprBinaryFloat32Literal::from(*value).into(),
+            Literal::Float64(value) => ExprBinaryFloat64Literal::from(*value).into(),
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<(Token, Span)>,
+    index: usize,
+    /// Slots of the metavariables of the rule being parsed.
+    metavariables: HashMap<String, usize>,
+}
+
+/// Parses a text of rules separated by line breaks or `;`.
+pub(super) fn parse_rules(text: &str) -> Result<Vec<Rule>, RuleParseError> {
+    let tokens = Lexer {
+        chars: text.chars().peekable(),
+        position: Position::START,
+    }
+    .tokenize()?;
+    let mut parser = Parser {
+        tokens,
+        index: 0,
+        metavariables: HashMap::new(),
+    };
+    let mut rules = Vec::new();
+    loop {
+        match parser.peek() {
+            Token::End => return Ok(rules),
+            Token::Separator => parser.index += 1,
+            _ => rules.push(parser.rule()?),
+        }
+    }
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.index].0
+    }
+
+    fn span(&self) -> Span {
+        self.tokens[self.index].1
+    }
+
+    fn next(&mut self) -> (Token, Span) {
+        let token = self.tokens[self.index].clone();
+        if self.index + 1 < self.tokens.len() {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn unexpected(&self, expected: &str) -> RuleParseError {
+        RuleParseError::UnexpectedToken {
+            expected: expected.to_string(),
+            found: self.peek().to_string(),
+            span: self.span(),
+        }
+    }
+
+    fn expect(&mut self, token: Token, expected: &str) -> Result<Span, RuleParseError> {
+        if *self.peek() == token {
+            Ok(self.next().1)
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self.peek(), Token::Identifier(name) if name == keyword)
+    }
+
+    /// `[name ':'] pattern '=>' template ['if' condition ('and' condition)*]`
+    fn rule(&mut self) -> Result<Rule, RuleParseError> {
+        self.metavariables.clear();
+        let name = match (self.peek(), &self.tokens.get(self.index + 1)) {
+            (Token::Identifier(name), Some((Token::Colon, _))) => {
+                let name = name.clone();
+                self.index += 2;
+                Some(name)
+            }
+            _ => None,
+        };
+        let lhs = self.pattern()?;
+        self.expect(Token::Arrow, "'=>'")?;
+        let rhs = self.template()?;
+        let mut conditions = Vec::new();
+        if self.is_keyword("if") {
+            self.next();
+            conditions.push(self.condition()?);
+            while self.is_keyword("and") {
+                self.next();
+                conditions.push(self.condition()?);
+            }
+        }
+        match self.peek() {
+            Token::Separator | Token::End => {}
+            _ => return Err(self.unexpected("end of rule")),
+        }
+        Ok(Rule {
+            name,
+            lhs,
+            rhs,
+            conditions,
+            slots: self.metavariables.len(),
+        })
+    }
+
+    /// Parses a literal, optionally negated.
+    fn literal(&mut self) -> Result<Literal, RuleParseError> {
+        let start = self.span();
+        let negative = *self.peek() == Token::Minus;
+        if negative {
+            self.next();
+        }
+        let Token::Number { .. } = self.peek() else {
+            return Err(self.unexpected("a number"));
+        };
+        let (
+            Token::Number {
+                text,
+                is_float,
+                suffix,
+            },
+            span,
+        ) = self.next()
+        else {
+            unreachable!("checked to be a number");
+        };
+        let span = start.join(span);
+        let out_of_range = || RuleParseError::LiteralOutOfRange {
+            literal: format!("{}{}", if negative { "-" } else { "" }, text),
+            span,
+        };
+        let sign = if negative { -1.0 } else { 1.0 };
+        match suffix.as_deref() {
+            Some("f32") => text
+                .parse::<f32>()
+                .map(|value| Literal::Float32(sign as f32 * value))
+                .map_err(|_| out_of_range()),
+            Some("f64") => text
+                .parse::<f64>()
+                .map(|value| Literal::Float64(sign * value))
+                .map_err(|_| out_of_range()),
+            Some(suffix) => Err(RuleParseError::UnexpectedToken {
+                expected: "the suffix f32 or f64".to_string(),
+                found: format!("suffix {}", suffix),
+                span,
+            }),
+            None if is_float => text
+                .parse::<f64>()
+                .map(|value| Literal::Float64(sign * value))
+                .map_err(|_| out_of_range()),
+            None => {
+                let value = text.parse::<u64>().map_err(|_| out_of_range())? as i128;
+                let value = if negative { -value } else { value };
+                if value < i64::MIN as i128 {
+                    return Err(out_of_range());
+                }
+                Ok(Literal::Integer(value))
+            }
+        }
+    }
+
+    /// Parses the operands of an operation or another form after its name, checking their number.
+    fn arguments<T>(
+        &mut self,
+        name: &str,
+        name_span: Span,
+        expected: usize,
+        mut argument: impl FnMut(&mut Self) -> Result<T, RuleParseError>,
+    ) -> Result<Vec<T>, RuleParseError> {
+        self.expect(Token::LeftParen, "'('")?;
+        let mut arguments = Vec::new();
+        if *self.peek() != Token::RightParen {
+            arguments.push(argument(self)?);
+            while *self.peek() == Token::Comma {
+                self.next();
+                arguments.push(argument(self)?);
+            }
+        }
+        let end = self.expect(Token::RightParen, "',' or ')'")?;
+        if arguments.len() != expected {
+            return Err(RuleParseError::ArgumentCount {
+                operation: name.to_string(),
+                expected,
+                found: arguments.len(),
+                span: name_span.join(end),
+            });
+        }
+        Ok(arguments)
+    }
+
+    /// Parses the identifier of `Variable(id)`.
+    fn variable(&mut self, span: Span) -> Result<VariableLengthEnum, RuleParseError> {
+        let mut identifiers = self.arguments("Variable", span, 1, |parser| {
+            let span = parser.span();
+            match parser.literal()? {
+                Literal::Integer(value) if value >= 0 => {
+                    Ok(VariableLengthEnum::from(value as usize))
+                }
+                _ => Err(RuleParseError::UnexpectedToken {
+                    expected: "an identifier of a variable".to_string(),
+                    found: "a number".to_string(),
+                    span,
+                }),
+            }
+        })?;
+        Ok(identifiers.remove(0))
+    }
+
+    /// Parses the name of an operation and returns its token and number of operands.
+    fn operation(&mut self, name: &str, span: Span) -> Result<(ExprToken, usize), RuleParseError> {
+        OPERATIONS
+            .iter()
+            .find(|(operation, _, _)| *operation == name)
+            .map(|(_, token, arity)| (*token, *arity))
+            .ok_or_else(|| RuleParseError::UnknownOperation {
+                name: name.to_string(),
+                span,
+            })
+    }
+
+    /// Parses a boolean literal, a variable or an operation whose operands are parsed by `operand`.
+    fn expr<S: Sized>(
+        &mut self,
+        name: String,
+        operand: fn(&mut Self) -> Result<S, RuleParseError>,
+    ) -> Result<Expr<S>, RuleParseError> {
+        let (_, span) = self.next();
+        match name.as_str() {
+            "true" => Ok(ExprTrueLiteral::default().into()),
+            "false" => Ok(ExprFalseLiteral::default().into()),
+            "Variable" => Ok(ExprVariable::from(self.variable(span)?).into()),
+            _ => {
+                let (token, arity) = self.operation(&name, span)?;
+                let operands = self.arguments(&name, span, arity, operand)?;
+                Ok(operation(token, operands))
+            }
+        }
+    }
+
+    fn pattern(&mut self) -> Result<Pattern, RuleParseError> {
+        match self.peek().clone() {
+            Token::Metavariable(name) => {
+                self.next();
+                let predicate = if *self.peek() == Token::Colon {
+                    self.next();
+                    let Token::Identifier(predicate) = self.peek().clone() else {
+                        return Err(self.unexpected("a predicate"));
+                    };
+                    let (_, span) = self.next();
+                    Some(Predicate::from_name(&predicate).ok_or(
+                        RuleParseError::UnknownPredicate {
+                            name: predicate,
+                            span,
+                        },
+                    )?)
+                } else {
+                    None
+                };
+                let slots = self.metavariables.len();
+                let slot = *self.metavariables.entry(name).or_insert(slots);
+                Ok(Pattern::Metavariable { slot, predicate })
+            }
+            Token::Number { .. } | Token::Minus => {
+                Ok(Pattern::Expr(Box::new(self.literal()?.expr())))
+            }
+            Token::Identifier(name) => Ok(Pattern::Expr(Box::new(self.expr(name, Self::pattern)?))),
+            _ => Err(self.unexpected("a pattern")),
+        }
+    }
+
+    fn bound_metavariable(&mut self) -> Result<usize, RuleParseError> {
+        let (token, span) = self.next();
+        let Token::Metavariable(name) = token else {
+            unreachable!("called on a metavariable token");
+        };
+        self.metavariables
+            .get(&name)
+            .copied()
+            .ok_or(RuleParseError::UnboundMetavariable { name, span })
+    }
+
+    fn template(&mut self) -> Result<Template, RuleParseError> {
+        match self.peek().clone() {
+            Token::Metavariable(_) => Ok(Template::Metavariable(self.bound_metavariable()?)),
+            Token::Number { .. } | Token::Minus => {
+                Ok(Template::Expr(Box::new(self.literal()?.expr())))
+            }
+            Token::Identifier(name) => {
+                Ok(Template::Expr(Box::new(self.expr(name, Self::template)?)))
+            }
+            _ => Err(self.unexpected("an expression")),
+        }
+    }
+
+    fn condition_operand(&mut self) -> Result<Operand, RuleParseError> {
+        match self.peek() {
+            Token::Metavariable(_) => Ok(Operand::Metavariable(self.bound_metavariable()?)),
+            Token::Number { .. } | Token::Minus => Ok(Operand::Number(self.literal()?.number())),
+            _ => Err(self.unexpected("a metavariable or a number")),
+        }
+    }
+
+    /// `operand comparison operand`
+    fn condition(&mut self) -> Result<Condition, RuleParseError> {
+        let lhs = self.condition_operand()?;
+        let Token::Comparison(comparison) = *self.peek() else {
+            return Err(self.unexpected("a comparison"));
+        };
+        self.next();
+        let rhs = self.condition_operand()?;
+        Ok(Condition {
+            lhs,
+            comparison,
+            rhs,
+        })
+    }
+}
@@ -0,0 +1,233 @@
+//! Compiled form of rules and their matching against expression trees.
+
+use crate::v0::{
+    expr::{
+        egraph::matches_operation, Expr, ExprAddition, ExprCube, ExprCubeRoot, ExprDivision,
+        ExprIntDivision, ExprIntRoot, ExprModulo, ExprMultiplication, ExprNegation, ExprPower,
+        ExprReciprocal, ExprRoot, ExprSquare, ExprSquareRoot, ExprSubtraction, ExprTree,
+    },
+    tokens::ExprToken,
+};
+
+/// Metavariables bound by a match, indexed by their slots.
+pub(super) type Bindings<'a> = Vec<Option<&'a ExprTree>>;
+
+/// Kind of expressions a metavariable matches.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) enum Predicate {
+    Integer,
+    Float,
+    Literal,
+    Boolean,
+    Variable,
+}
+
+impl Predicate {
+    pub(super) fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "int" => Some(Predicate::Integer),
+            "float" => Some(Predicate::Float),
+            "literal" => Some(Predicate::Literal),
+            "bool" => Some(Predicate::Boolean),
+            "var" => Some(Predicate::Variable),
+            _ => None,
+        }
+    }
+
+    fn test(&self, tree: &ExprTree) -> bool {
+        match (self, number(tree)) {
+            (Predicate::Integer, Some(Number::Integer(_))) => true,
+            (Predicate::Float, Some(Number::Float(_))) => true,
+            (Predicate::Literal, Some(_)) => true,
+            (Predicate::Boolean, _) => {
+                matches!(tree.inner(), Expr::TrueLiteral(_) | Expr::FalseLiteral(_))
+            }
+            (Predicate::Variable, _) => matches!(tree.inner(), Expr::Variable(_)),
+            _ => false,
+        }
+    }
+}
+
+/// Value of a number literal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Number {
+    Integer(i128),
+    Float(f64),
+}
+
+impl Number {
+    fn as_f64(&self) -> f64 {
+        match self {
+            Number::Integer(value) => *value as f64,
+            Number::Float(value) => *value,
+        }
+    }
+
+    fn partial_cmp(&self, other: &Number) -> Option<std::cmp::Ordering> {
+        match (self, other) {
+            (Number::Integer(lhs), Number::Integer(rhs)) => Some(lhs.cmp(rhs)),
+            _ => self.as_f64().partial_cmp(&other.as_f64()),
+        }
+    }
+}
+
+pub(super) fn number(tree: &ExprTree) -> Option<Number> {
+    match tree.inner() {
+        Expr::UnsignedIntLiteral(literal) => Some(Number::Integer(literal.value as i128)),
+        Expr::SignedIntLiteral(literal) => Some(Number::Integer(literal.value as i128)),
+        Expr::BinaryFloat32Literal(literal) => Some(Number::Float(literal.value as f64)),
+        Expr::BinaryFloat64Literal(literal) => Some(Number::Float(literal.value)),
+        _ => None,
+    }
+}
+
+/// Creates the operation of the token with the operands.
+///
+/// Panics if the token is not an operation or the number of operands doesn't match,
+/// which the [parser](super::parse) rules out.
+pub(super) fn operation<S: Sized>(token: ExprToken, operands: Vec<S>) -> Expr<S> {
+    let mut operands = operands.into_iter();
+    let mut operand = || operands.next().expect("operation has enough operands");
+    match token {
+        ExprToken::Addition => ExprAddition::from((operand(), operand())).into(),
+        ExprToken::Subtraction => ExprSubtraction::from((operand(), operand())).into(),
+        ExprToken::Multiplication => ExprMultiplication::from((operand(), operand())).into(),
+        ExprToken::Division => ExprDivision::from((operand(), operand())).into(),
+        ExprToken::IntDivision => ExprIntDivision::from((operand(), operand())).into(),
+        ExprToken::Modulo => ExprModulo::from((operand(), operand())).into(),
+        ExprToken::Power => ExprPower::from((operand(), operand())).into(),
+        ExprToken::Root => ExprRoot::from((operand(), operand())).into(),
+        ExprToken::IntRoot => ExprIntRoot::from((operand(), operand())).into(),
+        ExprToken::Negation => ExprNegation::from(operand()).into(),
+        ExprToken::Square => ExprSquare::from(operand()).into(),
+        ExprToken::Cube => ExprCube::from(operand()).into(),
+        ExprToken::SquareRoot => ExprSquareRoot::from(operand()).into(),
+        ExprToken::CubeRoot => ExprCubeRoot::from(operand()).into(),
+        ExprToken::Reciprocal => ExprReciprocal::from(operand()).into(),
+        _ => unreachable!("{} is not an operation", token),
+    }
+}
+
+/// Left-hand side of a rule.
+///
+/// Expressions are matched the same way as by the patterns of the [e-graph](crate::v0::expr::egraph::Pattern),
+/// see the [module documentation](super#patterns) for why they are a separate type.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Pattern {
+    /// Matches any expression satisfying the predicate. Repeated metavariables match equal expressions.
+    Metavariable {
+        slot: usize,
+        predicate: Option<Predicate>,
+    },
+    /// Matches expressions of the same operation or literals of the same value, whose operands match the operand patterns.
+    Expr(Box<Expr<Pattern>>),
+}
+
+impl Pattern {
+    /// Matches the pattern against the expression, binding the metavariables.
+    pub(super) fn matches<'a>(&self, tree: &'a ExprTree, bindings: &mut Bindings<'a>) -> bool {
+        match self {
+            Pattern::Metavariable { slot, predicate } => {
+                if predicate.is_some_and(|predicate| !predicate.test(tree)) {
+                    return false;
+                }
+                match bindings[*slot] {
+                    Some(bound) => bound == tree,
+                    None => {
+                        bindings[*slot] = Some(tree);
+                        true
+                    }
+                }
+            }
+            Pattern::Expr(pattern) => {
+                let expr = tree.inner();
+                matches_operation(pattern, expr)
+                    && pattern
+                        .children()
+                        .zip(expr.children())
+                        .all(|(pattern, operand)| pattern.matches(operand, bindings))
+            }
+        }
+    }
+}
+
+/// Right-hand side of a rule.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Template {
+    Metavariable(usize),
+    /// Expression with templates as operands.
+    Expr(Box<Expr<Template>>),
+}
+
+impl Template {
+    /// Creates the expression with the metavariables substituted by their bindings.
+    pub(super) fn instantiate(&self, bindings: &Bindings<'_>) -> ExprTree {
+        match self {
+            Template::Metavariable(slot) => bindings[*slot]
+                .expect("metavariables of the right-hand side are bound")
+                .clone(),
+            Template::Expr(expr) => {
+                ExprTree::from(expr.map(|operand| operand.instantiate(bindings)))
+            }
+        }
+    }
+}
+
+/// Operand of a condition.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Operand {
+    Metavariable(usize),
+    Number(Number),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+/// Side condition of a rule.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Condition {
+    pub(super) lhs: Operand,
+    pub(super) comparison: Comparison,
+    pub(super) rhs: Operand,
+}
+
+impl Condition {
+    /// Evaluates the condition, see [side conditions](super#side-conditions).
+    pub(super) fn holds(&self, bindings: &Bindings<'_>) -> bool {
+        let tree = |operand: &Operand| match operand {
+            Operand::Metavariable(slot) => bindings[*slot],
+            Operand::Number(_) => None,
+        };
+        let value = |operand: &Operand| match operand {
+            Operand::Metavariable(slot) => bindings[*slot].and_then(number),
+            Operand::Number(number) => Some(*number),
+        };
+        let ordering = match (value(&self.lhs), value(&self.rhs)) {
+            (Some(lhs), Some(rhs)) => lhs.partial_cmp(&rhs),
+            _ => match (tree(&self.lhs), tree(&self.rhs), self.comparison) {
+                (Some(lhs), Some(rhs), Comparison::Equal) => return lhs == rhs,
+                (Some(lhs), Some(rhs), Comparison::NotEqual) => return lhs != rhs,
+                (_, _, Comparison::NotEqual) => return true,
+                _ => return false,
+            },
+        };
+        let Some(ordering) = ordering else {
+            return self.comparison == Comparison::NotEqual;
+        };
+        match self.comparison {
+            Comparison::Equal => ordering.is_eq(),
+            Comparison::NotEqual => ordering.is_ne(),
+            Comparison::Less => ordering.is_lt(),
+            Comparison::LessOrEqual => ordering.is_le(),
+            Comparison::Greater => ordering.is_gt(),
+            Comparison::GreaterOrEqual => ordering.is_ge(),
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! Algebraic simplification and constant folding of expressions.
+//!
+//! [`simplify`] rewrites an [`ExprTree`] from the leaves up, applying the [rules](SimplificationRule)
+//! allowed by the [mode](SimplificationMode) to every expression until none of them applies. The result holds the simplified tree
+//! and every [applied rule](AppliedRule) with the path of the expression it was applied to in the original tree.
+//!
+//! # Modes
+//! * [`Conservative`](SimplificationMode::Conservative) only applies rules which never change the result of the
+//!   [`FloatEvaluator`](crate::v0::expr::eval::FloatEvaluator), including signed zeros, NaNs and evaluation errors.
+//!   For example `x + 0` is kept, as `-0.0 + 0` is `+0.0`.
+//! * [`Aggressive`](SimplificationMode::Aggressive) also applies rules which are valid for real numbers, but may change the result of
+//!   floating-point evaluation. For example `x * 0` becomes `0`, even though `x` may be infinite, NaN or fail to evaluate.
+//!
+//! # Constant folding
+//! Operations on number literals are replaced by a literal of their value, computed by the [`FloatEvaluator`](crate::v0::expr::eval::FloatEvaluator).
+//! Operations which fail to evaluate, e.g. a division by zero, are kept. The literal respects the [type](super::typing::ExprType) of the operation:
+//! * Operations with an integer type are folded into an integer literal of their exact value. In the conservative mode, this only happens
+//!   if the literal evaluates to the same floating-point value as the operation, while the aggressive mode folds integers exactly
+//!   even if the floating-point evaluation rounds them.
+//! * Operations with a float type are folded into a 32-bit float literal if it represents the value exactly, otherwise into a 64-bit float literal.
+//!
+//! # Examples
+//! ```rust
+//! # use fef::v0::expr::simplify::{simplify, SimplificationMode, SimplificationRule};
+//! # use fef::v0::expr::ExprPath;
+//! # use fef::v0::text::{infix::{parse_infix, InfixPrinter, InfixStyle}, VariableNames};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut names = VariableNames::new();
+//! let tree = parse_infix("--(x * (2 + 3 - 4)) + 0 * y", &mut names)?;
+//! let printer = InfixPrinter::new(InfixStyle::Ascii, Some(&names));
+//!
+//! let conservative = simplify(&tree, SimplificationMode::Conservative);
+//! assert_eq!(printer.print(conservative.tree()), "x + 0 * y");
+//! assert_eq!(conservative.count(SimplificationRule::ConstantFolding), 2);
+//!
+//! let aggressive = simplify(&tree, SimplificationMode::Aggressive);
+//! assert_eq!(printer.print(aggressive.tree()), "x");
+//! assert_eq!(aggressive.applied_rules().last().unwrap().path(), &ExprPath::root());
+//! # Ok(())
+//! # }
+//! ```
+
+mod rewrite;
+
+pub use rewrite::simplify;
+
+use crate::v0::expr::{ExprPath, ExprTree};
+
+/// Set of rules applied by [`simplify`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum SimplificationMode {
+    /// Only rules which never change the result of floating-point evaluation.
+    #[default]
+    Conservative,
+    /// All rules valid for real numbers.
+    Aggressive,
+}
+
+/// Rewriting rule of the simplifier.
+///
+/// Rules marked as aggressive are only applied in the [aggressive mode](SimplificationMode::Aggressive).
+/// Rules about zero in the conservative mode only apply to the signed zero which keeps the result, see the individual rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum SimplificationRule {
+    /// An operation on number literals is replaced by a literal of its value, see [constant folding](self#constant-folding).
+    ConstantFolding,
+    /// `x + 0` and `0 + x` become `x`. In the conservative mode, only for the zero `-0.0`.
+    AdditionOfZero,
+    /// `x - 0` becomes `x`. In the conservative mode, only for the zero `+0`.
+    SubtractionOfZero,
+    /// `x * 1` and `1 * x` become `x`.
+    MultiplicationByOne,
+    /// `x / 1` becomes `x`.
+    DivisionByOne,
+    /// `x^1` becomes `x`.
+    ExponentOne,
+    /// `--x` becomes `x`.
+    DoubleNegation,
+    /// `x * 0` and `0 * x` become `0`. Aggressive.
+    MultiplicationByZero,
+    /// `0 / x` becomes `0`. Aggressive.
+    DivisionOfZero,
+    /// `x^0` and `1^x` become `1`. Aggressive.
+    TrivialPower,
+    /// `0 - x` becomes `-x`. Aggressive.
+    SubtractionFromZero,
+    /// `x * -1` and `-1 * x` become `-x`. Aggressive.
+    MultiplicationByMinusOne,
+    /// `x - x` becomes `0`. Aggressive.
+    SelfSubtraction,
+    /// `x / x` becomes `1`. Aggressive.
+    SelfDivision,
+    /// `recip(recip(x))` becomes `x`. Aggressive.
+    DoubleReciprocal,
+}
+
+impl SimplificationRule {
+    /// Returns `true` if the rule is only applied in the [aggressive mode](SimplificationMode::Aggressive).
+    pub fn is_aggressive(&self) -> bool {
+        matches!(
+            self,
+            SimplificationRule::MultiplicationByZero
+                | SimplificationRule::DivisionOfZero
+                | SimplificationRule::TrivialPower
+                | SimplificationRule::SubtractionFromZero
+                | SimplificationRule::MultiplicationByMinusOne
+                | SimplificationRule::SelfSubtraction
+                | SimplificationRule::SelfDivision
+                | SimplificationRule::DoubleReciprocal
+        )
+    }
+}
+
+/// Formats the rule as its lowercase name.
+impl std::fmt::Display for SimplificationRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            SimplificationRule::ConstantFolding => "constant folding",
+            SimplificationRule::AdditionOfZero => "addition of zero",
+            SimplificationRule::SubtractionOfZero => "subtraction of zero",
+            SimplificationRule::MultiplicationByOne => "multiplication by one",
+            SimplificationRule::DivisionByOne => "division by one",
+            SimplificationRule::ExponentOne => "exponent one",
+            SimplificationRule::DoubleNegation => "double negation",
+            SimplificationRule::MultiplicationByZero => "multiplication by zero",
+            SimplificationRule::DivisionOfZero => "division of zero",
+            SimplificationRule::TrivialPower => "trivial power",
+            SimplificationRule::SubtractionFromZero => "subtraction from zero",
+            SimplificationRule::MultiplicationByMinusOne => "multiplication by minus one",
+            SimplificationRule::SelfSubtraction => "self subtraction",
+            SimplificationRule::SelfDivision => "self division",
+            SimplificationRule::DoubleReciprocal => "double reciprocal",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Rule applied by [`simplify`] to the expression at a path of the original tree.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AppliedRule {
+    rule: SimplificationRule,
+    path: ExprPath,
+}
+
+impl AppliedRule {
+    /// Returns the applied rule.
+    pub fn rule(&self) -> SimplificationRule {
+        self.rule
+    }
+
+    /// Returns the path of the expression in the original tree the rule was applied to.
+    pub fn path(&self) -> &ExprPath {
+        &self.path
+    }
+}
+
+/// Result of simplifying an expression.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Simplification {
+    tree: ExprTree,
+    applied_rules: Vec<AppliedRule>,
+}
+
+impl Simplification {
+    /// Returns the simplified tree.
+    pub fn tree(&self) -> &ExprTree {
+        &self.tree
+    }
+
+    /// Converts the result into the simplified tree.
+    pub fn into_tree(self) -> ExprTree {
+        self.tree
+    }
+
+    /// Returns all applied rules in the order they were applied.
+    pub fn applied_rules(&self) -> &[AppliedRule] {
+        &self.applied_rules
+    }
+
+    /// Returns how many times a rule was applied.
+    pub fn count(&self, rule: SimplificationRule) -> usize {
+        self.applied_rules
+            .iter()
+            .filter(|applied| applied.rule == rule)
+            .count()
+    }
+
+    /// Returns `true` if no rule was applied, so the tree is unchanged.
+    pub fn is_unchanged(&self) -> bool {
+        self.applied_rules.is_empty()
+    }
+}
@@ -0,0 +1,385 @@
+use crate::v0::expr::{
+    eval::{evaluate_tree, FloatEvaluator, VariableBindings},
+    float_literal, is_value, literal_value,
+    traits::{BinaryOperationExpr, UnaryOperationExpr},
+    tree,
+    typing::{expr_type, ExprType},
+    Expr, ExprNegation, ExprPath, ExprSignedIntLiteral, ExprTree, ExprUnsignedIntLiteral,
+};
+
+use super::{AppliedRule, Simplification, SimplificationMode, SimplificationRule};
+
+/// Simplifies an [`ExprTree`] using the rules allowed by `mode`.
+///
+/// For an example, see the [module documentation](super).
+///
+/// The tree is traversed without recursion and rules move operands instead of cloning them, so deeply nested trees,
+/// which can be read from untrusted input, are simplified without overflowing the stack:
+/// ```rust
+/// # use fef::v0::read::read_expression_into_tree;
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # use fef::v0::expr::{Expr, simplify::{simplify, SimplificationMode, SimplificationRule}};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut bytes = vec![0x12]; // Multiplication
+/// bytes.extend([0x20; 100_000]); // Squares
+/// bytes.extend([0x04, 0x00, 0x38, 0x01]); // Variable 0, Number 1
+/// let tree = read_expression_into_tree(&mut &bytes[..], &DEFAULT_CONFIG)?;
+///
+/// let simplification = simplify(&tree, SimplificationMode::Conservative);
+/// assert!(matches!(simplification.tree().inner(), Expr::Square(_)));
+/// assert_eq!(simplification.applied_rules().len(), 1);
+/// assert_eq!(simplification.count(SimplificationRule::MultiplicationByOne), 1);
+/// # Ok(())
+/// # }
+/// ```
+pub fn simplify(tree: &ExprTree, mode: SimplificationMode) -> Simplification {
+    let mut simplifier = Simplifier {
+        mode,
+        path: Vec::new(),
+        applied_rules: Vec::new(),
+    };
+    let tree = simplifier.simplify(tree);
+    Simplification {
+        tree,
+        applied_rules: simplifier.applied_rules,
+    }
+}
+
+/// Returns the value of an integer literal.
+fn integer_value(tree: &ExprTree) -> Option<i128> {
+    match tree.inner() {
+        Expr::UnsignedIntLiteral(literal) => Some(literal.value as i128),
+        Expr::SignedIntLiteral(literal) => Some(literal.value as i128),
+        _ => None,
+    }
+}
+
+fn is_positive_zero(tree: &ExprTree) -> bool {
+    literal_value(tree).is_some_and(|value| value == 0.0 && value.is_sign_positive())
+}
+
+fn is_negative_zero(tree: &ExprTree) -> bool {
+    literal_value(tree).is_some_and(|value| value == 0.0 && value.is_sign_negative())
+}
+
+fn integer_literal(value: i128) -> Option<ExprTree> {
+    if let Ok(value) = u64::try_from(value) {
+        Some(tree(ExprUnsignedIntLiteral::from(value)))
+    } else if let Ok(value) = i64::try_from(value) {
+        Some(tree(ExprSignedIntLiteral::from(value)))
+    } else {
+        None
+    }
+}
+
+/// Quotient rounded towards negative infinity and the remainder with the sign of the divisor.
+fn floored_division(lhs: i128, rhs: i128) -> Option<(i128, i128)> {
+    let mut quotient = lhs.checked_div(rhs)?;
+    if lhs % rhs != 0 && (lhs < 0) != (rhs < 0) {
+        quotient -= 1;
+    }
+    Some((quotient, lhs - rhs * quotient))
+}
+
+/// Computes the value of an operation on integer literals exactly, if the operation has an integer type.
+fn exact_integer(expr: &Expr<ExprTree>) -> Option<i128> {
+    let binary = |lhs: &ExprTree, rhs: &ExprTree| Some((integer_value(lhs)?, integer_value(rhs)?));
+    match expr {
+        Expr::Addition(expr) => binary(expr.lhs(), expr.rhs()).and_then(|(l, r)| l.checked_add(r)),
+        Expr::Subtraction(expr) => {
+            binary(expr.lhs(), expr.rhs()).and_then(|(l, r)| l.checked_sub(r))
+        }
+        Expr::Multiplication(expr) => {
+            binary(expr.lhs(), expr.rhs()).and_then(|(l, r)| l.checked_mul(r))
+        }
+        Expr::IntDivision(expr) => binary(expr.lhs(), expr.rhs())
+            .and_then(|(l, r)| floored_division(l, r))
+            .map(|(quotient, _)| quotient),
+        Expr::Modulo(expr) => binary(expr.lhs(), expr.rhs())
+            .and_then(|(l, r)| floored_division(l, r))
+            .map(|(_, remainder)| remainder),
+        Expr::Negation(expr) => integer_value(expr.inner()).and_then(i128::checked_neg),
+        Expr::Square(expr) => integer_value(expr.inner()).and_then(|v| v.checked_mul(v)),
+        Expr::Cube(expr) => {
+            integer_value(expr.inner()).and_then(|v| v.checked_mul(v)?.checked_mul(v))
+        }
+        _ => None,
+    }
+}
+
+/// Expression replacing an expression matched by a rule, operands are moved out of the matched expression instead of being cloned.
+enum Rewrite {
+    /// The operand at the index.
+    Operand(usize),
+    /// Negation of the operand at the index.
+    NegatedOperand(usize),
+    /// The operand of the only operand, e.g. `x` of `-(-x)`.
+    InnerOperand,
+    /// A new expression.
+    Tree(ExprTree),
+}
+
+/// Moves the operand at the index out of the expression, leaving a leaf in its place.
+fn take_operand(expression: &mut ExprTree, index: usize) -> ExprTree {
+    let operand = expression
+        .inner_mut()
+        .children_mut()
+        .nth(index)
+        .expect("rules only refer to existing operands");
+    std::mem::replace(operand, tree(ExprUnsignedIntLiteral::from(0u64)))
+}
+
+struct Simplifier {
+    mode: SimplificationMode,
+    path: Vec<usize>,
+    applied_rules: Vec<AppliedRule>,
+}
+
+/// Step of the simplification, kept on a heap stack instead of the call stack.
+enum Step<'a> {
+    /// Schedules simplification of the operands of the expression. `operand` is the index of the expression in its parent.
+    Enter {
+        expression: &'a ExprTree,
+        operand: Option<usize>,
+    },
+    /// Rebuilds the expression from its simplified operands and simplifies it.
+    Exit { expression: &'a ExprTree },
+}
+
+impl Simplifier {
+    /// Simplifies the children of every expression before the expression itself, without recursion.
+    ///
+    /// Steps waiting to be done are kept on a heap stack, the next one on top,
+    /// and simplified operands, which their parent hasn't used yet, on another one.
+    fn simplify(&mut self, expression: &ExprTree) -> ExprTree {
+        let mut steps = vec![Step::Enter {
+            expression,
+            operand: None,
+        }];
+        let mut operands: Vec<ExprTree> = Vec::new();
+        while let Some(step) = steps.pop() {
+            match step {
+                Step::Enter {
+                    expression,
+                    operand,
+                } => {
+                    if let Some(operand) = operand {
+                        self.path.push(operand);
+                    }
+                    steps.push(Step::Exit { expression });
+                    // Operands are pushed in reverse, so that lhs is simplified first.
+                    let operands_start = steps.len();
+                    for (operand, expression) in expression.inner().children().enumerate() {
+                        steps.push(Step::Enter {
+                            expression,
+                            operand: Some(operand),
+                        });
+                    }
+                    steps[operands_start..].reverse();
+                }
+                Step::Exit { expression } => {
+                    let arity = expression.inner().arity();
+                    let simplified = if arity == 0 {
+                        expression.clone()
+                    } else {
+                        let mut simplified_operands =
+                            operands.split_off(operands.len() - arity).into_iter();
+                        let rebuilt = tree(expression.inner().map(|_| {
+                            simplified_operands
+                                .next()
+                                .expect("every operand is simplified before its parent")
+                        }));
+                        self.rewrite_all(rebuilt)
+                    };
+                    operands.push(simplified);
+                    // The path of the root is empty.
+                    self.path.pop();
+                }
+            }
+        }
+        operands.pop().expect("the root is simplified last")
+    }
+
+    /// Applies rules to an expression whose children are simplified, until no rule applies.
+    fn rewrite_all(&mut self, mut simplified: ExprTree) -> ExprTree {
+        while let Some((rule, rewrite)) = self.rewrite(&simplified) {
+            self.applied_rules.push(AppliedRule {
+                rule,
+                path: ExprPath::from(self.path.clone()),
+            });
+            simplified = match rewrite {
+                Rewrite::Operand(index) => take_operand(&mut simplified, index),
+                Rewrite::NegatedOperand(index) => {
+                    tree(ExprNegation::from(take_operand(&mut simplified, index)))
+                }
+                Rewrite::InnerOperand => take_operand(&mut take_operand(&mut simplified, 0), 0),
+                Rewrite::Tree(rewritten) => rewritten,
+            };
+        }
+        simplified
+    }
+
+    /// Applies the first applicable rule to an expression whose children are simplified.
+    fn rewrite(&self, expression: &ExprTree) -> Option<(SimplificationRule, Rewrite)> {
+        if let Some(folded) = self.fold(expression) {
+            return Some((SimplificationRule::ConstantFolding, Rewrite::Tree(folded)));
+        }
+        let aggressive = self.mode == SimplificationMode::Aggressive;
+        let is_zero = |tree: &ExprTree| is_value(tree, 0.0);
+        let rule = |rule: SimplificationRule, rewrite: Rewrite| Some((rule, rewrite));
+        match expression.inner() {
+            Expr::Addition(expr) => {
+                let (lhs, rhs) = (expr.lhs(), expr.rhs());
+                let is_neutral = |tree: &ExprTree| {
+                    if aggressive {
+                        is_zero(tree)
+                    } else {
+                        is_negative_zero(tree)
+                    }
+                };
+                if is_neutral(rhs) {
+                    rule(SimplificationRule::AdditionOfZero, Rewrite::Operand(0))
+                } else if is_neutral(lhs) {
+                    rule(SimplificationRule::AdditionOfZero, Rewrite::Operand(1))
+                } else {
+                    None
+                }
+            }
+            Expr::Subtraction(expr) => {
+                let (lhs, rhs) = (expr.lhs(), expr.rhs());
+                if is_positive_zero(rhs) || (aggressive && is_zero(rhs)) {
+                    rule(SimplificationRule::SubtractionOfZero, Rewrite::Operand(0))
+                } else if aggressive && lhs == rhs {
+                    rule(
+                        SimplificationRule::SelfSubtraction,
+                        Rewrite::Tree(tree(ExprUnsignedIntLiteral::from(0u64))),
+                    )
+                } else if aggressive && is_zero(lhs) {
+                    rule(
+                        SimplificationRule::SubtractionFromZero,
+                        Rewrite::NegatedOperand(1),
+                    )
+                } else {
+                    None
+                }
+            }
+            Expr::Multiplication(expr) => {
+                let (lhs, rhs) = (expr.lhs(), expr.rhs());
+                if is_value(rhs, 1.0) {
+                    rule(SimplificationRule::MultiplicationByOne, Rewrite::Operand(0))
+                } else if is_value(lhs, 1.0) {
+                    rule(SimplificationRule::MultiplicationByOne, Rewrite::Operand(1))
+                } else if aggressive && is_zero(rhs) {
+                    rule(
+                        SimplificationRule::MultiplicationByZero,
+                        Rewrite::Operand(1),
+                    )
+                } else if aggressive && is_zero(lhs) {
+                    rule(
+                        SimplificationRule::MultiplicationByZero,
+                        Rewrite::Operand(0),
+                    )
+                } else if aggressive && is_value(rhs, -1.0) {
+                    rule(
+                        SimplificationRule::MultiplicationByMinusOne,
+                        Rewrite::NegatedOperand(0),
+                    )
+                } else if aggressive && is_value(lhs, -1.0) {
+                    rule(
+                        SimplificationRule::MultiplicationByMinusOne,
+                        Rewrite::NegatedOperand(1),
+                    )
+                } else {
+                    None
+                }
+            }
+            Expr::Division(expr) => {
+                let (lhs, rhs) = (expr.lhs(), expr.rhs());
+                if is_value(rhs, 1.0) {
+                    rule(SimplificationRule::DivisionByOne, Rewrite::Operand(0))
+                } else if aggressive && is_zero(lhs) {
+                    rule(SimplificationRule::DivisionOfZero, Rewrite::Operand(0))
+                } else if aggressive && lhs == rhs {
+                    rule(
+                        SimplificationRule::SelfDivision,
+                        Rewrite::Tree(tree(ExprUnsignedIntLiteral::from(1u64))),
+                    )
+                } else {
+                    None
+                }
+            }
+            Expr::Power(expr) => {
+                let (lhs, rhs) = (expr.lhs(), expr.rhs());
+                if is_value(rhs, 1.0) {
+                    rule(SimplificationRule::ExponentOne, Rewrite::Operand(0))
+                } else if aggressive && (is_zero(rhs) || is_value(lhs, 1.0)) {
+                    rule(
+                        SimplificationRule::TrivialPower,
+                        Rewrite::Tree(tree(ExprUnsignedIntLiteral::from(1u64))),
+                    )
+                } else {
+                    None
+                }
+            }
+            Expr::Negation(expr) => match expr.inner().inner() {
+                Expr::Negation(_) => {
+                    rule(SimplificationRule::DoubleNegation, Rewrite::InnerOperand)
+                }
+                _ => None,
+            },
+            Expr::Reciprocal(expr) => match expr.inner().inner() {
+                Expr::Reciprocal(_) if aggressive => {
+                    rule(SimplificationRule::DoubleReciprocal, Rewrite::InnerOperand)
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Folds an operation on number literals into a literal, see [constant folding](super#constant-folding).
+    fn fold(&self, expression: &ExprTree) -> Option<ExprTree> {
+        let expr = expression.inner();
+        if expr.arity() == 0
+            || expr
+                .children()
+                .any(|operand| literal_value(operand).is_none())
+        {
+            return None;
+        }
+        let bindings = VariableBindings::new();
+        let value = evaluate_tree(expression, &mut FloatEvaluator::new(&bindings)).ok()?;
+        // The operands are number literals, whose types are known without type checking.
+        let operand_types: Vec<ExprType> = expr
+            .children()
+            .map(|operand| match integer_value(operand) {
+                Some(_) => ExprType::Integer,
+                None => ExprType::Float,
+            })
+            .collect();
+        match expr_type(expr, &operand_types) {
+            ExprType::Integer => {
+                let integer = if expr
+                    .children()
+                    .all(|operand| integer_value(operand).is_some())
+                {
+                    let exact = exact_integer(expr)?;
+                    if self.mode == SimplificationMode::Conservative
+                        && (exact as f64).to_bits() != value.to_bits()
+                    {
+                        return None;
+                    }
+                    exact
+                } else {
+                    if value.fract() != 0.0 || (value == 0.0 && value.is_sign_negative()) {
+                        return None;
+                    }
+                    value as i128
+                };
+                integer_literal(integer)
+            }
+            ExprType::Float => Some(float_literal(value)),
+            ExprType::Boolean | ExprType::Number => None,
+        }
+    }
+}
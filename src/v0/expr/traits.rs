@@ -146,6 +146,20 @@ pub trait BinaryOperationExpr<S: Sized>: Sealed + Into<(S, S)> + From<(S, S)> {
     /// *addition.rhs_mut() = 3;
     /// assert_eq!(addition.rhs(), &3);
     fn rhs_mut(&mut self) -> &mut S;
+
+    /// Returns mutable references to both sides of the binary operation at once.
+    ///
+    /// # Examples
+    /// Using addition of two [`isize`]s:
+    /// ```rust
+    /// # use fef::v0::expr::{ExprAddition, traits::BinaryOperationExpr};
+    /// let mut addition = ExprAddition::from((1, 2));
+    ///
+    /// let (lhs, rhs) = addition.operands_mut();
+    /// std::mem::swap(lhs, rhs);
+    /// assert_eq!(addition.lhs(), &2);
+    /// ```
+    fn operands_mut(&mut self) -> (&mut S, &mut S);
 }
 
 /// A trait for all unary operation expression objects.
@@ -207,6 +221,57 @@ pub trait UnaryOperationExpr<S: Sized>: Sealed + From<S> + AsRef<S> + AsMut<S> {
     fn into_inner(self) -> S;
 }
 
+/// Storage of child expressions, that gives direct access to the stored expression.
+///
+/// Unlike a [`Decomposer`], which can be used to access any storage type, this trait is implemented by the storage type itself.
+/// It is used to walk expressions using [visitors](crate::v0::expr::visit).
+///
+/// # Examples
+/// Implementing the trait for a reference counted version of the [`ExprTree`](crate::v0::expr::ExprTree):
+/// ```rust
+/// # use std::rc::Rc;
+/// # use fef::v0::expr::{Expr, traits::ExprStorage};
+/// struct ExprTreeRc {
+///     inner: Rc<Expr<ExprTreeRc>>,
+/// }
+///
+/// impl ExprStorage for ExprTreeRc {
+///     fn expr(&self) -> &Expr<ExprTreeRc> {
+///         &self.inner
+///     }
+/// }
+/// ```
+pub trait ExprStorage: Sized {
+    /// Returns a reference to the stored expression.
+    fn expr(&self) -> &Expr<Self>;
+}
+
+/// Storage of child expressions, that gives direct mutable access to the stored expression.
+pub trait ExprStorageMut: ExprStorage {
+    /// Returns a mutable reference to the stored expression.
+    fn expr_mut(&mut self) -> &mut Expr<Self>;
+}
+
+/// A Rust value, that can be stored in a literal expression.
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+/// Unsigned integers are stored in [`ExprUnsignedIntLiteral`], signed integers in [`ExprSignedIntLiteral`], [`f32`] in [`ExprBinaryFloat32Literal`],
+/// [`f64`] in [`ExprBinaryFloat64Literal`] and [`bool`] in [`ExprTrueLiteral`] or [`ExprFalseLiteral`].
+///
+/// # Examples
+/// ```rust
+/// # use fef::v0::expr::{Expr, ExprTree, traits::LiteralValue};
+/// let expr: Expr<ExprTree> = 3u8.into_expr();
+/// assert!(matches!(expr, Expr::UnsignedIntLiteral(_)));
+///
+/// let expr: Expr<ExprTree> = (-3i8).into_expr();
+/// assert!(matches!(expr, Expr::SignedIntLiteral(_)));
+/// ```
+pub trait LiteralValue: Sealed + Sized {
+    /// Converts the value into a literal expression.
+    fn into_expr<S: Sized>(self) -> Expr<S>;
+}
+
 macro_rules! compose_expr {
     ($name:ident, $type:ty) => {
         /// Composes this expression type into the storage type `S`.
@@ -0,0 +1,305 @@
+use crate::v0::{
+    expr::{
+        error::DecomposeError,
+        traits::{Decomposer, DecompositionRefContainer, ExprObj},
+        Expr, ExprPath, ExprTree,
+    },
+    tokens::ExprToken,
+    write::ExprTreeDecomposer,
+};
+
+use super::{error::TypeError, ExprType, TypeHints};
+
+/// Result of type checking an expression.
+///
+/// Holds the inferred type of every expression and all type errors found.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeCheck {
+    /// Types of all expressions, in the order they are stored in a file.
+    types: Vec<CheckedExpr>,
+    errors: Vec<TypeError>,
+}
+
+/// Inferred type of an expression and the shape of its subtree, which locates the expressions by their paths.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CheckedExpr {
+    ty: ExprType,
+    arity: usize,
+    /// Number of expressions of the subtree, this expression included.
+    size: usize,
+}
+
+impl TypeCheck {
+    /// Returns the type of the root expression.
+    pub fn root_type(&self) -> ExprType {
+        self.types[0].ty
+    }
+
+    /// Returns the type of the expression at the given path, or `None` if there is no expression at the path.
+    pub fn type_of(&self, path: &ExprPath) -> Option<ExprType> {
+        let mut index = 0;
+        for &child in path.indices() {
+            if child >= self.types[index].arity {
+                return None;
+            }
+            index += 1;
+            for _ in 0..child {
+                index += self.types[index].size;
+            }
+        }
+        Some(self.types[index].ty)
+    }
+
+    /// Returns an iterator over the paths and types of all expressions, in the order they are stored in a file.
+    pub fn iter(&self) -> impl Iterator<Item = (ExprPath, ExprType)> + '_ {
+        // Path of the next expression and the arities of its ancestors.
+        let mut path: Vec<usize> = Vec::new();
+        let mut arities: Vec<usize> = Vec::new();
+        self.types.iter().map(move |expr| {
+            let item = (ExprPath::from(path.clone()), expr.ty);
+            if expr.arity > 0 {
+                path.push(0);
+                arities.push(expr.arity);
+            } else {
+                // Moves to the next sibling of the closest ancestor, which has one.
+                while let (Some(child), Some(arity)) = (path.pop(), arities.pop()) {
+                    if child + 1 < arity {
+                        path.push(child + 1);
+                        arities.push(arity);
+                        break;
+                    }
+                }
+            }
+            item
+        })
+    }
+
+    /// Returns all type errors, in the order the ill-typed expressions are stored in a file.
+    pub fn errors(&self) -> &[TypeError] {
+        &self.errors
+    }
+
+    /// Returns `true` if no type errors were found.
+    pub fn is_well_typed(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Converts the result into the type of the root expression, or all type errors if any were found.
+    pub fn into_result(self) -> Result<ExprType, Vec<TypeError>> {
+        if self.errors.is_empty() {
+            Ok(self.root_type())
+        } else {
+            Err(self.errors)
+        }
+    }
+}
+
+/// Type checks an expression using a [`Decomposer`] to access child expressions.
+///
+/// Infers the type of every expression stored in `storage` using the [typing rules](super#typing-rules).
+/// If the expression is an [`ExprTree`], consider using [`type_check_tree`] instead.
+///
+/// The expression is checked without recursion, so deeply nested expressions cannot overflow the stack.
+///
+/// # Type parameters
+///
+/// - `S`: The storage type of the expression.
+/// - `DP`: The type of the decomposer.
+pub fn type_check<S: Sized, DP: ?Sized + Decomposer<S>>(
+    storage: &S,
+    decomposer: &mut DP,
+    hints: &TypeHints,
+) -> Result<TypeCheck, DecomposeError<DP::Error>> {
+    let mut checker = Checker {
+        hints,
+        path: Vec::new(),
+        types: Vec::new(),
+        errors: Vec::new(),
+    };
+    let expr = decomposer.decompose_as_ref(storage)?.inner_as_ref();
+    checker.check(expr, |operand| {
+        Ok(decomposer.decompose_as_ref(operand)?.inner_as_ref())
+    })?;
+    let Checker { types, errors, .. } = checker;
+    Ok(TypeCheck { types, errors })
+}
+
+/// Type checks an [`ExprTree`].
+///
+/// This function is a convenience function that simplifies calling [`type_check`] with a decomposer for [`ExprTree`].
+///
+/// For an example, see the [module documentation](super).
+///
+/// Deeply nested trees, which can be read from untrusted input, are checked without overflowing the stack:
+/// ```rust
+/// # use fef::v0::read::read_expression_into_tree;
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # use fef::v0::expr::typing::{type_check_tree, ExprType, TypeHints};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut bytes = vec![0x17; 100_000]; // 100 000 nested negations
+/// bytes.push(0x0A); // True
+///
+/// let tree = read_expression_into_tree(&mut &bytes[..], &DEFAULT_CONFIG)?;
+/// let check = type_check_tree(&tree, &TypeHints::new());
+///
+/// assert_eq!(check.errors().len(), 1);
+/// assert_eq!(check.root_type(), ExprType::Number);
+/// # Ok(())
+/// # }
+/// ```
+pub fn type_check_tree(tree: &ExprTree, hints: &TypeHints) -> TypeCheck {
+    let mut decomposer = ExprTreeDecomposer {};
+    match type_check(tree, &mut decomposer, hints) {
+        Ok(check) => check,
+        Err(error) => error.unreachable(),
+    }
+}
+
+/// Type of the result of an arithmetic operation on two numbers.
+fn join(lhs: ExprType, rhs: ExprType) -> ExprType {
+    match (lhs, rhs) {
+        (ExprType::Integer, ExprType::Integer) => ExprType::Integer,
+        (ExprType::Float, _) | (_, ExprType::Float) => ExprType::Float,
+        _ => ExprType::Number,
+    }
+}
+
+struct Checker<'a> {
+    hints: &'a TypeHints,
+    /// Path of the checked expression, converted to an [`ExprPath`] only for reported errors.
+    path: Vec<usize>,
+    types: Vec<CheckedExpr>,
+    errors: Vec<TypeError>,
+}
+
+/// Step of the type checking, kept on a heap stack instead of the call stack.
+enum Step<'a, S: Sized> {
+    /// Stores the expression and schedules checking of its operands. `operand` is the index of the expression in its parent.
+    Enter {
+        expr: &'a Expr<S>,
+        operand: Option<usize>,
+    },
+    /// Infers the type of the expression at `index` of the checked types, once its operands are checked.
+    Exit { expr: &'a Expr<S>, index: usize },
+}
+
+impl Checker<'_> {
+    /// Checks an expression without recursion, expressions of operands are obtained by `decompose`.
+    ///
+    /// Steps waiting to be done are kept on a heap stack, the next one on top,
+    /// and types of checked operands, which their parent hasn't used yet, on another one.
+    fn check<'a, S: Sized, E: std::error::Error>(
+        &mut self,
+        expr: &'a Expr<S>,
+        mut decompose: impl FnMut(&'a S) -> Result<&'a Expr<S>, DecomposeError<E>>,
+    ) -> Result<ExprType, DecomposeError<E>> {
+        let mut steps = vec![Step::Enter {
+            expr,
+            operand: None,
+        }];
+        let mut operands: Vec<ExprType> = Vec::new();
+        while let Some(step) = steps.pop() {
+            match step {
+                Step::Enter { expr, operand } => {
+                    if let Some(operand) = operand {
+                        self.path.push(operand);
+                    }
+                    // The expression is stored before its operands, its type is filled in once they are checked.
+                    let index = self.types.len();
+                    self.types.push(CheckedExpr {
+                        ty: ExprType::Number,
+                        arity: expr.arity(),
+                        size: 1,
+                    });
+                    steps.push(Step::Exit { expr, index });
+                    // Operands are decomposed in order, but pushed in reverse, so that lhs is checked first.
+                    let operands_start = steps.len();
+                    for (operand, child) in expr.children().enumerate() {
+                        steps.push(Step::Enter {
+                            expr: decompose(child)?,
+                            operand: Some(operand),
+                        });
+                    }
+                    steps[operands_start..].reverse();
+                }
+                Step::Exit { expr, index } => {
+                    let arity = expr.arity();
+                    let ty = self.infer(expr, &operands[operands.len() - arity..]);
+                    operands.truncate(operands.len() - arity);
+                    operands.push(ty);
+                    self.types[index] = CheckedExpr {
+                        ty,
+                        arity,
+                        size: self.types.len() - index,
+                    };
+                    // The path of the root is empty.
+                    self.path.pop();
+                }
+            }
+        }
+        Ok(operands[0])
+    }
+
+    /// Reports a boolean operand and returns the type the operand is treated as.
+    fn numeric(&mut self, ty: ExprType, operation: ExprToken, operand: usize) -> ExprType {
+        if ty.is_numeric() {
+            return ty;
+        }
+        self.errors.push(TypeError::NonNumericOperand {
+            path: ExprPath::from(self.path.clone()),
+            operation,
+            operand,
+        });
+        ExprType::Number
+    }
+
+    /// Infers the type of an expression from the types of its operands, reporting ill-typed operands.
+    fn infer<S: Sized>(&mut self, expr: &Expr<S>, operands: &[ExprType]) -> ExprType {
+        if let Expr::Variable(expr) = expr {
+            if let Some(ty) = self.hints.get(expr.as_ref()) {
+                return *ty;
+            }
+        }
+        let operation = expr.token();
+        let mut numeric = [ExprType::Number; 2];
+        for (operand, ty) in operands.iter().enumerate() {
+            numeric[operand] = self.numeric(*ty, operation, operand);
+        }
+        let numeric = &numeric[..operands.len()];
+        if matches!(expr, Expr::IntRoot(_)) && numeric[1] == ExprType::Float {
+            self.errors.push(TypeError::NonIntegerOperand {
+                path: ExprPath::from(self.path.clone()),
+                operation,
+                operand: 1,
+                found: numeric[1],
+            });
+        }
+        expr_type(expr, numeric)
+    }
+}
+
+/// Type of an expression with numeric operands of the given types, see the [typing rules](super#typing-rules).
+///
+/// Variables get the type of variables without a hint.
+pub(crate) fn expr_type<S: Sized>(expr: &Expr<S>, operands: &[ExprType]) -> ExprType {
+    match expr {
+        Expr::Variable(_) => ExprType::Number,
+        Expr::TrueLiteral(_) | Expr::FalseLiteral(_) => ExprType::Boolean,
+        Expr::SignedIntLiteral(_) | Expr::UnsignedIntLiteral(_) | Expr::IntDivision(_) => {
+            ExprType::Integer
+        }
+        Expr::BinaryFloat32Literal(_)
+        | Expr::BinaryFloat64Literal(_)
+        | Expr::Division(_)
+        | Expr::Power(_)
+        | Expr::Root(_)
+        | Expr::IntRoot(_)
+        | Expr::SquareRoot(_)
+        | Expr::CubeRoot(_)
+        | Expr::Reciprocal(_) => ExprType::Float,
+        Expr::Addition(_) | Expr::Subtraction(_) | Expr::Multiplication(_) | Expr::Modulo(_) => {
+            join(operands[0], operands[1])
+        }
+        Expr::Negation(_) | Expr::Square(_) | Expr::Cube(_) => operands[0],
+    }
+}
@@ -0,0 +1,28 @@
+//! Error types for type checking of expressions.
+
+use thiserror::Error;
+
+use crate::v0::{expr::ExprPath, tokens::ExprToken};
+
+use super::ExprType;
+
+/// Ill-typed expression found by the [type checker](super::type_check).
+///
+/// Operands are numbered from zero, `lhs` of binary operations is operand `0` and `rhs` is operand `1`.
+#[derive(Debug, Error, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TypeError {
+    #[error("operand {operand} of {operation} at {path} is a boolean, but a number is expected")]
+    NonNumericOperand {
+        path: ExprPath,
+        operation: ExprToken,
+        operand: usize,
+    },
+    #[error("operand {operand} of {operation} at {path} is {found}, but an integer is expected")]
+    NonIntegerOperand {
+        path: ExprPath,
+        operation: ExprToken,
+        operand: usize,
+        found: ExprType,
+    },
+}
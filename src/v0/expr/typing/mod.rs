@@ -0,0 +1,99 @@
+//! Static type inference and checking of expressions.
+//!
+//! The FEF format doesn't restrict the operands of expressions, so nothing prevents storing e.g. an addition of a boolean and a float.
+//! The type checker infers the [type](ExprType) of every expression and reports the expressions whose operands have types
+//! the operation is not defined for.
+//!
+//! # Typing rules
+//! * [Boolean literals](crate::v0::expr::ExprTrueLiteral) are booleans, integer literals are integers and float literals are floats.
+//! * Variables have the type given by the [type hints](TypeHints). Variables without a hint are [numbers](ExprType::Number).
+//! * All operations require numeric operands, a boolean is only allowed as the root expression.
+//! * [`Addition`](crate::v0::expr::Expr::Addition), [`Subtraction`](crate::v0::expr::Expr::Subtraction), [`Multiplication`](crate::v0::expr::Expr::Multiplication)
+//!   and [`Modulo`](crate::v0::expr::Expr::Modulo) of two integers are integers, if any operand is a float, the result is a float.
+//! * [`Negation`](crate::v0::expr::Expr::Negation), [`Square`](crate::v0::expr::Expr::Square) and [`Cube`](crate::v0::expr::Expr::Cube) have the type of their operand.
+//! * [`IntDivision`](crate::v0::expr::Expr::IntDivision) is always an integer.
+//! * [`Division`](crate::v0::expr::Expr::Division), [`Reciprocal`](crate::v0::expr::Expr::Reciprocal), all roots and [`Power`](crate::v0::expr::Expr::Power)
+//!   are always floats, as the exponent of a power may be negative.
+//! * The degree of an [`IntRoot`](crate::v0::expr::Expr::IntRoot) must be an integer. A [number](ExprType::Number) is accepted, as it may be an integer.
+//!
+//! Checking doesn't stop at the first ill-typed expression. An ill-typed expression gets the type it would have if the offending operands
+//! were numbers, so all errors of an expression are reported at once.
+//!
+//! # Examples
+//! ```rust
+//! # use fef::v0::read::read_expression_into_tree;
+//! # use fef::v0::config::DEFAULT_CONFIG;
+//! # use fef::v0::expr::ExprPath;
+//! # use fef::v0::expr::typing::{type_check_tree, ExprType, TypeHints, error::TypeError};
+//! # use fef::v0::tokens::ExprToken;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let bytes: Vec<u8> = vec![
+//!     0x12, // Multiplication
+//!         0x04, 0x00, // Variable 0
+//!         0x10, // Addition
+//!             0x0A, // True
+//!             0x38, 0x01, // Number 1
+//! ];
+//! let tree = read_expression_into_tree(&mut bytes.as_slice(), &DEFAULT_CONFIG)?;
+//!
+//! let mut hints = TypeHints::new();
+//! hints.bind(0.into(), ExprType::Integer);
+//!
+//! let check = type_check_tree(&tree, &hints);
+//!
+//! assert_eq!(check.root_type(), ExprType::Number);
+//! assert_eq!(check.type_of(&ExprPath::root().child(1).child(0)), Some(ExprType::Boolean));
+//! assert_eq!(check.errors(), &[TypeError::NonNumericOperand {
+//!     path: ExprPath::root().child(1),
+//!     operation: ExprToken::Addition,
+//!     operand: 0,
+//! }]);
+//! # Ok(())
+//! # }
+//! ```
+
+mod check;
+
+pub mod error;
+
+pub use check::{type_check, type_check_tree, TypeCheck};
+
+pub(crate) use check::expr_type;
+
+use super::eval::VariableBindings;
+
+/// Type of the value of an expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ExprType {
+    /// A boolean value.
+    Boolean,
+    /// An integer.
+    Integer,
+    /// A floating-point number.
+    Float,
+    /// A number that is not known to be an integer or a float, e.g. a variable without a type hint.
+    Number,
+}
+
+impl ExprType {
+    /// Returns `true` for all types except [`Boolean`](ExprType::Boolean).
+    pub fn is_numeric(&self) -> bool {
+        !matches!(self, ExprType::Boolean)
+    }
+}
+
+/// Formats the type as its lowercase name.
+impl std::fmt::Display for ExprType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExprType::Boolean => write!(f, "boolean"),
+            ExprType::Integer => write!(f, "integer"),
+            ExprType::Float => write!(f, "float"),
+            ExprType::Number => write!(f, "number"),
+        }
+    }
+}
+
+/// Types of variables known before type checking.
+pub type TypeHints = VariableBindings<ExprType>;
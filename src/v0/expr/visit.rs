@@ -0,0 +1,471 @@
+//! Traits for walking expressions without matching on all variants of [`Expr`].
+//!
+//! [`Expr`] is [non-exhaustive](https://doc.rust-lang.org/reference/attributes/type_system.html#the-non_exhaustive-attribute),
+//! so code matching on all of its variants has to handle unknown expressions. The traits in this module instead have a method for every
+//! expression type, with a default implementation routed through a method common to all leaves, unary operations or binary operations.
+//! Implementations override just the methods they are interested in and keep working when new expression types are added.
+//!
+//! * [`ExprVisitor`] walks an expression by reference.
+//! * [`ExprVisitorMut`] walks an expression by mutable reference, so it can modify it in place.
+//! * [`ExprFold`] computes a value of an expression bottom-up from the values of its child expressions.
+//!
+//! Child expressions are accessed through the [`ExprStorage`] trait, which is implemented by [`ExprTree`](super::ExprTree)
+//! and [`ExprDagNode`](super::dag::ExprDagNode). The walks are recursive, every child expression of a shared
+//! [`ExprDagNode`](super::dag::ExprDagNode) is visited again.
+//!
+//! # Examples
+//! Collecting the variables of an expression:
+//! ```rust
+//! # use fef::v0::expr::{ExprTree, ExprVariable, visit::ExprVisitor};
+//! # use fef::v0::raw::VariableLengthEnum;
+//! # use fef::v0::text::{infix::parse_infix, VariableNames};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! struct Variables(Vec<VariableLengthEnum>);
+//!
+//! impl ExprVisitor<ExprTree> for Variables {
+//!     fn visit_variable(&mut self, expr: &ExprVariable<ExprTree>) {
+//!         self.0.push(expr.as_ref().clone());
+//!     }
+//! }
+//!
+//! let tree = parse_infix("x * (y + x)", &mut VariableNames::new())?;
+//! let mut variables = Variables(Vec::new());
+//! variables.visit_expr(tree.inner());
+//!
+//! assert_eq!(variables.0, [0.into(), 1.into(), 0.into()]);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Computing the depth of an expression:
+//! ```rust
+//! # use fef::v0::expr::{ExprTree, visit::ExprFold};
+//! # use fef::v0::expr::traits::{BinaryOperationExpr, ExprObj, UnaryOperationExpr};
+//! # use fef::v0::text::{infix::parse_infix, VariableNames};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! struct Depth;
+//!
+//! impl ExprFold<ExprTree> for Depth {
+//!     type Output = usize;
+//!
+//!     fn fold_leaf<E: ExprObj<ExprTree>>(&mut self, _expr: &E) -> usize {
+//!         1
+//!     }
+//!
+//!     fn fold_unary<E: UnaryOperationExpr<ExprTree> + ExprObj<ExprTree>>(&mut self, _expr: &E, inner: usize) -> usize {
+//!         inner + 1
+//!     }
+//!
+//!     fn fold_binary<E: BinaryOperationExpr<ExprTree> + ExprObj<ExprTree>>(&mut self, _expr: &E, lhs: usize, rhs: usize) -> usize {
+//!         lhs.max(rhs) + 1
+//!     }
+//! }
+//!
+//! let tree = parse_infix("x * sqrt(y + 1)", &mut VariableNames::new())?;
+//! assert_eq!(Depth.fold_expr(tree.inner()), 4);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::v0::expr::{
+    traits::{BinaryOperationExpr, ExprObj, ExprStorage, ExprStorageMut, UnaryOperationExpr},
+    Expr, ExprAddition, ExprBinaryFloat32Literal, ExprBinaryFloat64Literal, ExprCube, ExprCubeRoot,
+    ExprDivision, ExprFalseLiteral, ExprIntDivision, ExprIntRoot, ExprModulo, ExprMultiplication,
+    ExprNegation, ExprPower, ExprReciprocal, ExprRoot, ExprSignedIntLiteral, ExprSquare,
+    ExprSquareRoot, ExprSubtraction, ExprTrueLiteral, ExprUnsignedIntLiteral, ExprVariable,
+};
+
+macro_rules! visit_expr {
+    ($name:ident, $type:ty, $route:ident) => {
+        /// Visits this expression type.
+        ///
+        #[doc = concat!("Has default implementation that calls [`", stringify!($route), "`](Self::", stringify!($route), ").")]
+        fn $name(&mut self, expr: $type) {
+            self.$route(expr)
+        }
+    };
+}
+
+macro_rules! fold_leaf {
+    ($name:ident, $type:ty) => {
+        /// Folds this expression type.
+        ///
+        /// Has default implementation that calls [`fold_leaf`](Self::fold_leaf).
+        fn $name(&mut self, expr: $type) -> Self::Output {
+            self.fold_leaf(expr)
+        }
+    };
+}
+
+macro_rules! fold_unary {
+    ($name:ident, $type:ty) => {
+        /// Folds this expression type.
+        ///
+        /// Has default implementation that folds the child expression and calls [`fold_unary`](Self::fold_unary).
+        fn $name(&mut self, expr: $type) -> Self::Output {
+            let inner = self.fold_child(expr.inner());
+            self.fold_unary(expr, inner)
+        }
+    };
+}
+
+macro_rules! fold_binary {
+    ($name:ident, $type:ty) => {
+        /// Folds this expression type.
+        ///
+        /// Has default implementation that folds lhs, then rhs and calls [`fold_binary`](Self::fold_binary).
+        fn $name(&mut self, expr: $type) -> Self::Output {
+            let lhs = self.fold_child(expr.lhs());
+            let rhs = self.fold_child(expr.rhs());
+            self.fold_binary(expr, lhs, rhs)
+        }
+    };
+}
+
+/// Walks an expression by reference.
+///
+/// [`visit_expr`](ExprVisitor::visit_expr) calls the method of the expression type, which by default calls
+/// [`visit_leaf`](ExprVisitor::visit_leaf), [`visit_unary`](ExprVisitor::visit_unary) or [`visit_binary`](ExprVisitor::visit_binary).
+/// These visit the child expressions using [`visit_child`](ExprVisitor::visit_child). When overriding a method, call the default
+/// method to continue the walk into the child expressions.
+///
+/// For an example, see the [module documentation](self).
+pub trait ExprVisitor<S: ExprStorage> {
+    /// Visits an expression by calling the method of its type.
+    fn visit_expr(&mut self, expr: &Expr<S>) {
+        match expr {
+            Expr::Variable(expr) => self.visit_variable(expr),
+            Expr::SignedIntLiteral(expr) => self.visit_signed_int_literal(expr),
+            Expr::UnsignedIntLiteral(expr) => self.visit_unsigned_int_literal(expr),
+            Expr::BinaryFloat32Literal(expr) => self.visit_binary_float_32_literal(expr),
+            Expr::BinaryFloat64Literal(expr) => self.visit_binary_float_64_literal(expr),
+            Expr::TrueLiteral(expr) => self.visit_true_literal(expr),
+            Expr::FalseLiteral(expr) => self.visit_false_literal(expr),
+            Expr::Addition(expr) => self.visit_addition(expr),
+            Expr::Subtraction(expr) => self.visit_subtraction(expr),
+            Expr::Multiplication(expr) => self.visit_multiplication(expr),
+            Expr::Division(expr) => self.visit_division(expr),
+            Expr::IntDivision(expr) => self.visit_int_division(expr),
+            Expr::Modulo(expr) => self.visit_modulo(expr),
+            Expr::Power(expr) => self.visit_power(expr),
+            Expr::Negation(expr) => self.visit_negation(expr),
+            Expr::Root(expr) => self.visit_root(expr),
+            Expr::IntRoot(expr) => self.visit_int_root(expr),
+            Expr::Square(expr) => self.visit_square(expr),
+            Expr::Cube(expr) => self.visit_cube(expr),
+            Expr::SquareRoot(expr) => self.visit_square_root(expr),
+            Expr::CubeRoot(expr) => self.visit_cube_root(expr),
+            Expr::Reciprocal(expr) => self.visit_reciprocal(expr),
+        }
+    }
+
+    /// Visits a child expression.
+    ///
+    /// Has default implementation that visits the stored expression using [`visit_expr`](Self::visit_expr).
+    fn visit_child(&mut self, child: &S) {
+        self.visit_expr(child.expr())
+    }
+
+    /// Visits an expression without child expressions.
+    ///
+    /// Has default implementation that does nothing.
+    #[allow(unused_variables)]
+    fn visit_leaf<E: ExprObj<S>>(&mut self, expr: &E) {}
+
+    /// Visits a unary operation.
+    ///
+    /// Has default implementation that visits the child expression.
+    fn visit_unary<E: UnaryOperationExpr<S> + ExprObj<S>>(&mut self, expr: &E) {
+        self.visit_child(expr.inner())
+    }
+
+    /// Visits a binary operation.
+    ///
+    /// Has default implementation that visits lhs, then rhs.
+    fn visit_binary<E: BinaryOperationExpr<S> + ExprObj<S>>(&mut self, expr: &E) {
+        self.visit_child(expr.lhs());
+        self.visit_child(expr.rhs());
+    }
+
+    visit_expr!(visit_variable, &ExprVariable<S>, visit_leaf);
+    visit_expr!(
+        visit_signed_int_literal,
+        &ExprSignedIntLiteral<S>,
+        visit_leaf
+    );
+    visit_expr!(
+        visit_unsigned_int_literal,
+        &ExprUnsignedIntLiteral<S>,
+        visit_leaf
+    );
+    visit_expr!(
+        visit_binary_float_32_literal,
+        &ExprBinaryFloat32Literal<S>,
+        visit_leaf
+    );
+    visit_expr!(
+        visit_binary_float_64_literal,
+        &ExprBinaryFloat64Literal<S>,
+        visit_leaf
+    );
+    visit_expr!(visit_true_literal, &ExprTrueLiteral<S>, visit_leaf);
+    visit_expr!(visit_false_literal, &ExprFalseLiteral<S>, visit_leaf);
+    visit_expr!(visit_addition, &ExprAddition<S>, visit_binary);
+    visit_expr!(visit_subtraction, &ExprSubtraction<S>, visit_binary);
+    visit_expr!(visit_multiplication, &ExprMultiplication<S>, visit_binary);
+    visit_expr!(visit_division, &ExprDivision<S>, visit_binary);
+    visit_expr!(visit_int_division, &ExprIntDivision<S>, visit_binary);
+    visit_expr!(visit_modulo, &ExprModulo<S>, visit_binary);
+    visit_expr!(visit_power, &ExprPower<S>, visit_binary);
+    visit_expr!(visit_negation, &ExprNegation<S>, visit_unary);
+    visit_expr!(visit_root, &ExprRoot<S>, visit_binary);
+    visit_expr!(visit_int_root, &ExprIntRoot<S>, visit_binary);
+    visit_expr!(visit_square, &ExprSquare<S>, visit_unary);
+    visit_expr!(visit_cube, &ExprCube<S>, visit_unary);
+    visit_expr!(visit_square_root, &ExprSquareRoot<S>, visit_unary);
+    visit_expr!(visit_cube_root, &ExprCubeRoot<S>, visit_unary);
+    visit_expr!(visit_reciprocal, &ExprReciprocal<S>, visit_unary);
+}
+
+/// Walks an expression by mutable reference.
+///
+/// Works the same way as [`ExprVisitor`], but every method gets a mutable reference, so the expression can be modified in place.
+///
+/// # Examples
+/// Swapping the operands of all additions:
+/// ```rust
+/// # use fef::v0::expr::{ExprAddition, ExprTree, visit::ExprVisitorMut};
+/// # use fef::v0::expr::traits::BinaryOperationExpr;
+/// # use fef::v0::text::{infix::{parse_infix, InfixPrinter, InfixStyle}, VariableNames};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// struct Swap;
+///
+/// impl ExprVisitorMut<ExprTree> for Swap {
+///     fn visit_addition_mut(&mut self, expr: &mut ExprAddition<ExprTree>) {
+///         let (lhs, rhs) = expr.operands_mut();
+///         std::mem::swap(lhs, rhs);
+///         self.visit_binary_mut(expr);
+///     }
+/// }
+///
+/// let mut names = VariableNames::new();
+/// let mut tree = parse_infix("x + 2 * (y + 1)", &mut names)?;
+/// Swap.visit_expr_mut(tree.inner_mut());
+///
+/// let printer = InfixPrinter::new(InfixStyle::Ascii, Some(&names));
+/// assert_eq!(printer.print(&tree), "2 * (1 + y) + x");
+/// # Ok(())
+/// # }
+/// ```
+pub trait ExprVisitorMut<S: ExprStorageMut> {
+    /// Visits an expression by calling the method of its type.
+    fn visit_expr_mut(&mut self, expr: &mut Expr<S>) {
+        match expr {
+            Expr::Variable(expr) => self.visit_variable_mut(expr),
+            Expr::SignedIntLiteral(expr) => self.visit_signed_int_literal_mut(expr),
+            Expr::UnsignedIntLiteral(expr) => self.visit_unsigned_int_literal_mut(expr),
+            Expr::BinaryFloat32Literal(expr) => self.visit_binary_float_32_literal_mut(expr),
+            Expr::BinaryFloat64Literal(expr) => self.visit_binary_float_64_literal_mut(expr),
+            Expr::TrueLiteral(expr) => self.visit_true_literal_mut(expr),
+            Expr::FalseLiteral(expr) => self.visit_false_literal_mut(expr),
+            Expr::Addition(expr) => self.visit_addition_mut(expr),
+            Expr::Subtraction(expr) => self.visit_subtraction_mut(expr),
+            Expr::Multiplication(expr) => self.visit_multiplication_mut(expr),
+            Expr::Division(expr) => self.visit_division_mut(expr),
+            Expr::IntDivision(expr) => self.visit_int_division_mut(expr),
+            Expr::Modulo(expr) => self.visit_modulo_mut(expr),
+            Expr::Power(expr) => self.visit_power_mut(expr),
+            Expr::Negation(expr) => self.visit_negation_mut(expr),
+            Expr::Root(expr) => self.visit_root_mut(expr),
+            Expr::IntRoot(expr) => self.visit_int_root_mut(expr),
+            Expr::Square(expr) => self.visit_square_mut(expr),
+            Expr::Cube(expr) => self.visit_cube_mut(expr),
+            Expr::SquareRoot(expr) => self.visit_square_root_mut(expr),
+            Expr::CubeRoot(expr) => self.visit_cube_root_mut(expr),
+            Expr::Reciprocal(expr) => self.visit_reciprocal_mut(expr),
+        }
+    }
+
+    /// Visits a child expression.
+    ///
+    /// Has default implementation that visits the stored expression using [`visit_expr_mut`](Self::visit_expr_mut).
+    fn visit_child_mut(&mut self, child: &mut S) {
+        self.visit_expr_mut(child.expr_mut())
+    }
+
+    /// Visits an expression without child expressions.
+    ///
+    /// Has default implementation that does nothing.
+    #[allow(unused_variables)]
+    fn visit_leaf_mut<E: ExprObj<S>>(&mut self, expr: &mut E) {}
+
+    /// Visits a unary operation.
+    ///
+    /// Has default implementation that visits the child expression.
+    fn visit_unary_mut<E: UnaryOperationExpr<S> + ExprObj<S>>(&mut self, expr: &mut E) {
+        self.visit_child_mut(expr.inner_mut())
+    }
+
+    /// Visits a binary operation.
+    ///
+    /// Has default implementation that visits lhs, then rhs.
+    fn visit_binary_mut<E: BinaryOperationExpr<S> + ExprObj<S>>(&mut self, expr: &mut E) {
+        self.visit_child_mut(expr.lhs_mut());
+        self.visit_child_mut(expr.rhs_mut());
+    }
+
+    visit_expr!(visit_variable_mut, &mut ExprVariable<S>, visit_leaf_mut);
+    visit_expr!(
+        visit_signed_int_literal_mut,
+        &mut ExprSignedIntLiteral<S>,
+        visit_leaf_mut
+    );
+    visit_expr!(
+        visit_unsigned_int_literal_mut,
+        &mut ExprUnsignedIntLiteral<S>,
+        visit_leaf_mut
+    );
+    visit_expr!(
+        visit_binary_float_32_literal_mut,
+        &mut ExprBinaryFloat32Literal<S>,
+        visit_leaf_mut
+    );
+    visit_expr!(
+        visit_binary_float_64_literal_mut,
+        &mut ExprBinaryFloat64Literal<S>,
+        visit_leaf_mut
+    );
+    visit_expr!(
+        visit_true_literal_mut,
+        &mut ExprTrueLiteral<S>,
+        visit_leaf_mut
+    );
+    visit_expr!(
+        visit_false_literal_mut,
+        &mut ExprFalseLiteral<S>,
+        visit_leaf_mut
+    );
+    visit_expr!(visit_addition_mut, &mut ExprAddition<S>, visit_binary_mut);
+    visit_expr!(
+        visit_subtraction_mut,
+        &mut ExprSubtraction<S>,
+        visit_binary_mut
+    );
+    visit_expr!(
+        visit_multiplication_mut,
+        &mut ExprMultiplication<S>,
+        visit_binary_mut
+    );
+    visit_expr!(visit_division_mut, &mut ExprDivision<S>, visit_binary_mut);
+    visit_expr!(
+        visit_int_division_mut,
+        &mut ExprIntDivision<S>,
+        visit_binary_mut
+    );
+    visit_expr!(visit_modulo_mut, &mut ExprModulo<S>, visit_binary_mut);
+    visit_expr!(visit_power_mut, &mut ExprPower<S>, visit_binary_mut);
+    visit_expr!(visit_negation_mut, &mut ExprNegation<S>, visit_unary_mut);
+    visit_expr!(visit_root_mut, &mut ExprRoot<S>, visit_binary_mut);
+    visit_expr!(visit_int_root_mut, &mut ExprIntRoot<S>, visit_binary_mut);
+    visit_expr!(visit_square_mut, &mut ExprSquare<S>, visit_unary_mut);
+    visit_expr!(visit_cube_mut, &mut ExprCube<S>, visit_unary_mut);
+    visit_expr!(
+        visit_square_root_mut,
+        &mut ExprSquareRoot<S>,
+        visit_unary_mut
+    );
+    visit_expr!(visit_cube_root_mut, &mut ExprCubeRoot<S>, visit_unary_mut);
+    visit_expr!(
+        visit_reciprocal_mut,
+        &mut ExprReciprocal<S>,
+        visit_unary_mut
+    );
+}
+
+/// Computes a value of an expression bottom-up.
+///
+/// [`fold_expr`](ExprFold::fold_expr) calls the method of the expression type, which by default folds the child expressions
+/// using [`fold_child`](ExprFold::fold_child) and combines their values by [`fold_leaf`](ExprFold::fold_leaf),
+/// [`fold_unary`](ExprFold::fold_unary) or [`fold_binary`](ExprFold::fold_binary). Only these three methods have to be implemented.
+///
+/// For an example, see the [module documentation](self).
+pub trait ExprFold<S: ExprStorage> {
+    /// The type of the computed values.
+    type Output;
+
+    /// Folds an expression by calling the method of its type.
+    fn fold_expr(&mut self, expr: &Expr<S>) -> Self::Output {
+        match expr {
+            Expr::Variable(expr) => self.fold_variable(expr),
+            Expr::SignedIntLiteral(expr) => self.fold_signed_int_literal(expr),
+            Expr::UnsignedIntLiteral(expr) => self.fold_unsigned_int_literal(expr),
+            Expr::BinaryFloat32Literal(expr) => self.fold_binary_float_32_literal(expr),
+            Expr::BinaryFloat64Literal(expr) => self.fold_binary_float_64_literal(expr),
+            Expr::TrueLiteral(expr) => self.fold_true_literal(expr),
+            Expr::FalseLiteral(expr) => self.fold_false_literal(expr),
+            Expr::Addition(expr) => self.fold_addition(expr),
+            Expr::Subtraction(expr) => self.fold_subtraction(expr),
+            Expr::Multiplication(expr) => self.fold_multiplication(expr),
+            Expr::Division(expr) => self.fold_division(expr),
+            Expr::IntDivision(expr) => self.fold_int_division(expr),
+            Expr::Modulo(expr) => self.fold_modulo(expr),
+            Expr::Power(expr) => self.fold_power(expr),
+            Expr::Negation(expr) => self.fold_negation(expr),
+            Expr::Root(expr) => self.fold_root(expr),
+            Expr::IntRoot(expr) => self.fold_int_root(expr),
+            Expr::Square(expr) => self.fold_square(expr),
+            Expr::Cube(expr) => self.fold_cube(expr),
+            Expr::SquareRoot(expr) => self.fold_square_root(expr),
+            Expr::CubeRoot(expr) => self.fold_cube_root(expr),
+            Expr::Reciprocal(expr) => self.fold_reciprocal(expr),
+        }
+    }
+
+    /// Folds a child expression.
+    ///
+    /// Has default implementation that folds the stored expression using [`fold_expr`](Self::fold_expr).
+    fn fold_child(&mut self, child: &S) -> Self::Output {
+        self.fold_expr(child.expr())
+    }
+
+    /// Computes the value of an expression without child expressions.
+    fn fold_leaf<E: ExprObj<S>>(&mut self, expr: &E) -> Self::Output;
+
+    /// Computes the value of a unary operation from the value of its child expression.
+    fn fold_unary<E: UnaryOperationExpr<S> + ExprObj<S>>(
+        &mut self,
+        expr: &E,
+        inner: Self::Output,
+    ) -> Self::Output;
+
+    /// Computes the value of a binary operation from the values of its lhs and rhs.
+    fn fold_binary<E: BinaryOperationExpr<S> + ExprObj<S>>(
+        &mut self,
+        expr: &E,
+        lhs: Self::Output,
+        rhs: Self::Output,
+    ) -> Self::Output;
+
+    fold_leaf!(fold_variable, &ExprVariable<S>);
+    fold_leaf!(fold_signed_int_literal, &ExprSignedIntLiteral<S>);
+    fold_leaf!(fold_unsigned_int_literal, &ExprUnsignedIntLiteral<S>);
+    fold_leaf!(fold_binary_float_32_literal, &ExprBinaryFloat32Literal<S>);
+    fold_leaf!(fold_binary_float_64_literal, &ExprBinaryFloat64Literal<S>);
+    fold_leaf!(fold_true_literal, &ExprTrueLiteral<S>);
+    fold_leaf!(fold_false_literal, &ExprFalseLiteral<S>);
+    fold_binary!(fold_addition, &ExprAddition<S>);
+    fold_binary!(fold_subtraction, &ExprSubtraction<S>);
+    fold_binary!(fold_multiplication, &ExprMultiplication<S>);
+    fold_binary!(fold_division, &ExprDivision<S>);
+    fold_binary!(fold_int_division, &ExprIntDivision<S>);
+    fold_binary!(fold_modulo, &ExprModulo<S>);
+    fold_binary!(fold_power, &ExprPower<S>);
+    fold_unary!(fold_negation, &ExprNegation<S>);
+    fold_binary!(fold_root, &ExprRoot<S>);
+    fold_binary!(fold_int_root, &ExprIntRoot<S>);
+    fold_unary!(fold_square, &ExprSquare<S>);
+    fold_unary!(fold_cube, &ExprCube<S>);
+    fold_unary!(fold_square_root, &ExprSquareRoot<S>);
+    fold_unary!(fold_cube_root, &ExprCubeRoot<S>);
+    fold_unary!(fold_reciprocal, &ExprReciprocal<S>);
+}
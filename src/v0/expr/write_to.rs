@@ -9,74 +9,33 @@ use crate::v0::{
 
 use super::{
     error::{ExprWriteError, ExprWriteWithDecomposerError},
-    traits::{
-        BinaryOperationExpr, Decomposer, DecompositionRefContainer, EnumExpr, ExprObj,
-        TryWriteToWithDecomposer, UnaryOperationExpr,
-    },
-    Expr, ExprAddition, ExprBinaryFloat32Literal, ExprBinaryFloat64Literal, ExprCube, ExprCubeRoot,
-    ExprDivision, ExprFalseLiteral, ExprIntDivision, ExprIntRoot, ExprModulo, ExprMultiplication,
-    ExprNegation, ExprPower, ExprReciprocal, ExprRoot, ExprSignedIntLiteral, ExprSquare,
-    ExprSquareRoot, ExprSubtraction, ExprTrueLiteral, ExprUnsignedIntLiteral, ExprVariable,
+    traits::{Decomposer, DecompositionRefContainer, EnumExpr, ExprObj, TryWriteToWithDecomposer},
+    Expr, ExprBinaryFloat32Literal, ExprBinaryFloat64Literal, ExprFalseLiteral,
+    ExprSignedIntLiteral, ExprTrueLiteral, ExprUnsignedIntLiteral, ExprVariable,
 };
 
-macro_rules! impl_try_write_to_with_decomposer_for_unary_expr {
-    ($expr_type:ty) => {
-        impl<W: ?Sized + Write, S: Sized, C: ?Sized + Config, DP: ?Sized + Decomposer<S>>
-            TryWriteToWithDecomposer<W, S, C, DP> for $expr_type
-        {
-            fn try_write_with_decomposer(
-                &self,
-                writer: &mut W,
-                config: &C,
-                decomposer: &mut DP,
-            ) -> Result<(), ExprWriteWithDecomposerError<<DP as Decomposer<S>>::Error>> {
-                let child: &S = self.inner();
-                let decomposition = decomposer.decompose_as_ref(child)?.inner_as_ref();
-                decomposition.try_write_with_decomposer(writer, config, decomposer)?;
-                Ok(())
-            }
-        }
-    };
+/// Writes the body of an expression without operands, which follows its token.
+trait WriteLeafBody<W: ?Sized + Write, C: ?Sized + Config> {
+    fn write_leaf_body(&self, writer: &mut W, config: &C) -> Result<(), ExprWriteError>;
 }
 
-impl_try_write_to_with_decomposer_for_unary_expr!(ExprReciprocal<S>);
-impl_try_write_to_with_decomposer_for_unary_expr!(ExprNegation<S>);
-impl_try_write_to_with_decomposer_for_unary_expr!(ExprSquare<S>);
-impl_try_write_to_with_decomposer_for_unary_expr!(ExprSquareRoot<S>);
-impl_try_write_to_with_decomposer_for_unary_expr!(ExprCube<S>);
-impl_try_write_to_with_decomposer_for_unary_expr!(ExprCubeRoot<S>);
-
-macro_rules! impl_try_write_to_with_decomposer_for_literal_expr {
+macro_rules! impl_write_leaf_body_for_literal_expr {
     ($expr_type:ty) => {
-        impl<W: ?Sized + Write, S: Sized, C: ?Sized + Config, DP: ?Sized + Decomposer<S>>
-            TryWriteToWithDecomposer<W, S, C, DP> for $expr_type
-        {
-            fn try_write_with_decomposer(
-                &self,
-                _writer: &mut W,
-                _config: &C,
-                _decomposer: &mut DP,
-            ) -> Result<(), ExprWriteWithDecomposerError<<DP as Decomposer<S>>::Error>> {
+        impl<W: ?Sized + Write, S: Sized, C: ?Sized + Config> WriteLeafBody<W, C> for $expr_type {
+            fn write_leaf_body(&self, _writer: &mut W, _config: &C) -> Result<(), ExprWriteError> {
                 Ok(())
             }
         }
     };
 }
 
-impl_try_write_to_with_decomposer_for_literal_expr!(ExprTrueLiteral<S>);
-impl_try_write_to_with_decomposer_for_literal_expr!(ExprFalseLiteral<S>);
+impl_write_leaf_body_for_literal_expr!(ExprTrueLiteral<S>);
+impl_write_leaf_body_for_literal_expr!(ExprFalseLiteral<S>);
 
-macro_rules! impl_try_write_to_with_decomposer_for_variable_expr {
+macro_rules! impl_write_leaf_body_for_variable_expr {
     ($expr_type:ty) => {
-        impl<W: ?Sized + Write, S: Sized, C: ?Sized + Config, DP: ?Sized + Decomposer<S>>
-            TryWriteToWithDecomposer<W, S, C, DP> for $expr_type
-        {
-            fn try_write_with_decomposer(
-                &self,
-                writer: &mut W,
-                config: &C,
-                _decomposer: &mut DP,
-            ) -> Result<(), ExprWriteWithDecomposerError<<DP as Decomposer<S>>::Error>> {
+        impl<W: ?Sized + Write, S: Sized, C: ?Sized + Config> WriteLeafBody<W, C> for $expr_type {
+            fn write_leaf_body(&self, writer: &mut W, config: &C) -> Result<(), ExprWriteError> {
                 let variable = self.variable_length_enum();
                 variable
                     .write_to(writer, config)
@@ -87,40 +46,7 @@ macro_rules! impl_try_write_to_with_decomposer_for_variable_expr {
     };
 }
 
-impl_try_write_to_with_decomposer_for_variable_expr!(ExprVariable<S>);
-
-macro_rules! impl_try_write_to_with_decomposer_for_binary_expr {
-    ($expr_type:ty) => {
-        impl<W: ?Sized + Write, S: Sized, C: ?Sized + Config, DP: ?Sized + Decomposer<S>>
-            TryWriteToWithDecomposer<W, S, C, DP> for $expr_type
-        {
-            fn try_write_with_decomposer(
-                &self,
-                writer: &mut W,
-                config: &C,
-                decomposer: &mut DP,
-            ) -> Result<(), ExprWriteWithDecomposerError<<DP as Decomposer<S>>::Error>> {
-                let left: &S = self.lhs();
-                let right: &S = self.rhs();
-                let left_decomposition = decomposer.decompose_as_ref(left)?.inner_as_ref();
-                let right_decomposition = decomposer.decompose_as_ref(right)?.inner_as_ref();
-                left_decomposition.try_write_with_decomposer(writer, config, decomposer)?;
-                right_decomposition.try_write_with_decomposer(writer, config, decomposer)?;
-                Ok(())
-            }
-        }
-    };
-}
-
-impl_try_write_to_with_decomposer_for_binary_expr!(ExprAddition<S>);
-impl_try_write_to_with_decomposer_for_binary_expr!(ExprSubtraction<S>);
-impl_try_write_to_with_decomposer_for_binary_expr!(ExprMultiplication<S>);
-impl_try_write_to_with_decomposer_for_binary_expr!(ExprDivision<S>);
-impl_try_write_to_with_decomposer_for_binary_expr!(ExprIntDivision<S>);
-impl_try_write_to_with_decomposer_for_binary_expr!(ExprPower<S>);
-impl_try_write_to_with_decomposer_for_binary_expr!(ExprRoot<S>);
-impl_try_write_to_with_decomposer_for_binary_expr!(ExprIntRoot<S>);
-impl_try_write_to_with_decomposer_for_binary_expr!(ExprModulo<S>);
+impl_write_leaf_body_for_variable_expr!(ExprVariable<S>);
 
 const U8_MIN: u64 = u8::MIN as u64;
 const U8_MAX: u64 = u8::MAX as u64;
@@ -130,15 +56,10 @@ const U32_MIN: u64 = u32::MIN as u64;
 const U32_MAX: u64 = u32::MAX as u64;
 const U64_MIN: u64 = u64::MIN;
 const U64_MAX: u64 = u64::MAX;
-impl<W: ?Sized + Write, S: Sized, C: ?Sized + Config, DP: ?Sized + Decomposer<S>>
-    TryWriteToWithDecomposer<W, S, C, DP> for ExprUnsignedIntLiteral<S>
+impl<W: ?Sized + Write, S: Sized, C: ?Sized + Config> WriteLeafBody<W, C>
+    for ExprUnsignedIntLiteral<S>
 {
-    fn try_write_with_decomposer(
-        &self,
-        writer: &mut W,
-        _config: &C,
-        _decomposer: &mut DP,
-    ) -> Result<(), ExprWriteWithDecomposerError<<DP as Decomposer<S>>::Error>> {
+    fn write_leaf_body(&self, writer: &mut W, _config: &C) -> Result<(), ExprWriteError> {
         match self.value {
             U8_MIN..=U8_MAX => {
                 let buffer = (self.value as u8).to_be_bytes();
@@ -180,15 +101,10 @@ const I32_MIN: i64 = i32::MIN as i64;
 const I32_MAX: i64 = i32::MAX as i64;
 const I64_MIN: i64 = i64::MIN;
 const I64_MAX: i64 = i64::MAX;
-impl<W: ?Sized + Write, S: Sized, C: ?Sized + Config, DP: ?Sized + Decomposer<S>>
-    TryWriteToWithDecomposer<W, S, C, DP> for ExprSignedIntLiteral<S>
+impl<W: ?Sized + Write, S: Sized, C: ?Sized + Config> WriteLeafBody<W, C>
+    for ExprSignedIntLiteral<S>
 {
-    fn try_write_with_decomposer(
-        &self,
-        writer: &mut W,
-        _config: &C,
-        _decomposer: &mut DP,
-    ) -> Result<(), ExprWriteWithDecomposerError<<DP as Decomposer<S>>::Error>> {
+    fn write_leaf_body(&self, writer: &mut W, _config: &C) -> Result<(), ExprWriteError> {
         match self.value {
             I8_MIN..=I8_MAX => {
                 let buffer = (self.value as i8).to_be_bytes();
@@ -222,15 +138,10 @@ impl<W: ?Sized + Write, S: Sized, C: ?Sized + Config, DP: ?Sized + Decomposer<S>
     }
 }
 
-impl<W: ?Sized + Write, S: Sized, C: ?Sized + Config, DP: ?Sized + Decomposer<S>>
-    TryWriteToWithDecomposer<W, S, C, DP> for ExprBinaryFloat32Literal<S>
+impl<W: ?Sized + Write, S: Sized, C: ?Sized + Config> WriteLeafBody<W, C>
+    for ExprBinaryFloat32Literal<S>
 {
-    fn try_write_with_decomposer(
-        &self,
-        writer: &mut W,
-        _config: &C,
-        _decomposer: &mut DP,
-    ) -> Result<(), ExprWriteWithDecomposerError<<DP as Decomposer<S>>::Error>> {
+    fn write_leaf_body(&self, writer: &mut W, _config: &C) -> Result<(), ExprWriteError> {
         let buffer = self.value.to_be_bytes();
         writer
             .write_all(&buffer)
@@ -239,15 +150,10 @@ impl<W: ?Sized + Write, S: Sized, C: ?Sized + Config, DP: ?Sized + Decomposer<S>
     }
 }
 
-impl<W: ?Sized + Write, S: Sized, C: ?Sized + Config, DP: ?Sized + Decomposer<S>>
-    TryWriteToWithDecomposer<W, S, C, DP> for ExprBinaryFloat64Literal<S>
+impl<W: ?Sized + Write, S: Sized, C: ?Sized + Config> WriteLeafBody<W, C>
+    for ExprBinaryFloat64Literal<S>
 {
-    fn try_write_with_decomposer(
-        &self,
-        writer: &mut W,
-        _config: &C,
-        _decomposer: &mut DP,
-    ) -> Result<(), ExprWriteWithDecomposerError<<DP as Decomposer<S>>::Error>> {
+    fn write_leaf_body(&self, writer: &mut W, _config: &C) -> Result<(), ExprWriteError> {
         let buffer = self.value.to_be_bytes();
         writer
             .write_all(&buffer)
@@ -265,43 +171,63 @@ impl<W: ?Sized + Write, S: Sized, C: ?Sized + Config, DP: ?Sized + Decomposer<S>
         config: &C,
         decomposer: &mut DP,
     ) -> Result<(), ExprWriteWithDecomposerError<<DP as Decomposer<S>>::Error>> {
-        let token: ExprToken = self.token();
+        write_expr(self, writer, config, |operand| {
+            Ok(decomposer.decompose_as_ref(operand)?.inner_as_ref())
+        })
+    }
+}
+
+/// Writes an expression without recursion, so that deeply nested expressions cannot overflow the stack.
+///
+/// Expressions of operands are obtained by `decompose`. Expressions waiting to be written are kept on a heap stack, the next one on top.
+pub(crate) fn write_expr<
+    'a,
+    W: ?Sized + Write,
+    S: Sized,
+    C: ?Sized + Config,
+    E: std::error::Error,
+>(
+    expr: &'a Expr<S>,
+    writer: &mut W,
+    config: &C,
+    mut decompose: impl FnMut(&'a S) -> Result<&'a Expr<S>, ExprWriteWithDecomposerError<E>>,
+) -> Result<(), ExprWriteWithDecomposerError<E>> {
+    let mut stack = vec![expr];
+    while let Some(expr) = stack.pop() {
+        let token: ExprToken = expr.token();
         token
             .write_to(writer, config)
             .map_err(|e| ExprWriteError::from(e))?;
-        match self {
-            Expr::UnsignedIntLiteral(expr) => {
-                expr.try_write_with_decomposer(writer, config, decomposer)
-            }
-            Expr::SignedIntLiteral(expr) => {
-                expr.try_write_with_decomposer(writer, config, decomposer)
-            }
-            Expr::BinaryFloat32Literal(expr) => {
-                expr.try_write_with_decomposer(writer, config, decomposer)
-            }
-            Expr::BinaryFloat64Literal(expr) => {
-                expr.try_write_with_decomposer(writer, config, decomposer)
-            }
-            Expr::TrueLiteral(expr) => expr.try_write_with_decomposer(writer, config, decomposer),
-            Expr::FalseLiteral(expr) => expr.try_write_with_decomposer(writer, config, decomposer),
-            Expr::Variable(expr) => expr.try_write_with_decomposer(writer, config, decomposer),
-            Expr::Addition(expr) => expr.try_write_with_decomposer(writer, config, decomposer),
-            Expr::Subtraction(expr) => expr.try_write_with_decomposer(writer, config, decomposer),
-            Expr::Multiplication(expr) => {
-                expr.try_write_with_decomposer(writer, config, decomposer)
-            }
-            Expr::Division(expr) => expr.try_write_with_decomposer(writer, config, decomposer),
-            Expr::IntDivision(expr) => expr.try_write_with_decomposer(writer, config, decomposer),
-            Expr::Modulo(expr) => expr.try_write_with_decomposer(writer, config, decomposer),
-            Expr::Power(expr) => expr.try_write_with_decomposer(writer, config, decomposer),
-            Expr::Negation(expr) => expr.try_write_with_decomposer(writer, config, decomposer),
-            Expr::Root(expr) => expr.try_write_with_decomposer(writer, config, decomposer),
-            Expr::IntRoot(expr) => expr.try_write_with_decomposer(writer, config, decomposer),
-            Expr::Square(expr) => expr.try_write_with_decomposer(writer, config, decomposer),
-            Expr::Cube(expr) => expr.try_write_with_decomposer(writer, config, decomposer),
-            Expr::SquareRoot(expr) => expr.try_write_with_decomposer(writer, config, decomposer),
-            Expr::CubeRoot(expr) => expr.try_write_with_decomposer(writer, config, decomposer),
-            Expr::Reciprocal(expr) => expr.try_write_with_decomposer(writer, config, decomposer),
+        match expr {
+            Expr::UnsignedIntLiteral(expr) => expr.write_leaf_body(writer, config)?,
+            Expr::SignedIntLiteral(expr) => expr.write_leaf_body(writer, config)?,
+            Expr::BinaryFloat32Literal(expr) => expr.write_leaf_body(writer, config)?,
+            Expr::BinaryFloat64Literal(expr) => expr.write_leaf_body(writer, config)?,
+            Expr::TrueLiteral(expr) => expr.write_leaf_body(writer, config)?,
+            Expr::FalseLiteral(expr) => expr.write_leaf_body(writer, config)?,
+            Expr::Variable(expr) => expr.write_leaf_body(writer, config)?,
+            Expr::Addition(_)
+            | Expr::Subtraction(_)
+            | Expr::Multiplication(_)
+            | Expr::Division(_)
+            | Expr::IntDivision(_)
+            | Expr::Modulo(_)
+            | Expr::Power(_)
+            | Expr::Negation(_)
+            | Expr::Root(_)
+            | Expr::IntRoot(_)
+            | Expr::Square(_)
+            | Expr::Cube(_)
+            | Expr::SquareRoot(_)
+            | Expr::CubeRoot(_)
+            | Expr::Reciprocal(_) => {}
+        }
+        // Operands are decomposed in order, but pushed in reverse, so that lhs is written first.
+        let operands_start = stack.len();
+        for operand in expr.children() {
+            stack.push(decompose(operand)?);
         }
+        stack[operands_start..].reverse();
     }
+    Ok(())
 }
@@ -2,8 +2,16 @@ use crate::{
     common::traits::private::Sealed,
     v0::{
         config::{Config, OverridableConfig},
-        expr::ExprTree,
+        expr::{
+            typing::{type_check_tree, TypeCheck, TypeHints},
+            ExprTree,
+        },
         metadata::MetadataRecord,
+        text::{
+            latex::LatexPrinter,
+            mathml::{ContentMathmlPrinter, PresentationMathmlPrinter},
+            VariableNames,
+        },
     },
 };
 
@@ -36,6 +44,37 @@ impl SingleFormulaFile {
         self.metadata.iter()
     }
 
+    /// Type checks the expression of the file
+    ///
+    /// Variables are typed using the given hints, see the [typing rules](crate::v0::expr::typing#typing-rules).
+    /// Use [`TypeCheck::is_well_typed`] or [`TypeCheck::into_result`] to reject files with ill-typed expressions.
+    pub fn type_check(&self, hints: &TypeHints) -> TypeCheck {
+        type_check_tree(&self.expression, hints)
+    }
+
+    /// Returns the names of variables from the [variable name](MetadataRecord::VariableName) metadata records
+    pub fn variable_names(&self) -> VariableNames {
+        VariableNames::from_metadata(self.metadata_iter())
+    }
+
+    /// Writes the expression in the [LaTeX notation](crate::v0::text::latex) using the variable names from the metadata
+    pub fn to_latex(&self) -> String {
+        let names = self.variable_names();
+        LatexPrinter::new(Some(&names)).print(&self.expression)
+    }
+
+    /// Writes the expression as [Presentation MathML](crate::v0::text::mathml) using the variable names from the metadata
+    pub fn to_presentation_mathml(&self) -> String {
+        let names = self.variable_names();
+        PresentationMathmlPrinter::new(Some(&names)).print(&self.expression)
+    }
+
+    /// Writes the expression as [Content MathML](crate::v0::text::mathml) using the variable names from the metadata
+    pub fn to_content_mathml(&self) -> String {
+        let names = self.variable_names();
+        ContentMathmlPrinter::new(Some(&names)).print(&self.expression)
+    }
+
     /// Decompose the file into the configuration, metadata records and the [`ExprTree`] root
     pub fn decompose(self) -> (impl Config, Vec<MetadataRecord>, ExprTree) {
         (self.configuration, self.metadata, self.expression)
@@ -22,3 +22,5 @@ pub mod write;
 pub mod metadata;
 
 pub mod file;
+
+pub mod text;
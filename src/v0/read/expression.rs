@@ -4,8 +4,13 @@ use crate::v0::{
     config::Config,
     expr::{
         error::{ComposeError, ExprReadWithComposerError},
-        traits::{Composer, ExprObj, TryReadFromWithComposer},
-        Expr, ExprTree,
+        eval::traits::Evaluator,
+        traits::{Composer, ExprObj, TryReadFromWithComposer, UnaryOperationExpr},
+        Expr, ExprAddition, ExprBinaryFloat32Literal, ExprBinaryFloat64Literal, ExprCube,
+        ExprCubeRoot, ExprDivision, ExprFalseLiteral, ExprIntDivision, ExprIntRoot, ExprModulo,
+        ExprMultiplication, ExprNegation, ExprPower, ExprReciprocal, ExprRoot,
+        ExprSignedIntLiteral, ExprSquare, ExprSquareRoot, ExprSubtraction, ExprTree,
+        ExprTrueLiteral, ExprUnsignedIntLiteral, ExprVariable,
     },
 };
 /// Reads an [expression](https://github.com/jiricekcz/fef-specification/blob/main/expressions/Expression.md) from a byte stream using a composer.
@@ -25,6 +30,28 @@ use crate::v0::{
 ///
 /// All but the `S` type parameter should in most cases be inferred.
 ///
+/// # Nesting depth
+///
+/// The expression is read without recursion, operations waiting for their operands are kept on the heap. The nesting depth
+/// of the expression is therefore limited only by the available memory and reading untrusted input cannot overflow the stack.
+/// [`ExprTree`] is written and dropped the same way.
+/// ```rust
+/// # use fef::v0::read::read_expression_into_tree;
+/// # use fef::v0::write::write_expression_tree;
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut bytes = vec![0x17; 100_000]; // 100 000 nested negations
+/// bytes.extend([0x04, 0x00]); // Variable 0
+///
+/// let tree = read_expression_into_tree(&mut &bytes[..], &DEFAULT_CONFIG)?;
+///
+/// let mut written = Vec::new();
+/// write_expression_tree(&mut written, &tree, &DEFAULT_CONFIG)?;
+/// assert_eq!(written, bytes);
+/// # Ok(())
+/// # }
+/// ```
+///
 /// # Usage
 ///
 /// For usage, see the [`Composer`] trait.
@@ -40,10 +67,6 @@ pub fn read_expression<R: ?Sized + Read, C: ?Sized + Config, S: Sized, CP: ?Size
     )
 }
 
-// TODO:
-// PLEASE FIX THIS PIECE OF SHIT EXAMPLE AS SOON AS MORE ERGONOMIC EXPRESSION BUILDING PATTERNS ARE AVAILABLE.
-// THIS IS VERY PAINFUL TO LOOK AT
-
 /// Reads an [expression](https://github.com/jiricekcz/fef-specification/blob/main/expressions/Expression.md) from a byte stream and returns it as an [`ExprTree`].
 ///
 /// This function is a convenience function that simplifies calling [`read_expression`] with a composer that composes to an [`ExprTree`].
@@ -55,18 +78,6 @@ pub fn read_expression<R: ?Sized + Read, C: ?Sized + Config, S: Sized, CP: ?Size
 /// # use fef::v0::read::read_expression_into_tree;
 /// # use fef::v0::config::DEFAULT_CONFIG;
 /// # use fef::v0::expr::ExprTree;
-/// # use fef::v0::expr::Expr;
-/// # use fef::v0::expr::ExprFalseLiteral;
-/// # use fef::v0::expr::ExprVariable;
-/// # use fef::v0::expr::ExprUnsignedIntLiteral;
-/// # use fef::v0::expr::ExprAddition;
-/// # use fef::v0::expr::ExprSubtraction;
-/// # use fef::v0::expr::ExprMultiplication;
-/// # use fef::v0::expr::ExprDivision;
-/// # use fef::v0::expr::ExprSquareRoot;
-/// # use fef::v0::expr::ExprSquare;
-/// # use fef::v0::expr::ExprNegation;
-/// # use fef::v0::raw::VariableLengthEnum;
 /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
 /// let bytes: Vec<u8> = vec![
 ///     0x13, // Divide
@@ -87,53 +98,12 @@ pub fn read_expression<R: ?Sized + Read, C: ?Sized + Config, S: Sized, CP: ?Size
 ///             0x04, 0x00, // Variable 0 (a)
 /// ];
 ///
-/// let a: ExprTree = Expr::<ExprTree>::Variable(VariableLengthEnum::from(0).into()).into();
-/// let b: ExprTree = Expr::<ExprTree>::Variable(VariableLengthEnum::from(1).into()).into();
-/// let c: ExprTree = Expr::<ExprTree>::Variable(VariableLengthEnum::from(2).into()).into();
-///
-/// let four: ExprTree = Expr::<ExprTree>::UnsignedIntLiteral(
-///     ExprUnsignedIntLiteral::from(4u64)
-/// ).into();
-/// let two: ExprTree = Expr::<ExprTree>::UnsignedIntLiteral(
-///     ExprUnsignedIntLiteral::from(2u64)
-/// ).into();
-///
-/// let ac: ExprTree = Expr::<ExprTree>::Multiplication(
-///     ExprMultiplication::from((a.clone(), c.clone()))
-/// ).into();
-/// let four_ac: ExprTree = Expr::<ExprTree>::Multiplication(
-///     ExprMultiplication::from((four.clone(), ac))
-/// ).into();
-///
-/// let b_square: ExprTree = Expr::<ExprTree>::Square(
-///     ExprSquare::from(b.clone())
-/// ).into();
-/// let b_square_minus_four_ac: ExprTree = Expr::<ExprTree>::Subtraction(
-///     ExprSubtraction::from((b_square, four_ac))
-/// ).into();
-///
-/// let sqrt_b_square_minus_four_ac: ExprTree = Expr::<ExprTree>::SquareRoot(
-///     ExprSquareRoot::from(b_square_minus_four_ac)
-/// ).into();
-///
-/// let minus_b: ExprTree = Expr::<ExprTree>::Negation(
-///     ExprNegation::from(b.clone())
-/// ).into();
-///
-/// let numerator: ExprTree = Expr::<ExprTree>::Addition(
-///     ExprAddition::from((minus_b, sqrt_b_square_minus_four_ac))
-/// ).into();
-///
-///
-/// let denominator: ExprTree = Expr::<ExprTree>::Multiplication(
-///     ExprMultiplication::from((two.clone(), a.clone()))
-/// ).into();
-///
-///
-/// let fraction: ExprTree = Expr::<ExprTree>::Division(
-///     ExprDivision::from((numerator, denominator))
-/// ).into();
+/// let a = ExprTree::var(0);
+/// let b = ExprTree::var(1);
+/// let c = ExprTree::var(2);
 ///
+/// let discriminant = b.clone().square() - 4u8 * (a.clone() * c);
+/// let fraction = (-b + discriminant.sqrt()) / (2u8 * a);
 ///
 /// let mut reader = &mut bytes.as_slice();
 /// let expr = read_expression_into_tree(&mut reader, &DEFAULT_CONFIG)?;
@@ -160,3 +130,170 @@ impl Composer<ExprTree> for ExprTreeComposer {
         Ok(ExprTree::from(expr.into()))
     }
 }
+
+/// Composer evaluating expressions while they are being read.
+///
+/// Instead of building an expression tree, every expression is composed directly into its value using an [`Evaluator`].
+/// Children of an expression are always read (and evaluated) before the expression itself, so when the composer is asked
+/// to compose an expression, it already has the values of all of its children. This means no expression tree is ever allocated.
+///
+/// # Type parameters
+///
+/// - `E`: The evaluator defining the values and semantics of expressions. See the [`eval`](crate::v0::expr::eval) module.
+///
+/// # Example
+/// Evaluating the pythagorean theorem straight from the byte stream:
+/// ```rust
+/// # use fef::v0::read::{read_expression, EvaluatingComposer};
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # use fef::v0::expr::eval::{FloatEvaluator, VariableBindings};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let bytes: Vec<u8> = vec![
+///     0x22, // Square root
+///         0x10, // Add
+///             0x20, // Square
+///                 0x04, 0x00, // Variable 0 (a)
+///             0x20, // Square
+///                 0x04, 0x01, // Variable 1 (b)
+/// ];
+///
+/// let bindings: VariableBindings<f64> = [(0.into(), 3.0), (1.into(), 4.0)].into_iter().collect();
+/// let mut composer = EvaluatingComposer::new(FloatEvaluator::new(&bindings));
+///
+/// let value: f64 = read_expression(&mut bytes.as_slice(), &DEFAULT_CONFIG, &mut composer)?;
+/// assert_eq!(value, 5.0);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct EvaluatingComposer<E: Evaluator> {
+    evaluator: E,
+}
+
+impl<E: Evaluator> EvaluatingComposer<E> {
+    /// Creates a composer evaluating expressions using the given evaluator.
+    pub fn new(evaluator: E) -> Self {
+        Self { evaluator }
+    }
+
+    /// Returns a reference to the evaluator used by this composer.
+    pub fn evaluator(&self) -> &E {
+        &self.evaluator
+    }
+
+    /// Returns a mutable reference to the evaluator used by this composer.
+    pub fn evaluator_mut(&mut self) -> &mut E {
+        &mut self.evaluator
+    }
+
+    /// Converts the composer into the evaluator it uses.
+    pub fn into_inner(self) -> E {
+        self.evaluator
+    }
+}
+
+impl<E: Evaluator> From<E> for EvaluatingComposer<E> {
+    fn from(evaluator: E) -> Self {
+        Self::new(evaluator)
+    }
+}
+
+macro_rules! compose_literal {
+    ($name:ident, $type:ident, $evaluate:ident) => {
+        fn $name(&mut self, expr: $type<E::Value>) -> Result<E::Value, ComposeError<E::Error>> {
+            self.evaluator
+                .$evaluate(expr.value)
+                .map_err(ComposeError::CustomError)
+        }
+    };
+}
+
+macro_rules! compose_unary {
+    ($name:ident, $type:ident, $evaluate:ident) => {
+        fn $name(&mut self, expr: $type<E::Value>) -> Result<E::Value, ComposeError<E::Error>> {
+            self.evaluator
+                .$evaluate(expr.into_inner())
+                .map_err(ComposeError::CustomError)
+        }
+    };
+}
+
+macro_rules! compose_binary {
+    ($name:ident, $type:ident, $evaluate:ident) => {
+        fn $name(&mut self, expr: $type<E::Value>) -> Result<E::Value, ComposeError<E::Error>> {
+            let (lhs, rhs) = expr.into();
+            self.evaluator
+                .$evaluate(lhs, rhs)
+                .map_err(ComposeError::CustomError)
+        }
+    };
+}
+
+impl<E: Evaluator> Composer<E::Value> for EvaluatingComposer<E> {
+    type Error = E::Error;
+
+    fn compose_variable(
+        &mut self,
+        expr: ExprVariable<E::Value>,
+    ) -> Result<E::Value, ComposeError<E::Error>> {
+        self.evaluator
+            .variable(expr.as_ref())
+            .map_err(ComposeError::CustomError)
+    }
+
+    fn compose_true_literal(
+        &mut self,
+        _expr: ExprTrueLiteral<E::Value>,
+    ) -> Result<E::Value, ComposeError<E::Error>> {
+        self.evaluator
+            .true_literal()
+            .map_err(ComposeError::CustomError)
+    }
+
+    fn compose_false_literal(
+        &mut self,
+        _expr: ExprFalseLiteral<E::Value>,
+    ) -> Result<E::Value, ComposeError<E::Error>> {
+        self.evaluator
+            .false_literal()
+            .map_err(ComposeError::CustomError)
+    }
+
+    compose_literal!(
+        compose_signed_int_literal,
+        ExprSignedIntLiteral,
+        signed_int_literal
+    );
+    compose_literal!(
+        compose_unsigned_int_literal,
+        ExprUnsignedIntLiteral,
+        unsigned_int_literal
+    );
+    compose_literal!(
+        compose_binary_float_32_literal,
+        ExprBinaryFloat32Literal,
+        binary_float_32_literal
+    );
+    compose_literal!(
+        compose_binary_float_64_literal,
+        ExprBinaryFloat64Literal,
+        binary_float_64_literal
+    );
+
+    compose_binary!(compose_addition, ExprAddition, addition);
+    compose_binary!(compose_subtraction, ExprSubtraction, subtraction);
+    compose_binary!(compose_multiplication, ExprMultiplication, multiplication);
+    compose_binary!(compose_division, ExprDivision, division);
+    compose_binary!(compose_int_division, ExprIntDivision, int_division);
+    compose_binary!(compose_modulo, ExprModulo, modulo);
+    compose_binary!(compose_power, ExprPower, power);
+    compose_binary!(compose_root, ExprRoot, root);
+    compose_binary!(compose_int_root, ExprIntRoot, int_root);
+
+    compose_unary!(compose_negation, ExprNegation, negation);
+    compose_unary!(compose_square, ExprSquare, square);
+    compose_unary!(compose_cube, ExprCube, cube);
+    compose_unary!(compose_square_root, ExprSquareRoot, square_root);
+    compose_unary!(compose_cube_root, ExprCubeRoot, cube_root);
+    compose_unary!(compose_reciprocal, ExprReciprocal, reciprocal);
+}
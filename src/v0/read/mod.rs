@@ -16,6 +16,7 @@ mod metadata;
 
 pub use expression::read_expression;
 pub use expression::read_expression_into_tree;
+pub use expression::EvaluatingComposer;
 
 pub use configuration::read_configuration;
 pub use configuration::read_configuration_with_default_configuration;
@@ -0,0 +1,217 @@
+//! Error types for text formats of expressions.
+
+use thiserror::Error;
+
+use crate::v0::{
+    expr::error::DecomposeError,
+    raw::error::VariableLengthEnumError,
+    tokens::error::{ExprTokenReadError, FileContentTypeTokenError},
+};
+
+use super::Span;
+
+/// Error of the [infix parser](super::infix::parse_infix).
+#[derive(Debug, Error, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum InfixParseError {
+    #[error("unexpected character '{character}' at {span}")]
+    UnexpectedCharacter { character: char, span: Span },
+    #[error("expected {expected}, but found {found} at {span}")]
+    UnexpectedToken {
+        expected: String,
+        found: String,
+        span: Span,
+    },
+    #[error("literal {literal} at {span} is out of range")]
+    LiteralOutOfRange { literal: String, span: Span },
+    #[error("unknown function {name} at {span}")]
+    UnknownFunction { name: String, span: Span },
+    #[error("function {function} at {span} takes {expected} arguments, but {found} were given")]
+    ArgumentCount {
+        function: String,
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+    #[error("expression at {span} is nested deeper than {limit} levels")]
+    NestingTooDeep { limit: usize, span: Span },
+}
+
+impl InfixParseError {
+    /// Returns the part of the text the error refers to.
+    pub fn span(&self) -> Span {
+        match self {
+            InfixParseError::UnexpectedCharacter { span, .. }
+            | InfixParseError::UnexpectedToken { span, .. }
+            | InfixParseError::LiteralOutOfRange { span, .. }
+            | InfixParseError::UnknownFunction { span, .. }
+            | InfixParseError::ArgumentCount { span, .. }
+            | InfixParseError::NestingTooDeep { span, .. } => *span,
+        }
+    }
+}
+
+/// Error of the printers of text formats.
+#[derive(Debug, Error)]
+#[error("failed to write expression.")]
+pub enum TextWriteError<E>
+where
+    E: std::error::Error,
+{
+    FmtError(#[from] std::fmt::Error),
+    DecomposeError(#[from] DecomposeError<E>),
+}
+
+/// Error of the [LaTeX parser](super::latex::parse_latex).
+#[derive(Debug, Error, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum LatexParseError {
+    #[error("unexpected character '{character}' at {span}")]
+    UnexpectedCharacter { character: char, span: Span },
+    #[error("expected {expected}, but found {found} at {span}")]
+    UnexpectedToken {
+        expected: String,
+        found: String,
+        span: Span,
+    },
+    #[error("literal {literal} at {span} is out of range")]
+    LiteralOutOfRange { literal: String, span: Span },
+    #[error("command \\{command} at {span} is not supported")]
+    UnsupportedCommand { command: String, span: Span },
+    #[error("floor at {span} is only supported around a fraction")]
+    UnsupportedFloor { span: Span },
+    #[error("expression at {span} is nested deeper than {limit} levels")]
+    NestingTooDeep { limit: usize, span: Span },
+}
+
+impl LatexParseError {
+    /// Returns the part of the text the error refers to.
+    pub fn span(&self) -> Span {
+        match self {
+            LatexParseError::UnexpectedCharacter { span, .. }
+            | LatexParseError::UnexpectedToken { span, .. }
+            | LatexParseError::LiteralOutOfRange { span, .. }
+            | LatexParseError::UnsupportedCommand { span, .. }
+            | LatexParseError::UnsupportedFloor { span }
+            | LatexParseError::NestingTooDeep { span, .. } => *span,
+        }
+    }
+}
+
+/// Error of the [Content MathML parser](super::mathml::parse_content_mathml).
+#[derive(Debug, Error, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum MathmlParseError {
+    #[error("unexpected character '{character}' at {span}")]
+    UnexpectedCharacter { character: char, span: Span },
+    #[error("unexpected end of input at {span}")]
+    UnexpectedEnd { span: Span },
+    #[error("unknown entity &{entity}; at {span}")]
+    UnknownEntity { entity: String, span: Span },
+    #[error("end tag </{found}> at {span} doesn't match the start tag <{expected}>")]
+    MismatchedEndTag {
+        expected: String,
+        found: String,
+        span: Span,
+    },
+    #[error("expected {expected}, but found {found} at {span}")]
+    UnexpectedContent {
+        expected: String,
+        found: String,
+        span: Span,
+    },
+    #[error("element <{element}> at {span} has no equivalent expression")]
+    UnsupportedElement { element: String, span: Span },
+    #[error("number type \"{number_type}\" at {span} is not supported")]
+    UnsupportedNumberType { number_type: String, span: Span },
+    #[error("operator <{operator}/> at {span} takes {expected} operands, but {found} were given")]
+    ArgumentCount {
+        operator: String,
+        expected: String,
+        found: usize,
+        span: Span,
+    },
+    #[error("invalid number {text} at {span}")]
+    InvalidNumber { text: String, span: Span },
+    #[error("literal {literal} at {span} is out of range")]
+    LiteralOutOfRange { literal: String, span: Span },
+    #[error("element at {span} is nested deeper than {limit} elements")]
+    NestingTooDeep { limit: usize, span: Span },
+}
+
+impl MathmlParseError {
+    /// Returns the part of the text the error refers to.
+    pub fn span(&self) -> Span {
+        match self {
+            MathmlParseError::UnexpectedCharacter { span, .. }
+            | MathmlParseError::UnexpectedEnd { span }
+            | MathmlParseError::UnknownEntity { span, .. }
+            | MathmlParseError::MismatchedEndTag { span, .. }
+            | MathmlParseError::UnexpectedContent { span, .. }
+            | MathmlParseError::UnsupportedElement { span, .. }
+            | MathmlParseError::UnsupportedNumberType { span, .. }
+            | MathmlParseError::ArgumentCount { span, .. }
+            | MathmlParseError::InvalidNumber { span, .. }
+            | MathmlParseError::LiteralOutOfRange { span, .. }
+            | MathmlParseError::NestingTooDeep { span, .. } => *span,
+        }
+    }
+}
+
+/// Error of the [S-expression parsers](super::sexpr).
+#[derive(Debug, Error, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum SexprParseError {
+    #[error("unexpected character '{character}' at {span}")]
+    UnexpectedCharacter { character: char, span: Span },
+    #[error("expected {expected}, but found {found} at {span}")]
+    UnexpectedToken {
+        expected: String,
+        found: String,
+        span: Span,
+    },
+    #[error("literal {literal} at {span} is out of range")]
+    LiteralOutOfRange { literal: String, span: Span },
+    #[error("unknown operator {name} at {span}")]
+    UnknownOperator { name: String, span: Span },
+    #[error("{form} at {span} takes {expected} arguments, but {found} were given")]
+    ArgumentCount {
+        form: String,
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+}
+
+impl SexprParseError {
+    /// Returns the part of the text the error refers to.
+    pub fn span(&self) -> Span {
+        match self {
+            SexprParseError::UnexpectedCharacter { span, .. }
+            | SexprParseError::UnexpectedToken { span, .. }
+            | SexprParseError::LiteralOutOfRange { span, .. }
+            | SexprParseError::UnknownOperator { span, .. }
+            | SexprParseError::ArgumentCount { span, .. } => *span,
+        }
+    }
+}
+
+/// Error of the [S-expression writers](super::sexpr), which read the binary format.
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum SexprWriteError {
+    #[error("failed to write text")]
+    FmtError(#[from] std::fmt::Error),
+    #[error("failed to read input")]
+    IOError(#[from] std::io::Error),
+    #[error("failed to read variable length enum")]
+    VariableLengthEnumError(#[from] VariableLengthEnumError),
+    #[error("failed to read expression token")]
+    ExprTokenError(#[from] ExprTokenReadError),
+    #[error("failed to read file content type")]
+    FileContentTypeError(#[from] FileContentTypeTokenError),
+    #[error(
+        "metadata records don't fit into the {byte_size} bytes declared by the metadata header"
+    )]
+    MetadataOverflow { byte_size: usize },
+}
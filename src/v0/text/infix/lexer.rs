@@ -0,0 +1,153 @@
+use std::{iter::Peekable, str::Chars};
+
+use crate::v0::text::{error::InfixParseError, Position, Span};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Token {
+    Integer(String),
+    Float(String),
+    Identifier(String),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    DoubleSlash,
+    Percent,
+    Caret,
+    LeftParen,
+    RightParen,
+    Comma,
+    End,
+}
+
+/// Formats the token the way it is shown in error messages.
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Integer(text) | Token::Float(text) => write!(f, "number {}", text),
+            Token::Identifier(name) => write!(f, "identifier {}", name),
+            Token::Plus => write!(f, "'+'"),
+            Token::Minus => write!(f, "'-'"),
+            Token::Star => write!(f, "'*'"),
+            Token::Slash => write!(f, "'/'"),
+            Token::DoubleSlash => write!(f, "'//'"),
+            Token::Percent => write!(f, "'%'"),
+            Token::Caret => write!(f, "'^'"),
+            Token::LeftParen => write!(f, "'('"),
+            Token::RightParen => write!(f, "')'"),
+            Token::Comma => write!(f, "','"),
+            Token::End => write!(f, "end of input"),
+        }
+    }
+}
+
+pub(super) struct Lexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    position: Position,
+}
+
+impl<'a> Lexer<'a> {
+    pub(super) fn new(text: &'a str) -> Self {
+        Self {
+            chars: text.chars().peekable(),
+            position: Position::START,
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let character = self.chars.next()?;
+        self.position = self.position.advance(character);
+        Some(character)
+    }
+
+    fn bump_while(&mut self, text: &mut String, predicate: impl Fn(char) -> bool) {
+        while let Some(&character) = self.chars.peek() {
+            if !predicate(character) {
+                break;
+            }
+            text.push(character);
+            self.bump();
+        }
+    }
+
+    /// Lexes the whole text, the last token is always [`Token::End`].
+    pub(super) fn tokenize(mut self) -> Result<Vec<(Token, Span)>, InfixParseError> {
+        let mut tokens = Vec::new();
+        loop {
+            self.bump_while(&mut String::new(), char::is_whitespace);
+            let start = self.position;
+            let token = match self.chars.peek().copied() {
+                None => {
+                    tokens.push((Token::End, Span::new(start, start)));
+                    return Ok(tokens);
+                }
+                Some(character) if character.is_ascii_digit() => self.number(),
+                Some(character) if character.is_alphabetic() || character == '_' => {
+                    let mut name = String::new();
+                    self.bump_while(&mut name, |c| c.is_alphanumeric() || c == '_');
+                    Token::Identifier(name)
+                }
+                Some(character) => {
+                    self.bump();
+                    match character {
+                        '+' => Token::Plus,
+                        '-' => Token::Minus,
+                        '*' => Token::Star,
+                        '/' if self.chars.peek() == Some(&'/') => {
+                            self.bump();
+                            Token::DoubleSlash
+                        }
+                        '/' => Token::Slash,
+                        '%' => Token::Percent,
+                        '^' => Token::Caret,
+                        '(' => Token::LeftParen,
+                        ')' => Token::RightParen,
+                        ',' => Token::Comma,
+                        _ => {
+                            return Err(InfixParseError::UnexpectedCharacter {
+                                character,
+                                span: Span::new(start, self.position),
+                            })
+                        }
+                    }
+                }
+            };
+            tokens.push((token, Span::new(start, self.position)));
+        }
+    }
+
+    /// Lexes a number with an optional fraction and exponent.
+    fn number(&mut self) -> Token {
+        let mut text = String::new();
+        let mut is_float = false;
+        self.bump_while(&mut text, |c| c.is_ascii_digit());
+        if self.chars.peek() == Some(&'.') {
+            is_float = true;
+            text.push('.');
+            self.bump();
+            self.bump_while(&mut text, |c| c.is_ascii_digit());
+        }
+        if let Some(&exponent @ ('e' | 'E')) = self.chars.peek() {
+            let mut lookahead = self.chars.clone();
+            lookahead.next();
+            let has_sign = matches!(lookahead.peek(), Some('+' | '-'));
+            if has_sign {
+                lookahead.next();
+            }
+            if lookahead.peek().is_some_and(char::is_ascii_digit) {
+                is_float = true;
+                text.push(exponent);
+                self.bump();
+                if has_sign {
+                    text.extend(self.bump());
+                }
+                self.bump_while(&mut text, |c| c.is_ascii_digit());
+            }
+        }
+        if is_float {
+            Token::Float(text)
+        } else {
+            Token::Integer(text)
+        }
+    }
+}
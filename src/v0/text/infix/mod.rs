@@ -0,0 +1,81 @@
+//! Infix notation of expressions, e.g. `sqrt(x^2 + y^2) / 2`.
+//!
+//! Expressions are parsed by [`parse_infix`] and written by the [`InfixPrinter`].
+//!
+//! # Syntax
+//! Operators, from the lowest to the highest precedence:
+//!
+//! | Syntax | Expression | Associativity |
+//! |--------|------------|---------------|
+//! | `a + b`, `a - b` | [`Addition`](crate::v0::expr::Expr::Addition), [`Subtraction`](crate::v0::expr::Expr::Subtraction) | left |
+//! | `a * b`, `a / b`, `a // b`, `a % b` | [`Multiplication`](crate::v0::expr::Expr::Multiplication), [`Division`](crate::v0::expr::Expr::Division), [`IntDivision`](crate::v0::expr::Expr::IntDivision), [`Modulo`](crate::v0::expr::Expr::Modulo) | left |
+//! | `-a` | [`Negation`](crate::v0::expr::Expr::Negation) | |
+//! | `a ^ b` | [`Power`](crate::v0::expr::Expr::Power) | right |
+//!
+//! The exponent of a power may be negated, so `2^-1` is a valid expression. Negation has a lower precedence than power, so `-x^2` is `-(x^2)`.
+//!
+//! The remaining expressions are written as functions:
+//!
+//! | Syntax | Expression |
+//! |--------|------------|
+//! | `sqrt(a)` | [`SquareRoot`](crate::v0::expr::Expr::SquareRoot) |
+//! | `cbrt(a)` | [`CubeRoot`](crate::v0::expr::Expr::CubeRoot) |
+//! | `square(a)` | [`Square`](crate::v0::expr::Expr::Square) |
+//! | `cube(a)` | [`Cube`](crate::v0::expr::Expr::Cube) |
+//! | `recip(a)` | [`Reciprocal`](crate::v0::expr::Expr::Reciprocal) |
+//! | `root(a, n)` | [`Root`](crate::v0::expr::Expr::Root) of `a` with degree `n` |
+//! | `introot(a, n)` | [`IntRoot`](crate::v0::expr::Expr::IntRoot) of `a` with degree `n` |
+//!
+//! # Literals and variables
+//! * `true` and `false` are [boolean literals](crate::v0::expr::ExprTrueLiteral).
+//! * Numbers without a fraction or an exponent (`42`) are [unsigned integer literals](crate::v0::expr::ExprUnsignedIntLiteral).
+//!   A minus sign directly in front of an integer makes it a [signed integer literal](crate::v0::expr::ExprSignedIntLiteral) (`-42`),
+//!   while a minus in front of parentheses is a negation (`-(42)`).
+//! * Numbers with a fraction or an exponent (`1.5`, `2e-3`) are float literals. A [32-bit float](crate::v0::expr::ExprBinaryFloat32Literal)
+//!   is used if it represents the number exactly, otherwise a [64-bit float](crate::v0::expr::ExprBinaryFloat64Literal) is used.
+//!   A minus sign directly in front of a float makes it negative.
+//! * Any other identifier (letters, digits and underscores, not starting with a digit) is a [variable](crate::v0::expr::ExprVariable).
+//!   Identifiers of variables are assigned using [`VariableNames`](crate::v0::text::VariableNames).
+//!
+//! # Examples
+//! ```rust
+//! # use fef::v0::text::{infix::parse_infix, VariableNames};
+//! # use fef::v0::expr::{Expr, eval::{evaluate_tree, FloatEvaluator, VariableBindings}};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut names = VariableNames::new();
+//! let tree = parse_infix("sqrt(x^2 + y^2) / 2", &mut names)?;
+//!
+//! assert!(matches!(tree.inner(), Expr::Division(_)));
+//!
+//! let mut bindings = VariableBindings::new();
+//! bindings.bind(names.get_or_insert("x"), 6.0);
+//! bindings.bind(names.get_or_insert("y"), 8.0);
+//! assert_eq!(evaluate_tree(&tree, &mut FloatEvaluator::new(&bindings))?, 5.0);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Parsing errors carry the position of the problem:
+//! ```rust
+//! # use fef::v0::text::{infix::parse_infix, Position, VariableNames};
+//! let error = parse_infix("1 +\n  * 2", &mut VariableNames::new()).unwrap_err();
+//!
+//! assert_eq!(error.span().start, Position { line: 2, column: 3 });
+//! assert_eq!(error.to_string(), "expected an expression, but found '*' at 2:3-2:4");
+//! ```
+//!
+//! Text nested deeper than [`MAX_NESTING_DEPTH`](crate::v0::text::MAX_NESTING_DEPTH) is rejected instead of overflowing the stack:
+//! ```rust
+//! # use fef::v0::text::{error::InfixParseError, infix::parse_infix, VariableNames};
+//! let text = "(".repeat(2000) + "x" + &")".repeat(2000);
+//! let error = parse_infix(&text, &mut VariableNames::new()).unwrap_err();
+//!
+//! assert!(matches!(error, InfixParseError::NestingTooDeep { .. }));
+//! ```
+
+mod lexer;
+mod parse;
+mod print;
+
+pub use parse::parse_infix;
+pub use print::{InfixPrinter, InfixStyle};
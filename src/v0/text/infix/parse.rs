@@ -0,0 +1,251 @@
+use crate::v0::{
+    expr::{
+        float_literal, negate_literal, tree, ExprAddition, ExprCube, ExprCubeRoot, ExprDivision,
+        ExprFalseLiteral, ExprIntDivision, ExprIntRoot, ExprModulo, ExprMultiplication,
+        ExprNegation, ExprPower, ExprReciprocal, ExprRoot, ExprSquare, ExprSquareRoot,
+        ExprSubtraction, ExprTree, ExprTrueLiteral, ExprUnsignedIntLiteral, ExprVariable,
+    },
+    text::{error::InfixParseError, Span, VariableNames, MAX_NESTING_DEPTH},
+};
+
+use super::lexer::{Lexer, Token};
+
+/// Parses an expression written in the [infix notation](super).
+///
+/// Variables are looked up by name in `variables`. Names that are not mapped yet are assigned new identifiers,
+/// see [`VariableNames::get_or_insert`].
+///
+/// # Errors
+/// Returns an [`InfixParseError`] with the span of the offending part of the text if the text is not a valid expression
+/// or if it is nested deeper than [`MAX_NESTING_DEPTH`].
+pub fn parse_infix(text: &str, variables: &mut VariableNames) -> Result<ExprTree, InfixParseError> {
+    let tokens = Lexer::new(text).tokenize()?;
+    let mut parser = Parser {
+        tokens,
+        index: 0,
+        depth: 0,
+        variables,
+    };
+    let tree = parser.expression()?;
+    match parser.peek() {
+        Token::End => Ok(tree),
+        _ => Err(parser.unexpected("an operator or end of input")),
+    }
+}
+
+struct Parser<'a> {
+    tokens: Vec<(Token, Span)>,
+    index: usize,
+    /// Number of operands being parsed, which contain the current one.
+    depth: usize,
+    variables: &'a mut VariableNames,
+}
+
+fn binary(token: &Token, lhs: ExprTree, rhs: ExprTree) -> ExprTree {
+    match token {
+        Token::Plus => tree(ExprAddition::from((lhs, rhs))),
+        Token::Minus => tree(ExprSubtraction::from((lhs, rhs))),
+        Token::Star => tree(ExprMultiplication::from((lhs, rhs))),
+        Token::Slash => tree(ExprDivision::from((lhs, rhs))),
+        Token::DoubleSlash => tree(ExprIntDivision::from((lhs, rhs))),
+        Token::Percent => tree(ExprModulo::from((lhs, rhs))),
+        Token::Caret => tree(ExprPower::from((lhs, rhs))),
+        _ => unreachable!("only binary operator tokens are passed"),
+    }
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.index].0
+    }
+
+    fn span(&self) -> Span {
+        self.tokens[self.index].1
+    }
+
+    fn next(&mut self) -> (Token, Span) {
+        let token = self.tokens[self.index].clone();
+        if token.0 != Token::End {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn unexpected(&self, expected: &str) -> InfixParseError {
+        InfixParseError::UnexpectedToken {
+            expected: expected.to_string(),
+            found: self.peek().to_string(),
+            span: self.span(),
+        }
+    }
+
+    fn expect(&mut self, token: Token) -> Result<Span, InfixParseError> {
+        if *self.peek() == token {
+            Ok(self.next().1)
+        } else {
+            Err(self.unexpected(&token.to_string()))
+        }
+    }
+
+    /// `expression := term (('+' | '-') term)*`
+    fn expression(&mut self) -> Result<ExprTree, InfixParseError> {
+        let mut lhs = self.term()?;
+        while matches!(self.peek(), Token::Plus | Token::Minus) {
+            let (operator, _) = self.next();
+            let rhs = self.term()?;
+            lhs = binary(&operator, lhs, rhs);
+        }
+        Ok(lhs)
+    }
+
+    /// `term := unary (('*' | '/' | '//' | '%') unary)*`
+    fn term(&mut self) -> Result<ExprTree, InfixParseError> {
+        let mut lhs = self.unary()?;
+        while matches!(
+            self.peek(),
+            Token::Star | Token::Slash | Token::DoubleSlash | Token::Percent
+        ) {
+            let (operator, _) = self.next();
+            let rhs = self.unary()?;
+            lhs = binary(&operator, lhs, rhs);
+        }
+        Ok(lhs)
+    }
+
+    /// `unary := '-' unary | power`
+    ///
+    /// Every nested operand is parsed by this rule, so it limits the nesting depth.
+    fn unary(&mut self) -> Result<ExprTree, InfixParseError> {
+        if self.depth >= MAX_NESTING_DEPTH {
+            return Err(InfixParseError::NestingTooDeep {
+                limit: MAX_NESTING_DEPTH,
+                span: self.span(),
+            });
+        }
+        self.depth += 1;
+        let result = self.negation();
+        self.depth -= 1;
+        result
+    }
+
+    /// Parses the `unary` rule once the depth is checked.
+    ///
+    /// A minus sign directly followed by a number literal is parsed as a negative literal.
+    fn negation(&mut self) -> Result<ExprTree, InfixParseError> {
+        if *self.peek() != Token::Minus {
+            return self.power();
+        }
+        self.next();
+        let is_literal = matches!(self.peek(), Token::Integer(_) | Token::Float(_));
+        let inner = self.unary()?;
+        if is_literal {
+            if let Some(literal) = negate_literal(inner.inner()) {
+                return Ok(literal);
+            }
+        }
+        Ok(tree(ExprNegation::from(inner)))
+    }
+
+    /// `power := primary ('^' unary)?`
+    fn power(&mut self) -> Result<ExprTree, InfixParseError> {
+        let base = self.primary()?;
+        if *self.peek() != Token::Caret {
+            return Ok(base);
+        }
+        let (operator, _) = self.next();
+        let exponent = self.unary()?;
+        Ok(binary(&operator, base, exponent))
+    }
+
+    /// `primary := number | 'true' | 'false' | variable | function '(' arguments ')' | '(' expression ')'`
+    fn primary(&mut self) -> Result<ExprTree, InfixParseError> {
+        let (token, span) = self.next();
+        match token {
+            Token::Integer(text) => match text.parse::<u64>() {
+                Ok(value) => Ok(tree(ExprUnsignedIntLiteral::from(value))),
+                Err(_) => Err(InfixParseError::LiteralOutOfRange {
+                    literal: text,
+                    span,
+                }),
+            },
+            Token::Float(text) => match text.parse::<f64>() {
+                Ok(value) if value.is_finite() => Ok(float_literal(value)),
+                _ => Err(InfixParseError::LiteralOutOfRange {
+                    literal: text,
+                    span,
+                }),
+            },
+            Token::Identifier(name) if name == "true" => Ok(tree(ExprTrueLiteral::from(()))),
+            Token::Identifier(name) if name == "false" => Ok(tree(ExprFalseLiteral::from(()))),
+            Token::Identifier(name) => {
+                if *self.peek() == Token::LeftParen {
+                    return self.function(name, span);
+                }
+                if FUNCTIONS.iter().any(|(function, _)| *function == name) {
+                    return Err(self.unexpected("'('"));
+                }
+                let identifier = self.variables.get_or_insert(&name);
+                Ok(tree(ExprVariable::from(identifier)))
+            }
+            Token::LeftParen => {
+                let inner = self.expression()?;
+                self.expect(Token::RightParen)?;
+                Ok(inner)
+            }
+            _ => {
+                self.index -= 1;
+                Err(self.unexpected("an expression"))
+            }
+        }
+    }
+
+    fn function(&mut self, name: String, name_span: Span) -> Result<ExprTree, InfixParseError> {
+        let arity = match FUNCTIONS.iter().find(|(function, _)| *function == name) {
+            Some((_, arity)) => *arity,
+            None => {
+                return Err(InfixParseError::UnknownFunction {
+                    name,
+                    span: name_span,
+                })
+            }
+        };
+        self.expect(Token::LeftParen)?;
+        let mut arguments = vec![self.expression()?];
+        while *self.peek() == Token::Comma {
+            self.next();
+            arguments.push(self.expression()?);
+        }
+        let end = self.expect(Token::RightParen)?;
+        if arguments.len() != arity {
+            return Err(InfixParseError::ArgumentCount {
+                function: name,
+                expected: arity,
+                found: arguments.len(),
+                span: name_span.join(end),
+            });
+        }
+        let mut arguments = arguments.into_iter();
+        let mut argument = || arguments.next().expect("argument count is checked");
+        Ok(match name.as_str() {
+            "sqrt" => tree(ExprSquareRoot::from(argument())),
+            "cbrt" => tree(ExprCubeRoot::from(argument())),
+            "square" => tree(ExprSquare::from(argument())),
+            "cube" => tree(ExprCube::from(argument())),
+            "recip" => tree(ExprReciprocal::from(argument())),
+            "root" => tree(ExprRoot::from((argument(), argument()))),
+            "introot" => tree(ExprIntRoot::from((argument(), argument()))),
+            _ => unreachable!("all functions are matched"),
+        })
+    }
+}
+
+/// Names of the functions and their number of arguments.
+pub(super) const FUNCTIONS: [(&str, usize); 7] = [
+    ("sqrt", 1),
+    ("cbrt", 1),
+    ("square", 1),
+    ("cube", 1),
+    ("recip", 1),
+    ("root", 2),
+    ("introot", 2),
+];
@@ -0,0 +1,318 @@
+use std::fmt::Write;
+
+use crate::v0::{
+    expr::{
+        traits::{BinaryOperationExpr, Decomposer, DecompositionRefContainer, UnaryOperationExpr},
+        Expr, ExprTree,
+    },
+    raw::VariableLengthEnum,
+    text::{
+        error::TextWriteError,
+        print::{self, precedence, Notation, Part, ADDITIVE, ATOM, MULTIPLICATIVE, PREFIX},
+        VariableNames,
+    },
+};
+
+/// Characters used by the [`InfixPrinter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum InfixStyle {
+    /// Only ASCII characters are used. The output can be parsed back by [`parse_infix`](super::parse_infix),
+    /// with the exceptions listed in [what is lost](InfixPrinter#what-is-lost).
+    #[default]
+    Ascii,
+    /// [`Square`](crate::v0::expr::Expr::Square), [`Cube`](crate::v0::expr::Expr::Cube), [`SquareRoot`](crate::v0::expr::Expr::SquareRoot)
+    /// and [`CubeRoot`](crate::v0::expr::Expr::CubeRoot) are written as `x²`, `x³`, `√x` and `∛x`, unnamed variables have subscript indices.
+    Unicode,
+}
+
+/// Printer of expressions in the [infix notation](super).
+///
+/// Only parentheses required by the precedence and associativity of operators are written, so that parsing the output in
+/// the [`Ascii`](InfixStyle::Ascii) style results in the same operations. Literals are written as plain numbers, so some of
+/// them are parsed back differently, see [what is lost](#what-is-lost).
+///
+/// Variables are written by their names from the given [`VariableNames`], unnamed variables (or all variables, if no names are given)
+/// are written as `x` followed by their identifier, e.g. `x0`.
+///
+/// [`ExprTree`] implements [`Display`](std::fmt::Display) using this printer with the [`Ascii`](InfixStyle::Ascii) style and no variable names.
+///
+/// # What is lost
+/// The infix notation has no syntax for the type of a literal, so parsing the output doesn't restore these literals:
+/// * [Signed integer literals](crate::v0::expr::ExprSignedIntLiteral) that are not negative are written as `5` and parsed
+///   back as [unsigned integer literals](crate::v0::expr::ExprUnsignedIntLiteral).
+/// * Float literals are written in their shortest form, e.g. `1.5` or `0.1`, and parsed back as a 32-bit float if it represents
+///   the written number exactly, otherwise as a 64-bit float. So a 64-bit `1.5` or `-0.0` comes back as a 32-bit float,
+///   and a 32-bit `0.1` comes back as the 64-bit float closest to `0.1`, which is a different number.
+/// * NaN and infinities are written as `NaN`, `inf` and `-inf`, which are parsed back as the variable named `NaN`, the variable
+///   named `inf` and its negation.
+///
+/// ```rust
+/// # use fef::v0::expr::{Expr, ExprTree};
+/// # use fef::v0::text::{infix::parse_infix, VariableNames};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let parse = |text: &str| parse_infix(text, &mut VariableNames::new());
+///
+/// assert_eq!(ExprTree::lit(5i64).to_string(), "5");
+/// assert!(matches!(parse("5")?.inner(), Expr::UnsignedIntLiteral(_)));
+///
+/// assert_eq!(ExprTree::lit(-0.0f64).to_string(), "-0.0");
+/// assert!(matches!(parse("-0.0")?.inner(), Expr::BinaryFloat32Literal(_)));
+///
+/// assert_eq!(ExprTree::lit(f64::NAN).to_string(), "NaN");
+/// assert!(matches!(parse("NaN")?.inner(), Expr::Variable(_)));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Examples
+/// ```rust
+/// # use fef::v0::text::{infix::{parse_infix, InfixPrinter, InfixStyle}, VariableNames};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut names = VariableNames::new();
+/// let tree = parse_infix("(a - (b - c)) / square(a + 1) + sqrt(b)^2", &mut names)?;
+///
+/// assert_eq!(tree.to_string(), "(x0 - (x1 - x2)) / square(x0 + 1) + sqrt(x1)^2");
+///
+/// let printer = InfixPrinter::new(InfixStyle::Unicode, Some(&names));
+/// assert_eq!(printer.print(&tree), "(a - (b - c)) / (a + 1)² + (√b)^2");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct InfixPrinter<'a> {
+    style: InfixStyle,
+    names: Option<&'a VariableNames>,
+}
+
+impl<'a> InfixPrinter<'a> {
+    /// Creates a printer with the given style, resolving variable names from `names`.
+    pub fn new(style: InfixStyle, names: Option<&'a VariableNames>) -> Self {
+        Self { style, names }
+    }
+
+    /// Writes an expression using a [`Decomposer`] to access child expressions.
+    ///
+    /// The expression is written without recursion, so arbitrarily deep expressions cannot overflow the stack.
+    pub fn write<S: Sized, DP: ?Sized + Decomposer<S>, W: ?Sized + Write>(
+        &self,
+        storage: &S,
+        decomposer: &mut DP,
+        writer: &mut W,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        let expr = decomposer.decompose_as_ref(storage)?.inner_as_ref();
+        print::write_parts(expr, writer, |expr, parts| {
+            self.push_expr(expr, decomposer, parts)
+        })
+    }
+
+    /// Writes an [`ExprTree`] into a [`String`].
+    pub fn print(&self, tree: &ExprTree) -> String {
+        let mut output = String::new();
+        self.write_tree(tree, &mut output)
+            .expect("writing into a string never fails");
+        output
+    }
+
+    /// Writes an [`ExprTree`].
+    ///
+    /// This function is a convenience function that simplifies calling [`write`](InfixPrinter::write) with a decomposer for [`ExprTree`].
+    pub fn write_tree<W: ?Sized + Write>(
+        &self,
+        tree: &ExprTree,
+        writer: &mut W,
+    ) -> std::fmt::Result {
+        print::write_tree(tree, writer, |tree, decomposer, writer| {
+            self.write(tree, decomposer, writer)
+        })
+    }
+
+    fn notation(&self) -> Notation {
+        match self.style {
+            InfixStyle::Ascii => Notation::Functions,
+            InfixStyle::Unicode => Notation::Symbols,
+        }
+    }
+
+    /// Pushes a child expression, in parentheses if its precedence is lower than `minimum`.
+    fn push_child<'e, S: Sized, DP: ?Sized + Decomposer<S>>(
+        &self,
+        storage: &'e S,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+        minimum: u8,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        let expr = decomposer.decompose_as_ref(storage)?.inner_as_ref();
+        if precedence(expr, self.notation()) < minimum {
+            parts.extend([Part::text("("), Part::Expr(expr), Part::text(")")]);
+        } else {
+            parts.push(Part::Expr(expr));
+        }
+        Ok(())
+    }
+
+    /// Pushes a left-associative binary operator.
+    fn push_binary<'e, S: Sized, B: BinaryOperationExpr<S>, DP: ?Sized + Decomposer<S>>(
+        &self,
+        expr: &'e B,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+        operator: &str,
+        precedence: u8,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        self.push_child(expr.lhs(), decomposer, parts, precedence)?;
+        parts.push(Part::text(format!(" {} ", operator)));
+        self.push_child(expr.rhs(), decomposer, parts, precedence + 1)
+    }
+
+    fn push_function<'e, S: Sized, DP: ?Sized + Decomposer<S>>(
+        &self,
+        name: &str,
+        arguments: &[&'e S],
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        parts.push(Part::text(format!("{}(", name)));
+        for (index, argument) in arguments.iter().enumerate() {
+            if index > 0 {
+                parts.push(Part::text(", "));
+            }
+            self.push_child(*argument, decomposer, parts, ADDITIVE)?;
+        }
+        parts.push(Part::text(")"));
+        Ok(())
+    }
+
+    fn push_unary<'e, S: Sized, U: UnaryOperationExpr<S>, DP: ?Sized + Decomposer<S>>(
+        &self,
+        expr: &'e U,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+        ascii: &str,
+        unicode: Option<&'static str>,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        match (self.style, unicode) {
+            (InfixStyle::Unicode, Some(symbol @ ("√" | "∛"))) => {
+                parts.push(Part::text(symbol));
+                self.push_child(expr.inner(), decomposer, parts, ATOM)
+            }
+            (InfixStyle::Unicode, Some(symbol)) => {
+                self.push_child(expr.inner(), decomposer, parts, ATOM)?;
+                parts.push(Part::text(symbol));
+                Ok(())
+            }
+            _ => self.push_function(ascii, &[expr.inner()], decomposer, parts),
+        }
+    }
+
+    fn write_variable<W: ?Sized + Write>(
+        &self,
+        variable: &VariableLengthEnum,
+        writer: &mut W,
+    ) -> std::fmt::Result {
+        if let Some(name) = self.names.and_then(|names| names.name(variable)) {
+            return writer.write_str(name);
+        }
+        match self.style {
+            InfixStyle::Ascii => write!(writer, "x{}", variable),
+            InfixStyle::Unicode => {
+                writer.write_char('x')?;
+                for digit in variable.to_string().chars() {
+                    let subscript = char::from_u32('₀' as u32 + digit.to_digit(10).unwrap_or(0))
+                        .expect("subscript digits are valid characters");
+                    writer.write_char(subscript)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Pushes the text and the operands of an expression in the order they are written.
+    fn push_expr<'e, S: Sized, DP: ?Sized + Decomposer<S>>(
+        &self,
+        expr: &'e Expr<S>,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        match expr {
+            Expr::Variable(expr) => {
+                let mut name = String::new();
+                self.write_variable(expr.as_ref(), &mut name)?;
+                parts.push(Part::text(name));
+            }
+            Expr::TrueLiteral(_) => parts.push(Part::text("true")),
+            Expr::FalseLiteral(_) => parts.push(Part::text("false")),
+            Expr::SignedIntLiteral(expr) => parts.push(Part::text(expr.value.to_string())),
+            Expr::UnsignedIntLiteral(expr) => parts.push(Part::text(expr.value.to_string())),
+            // Debug formatting always contains a decimal point or an exponent, so the literal is not parsed as an integer.
+            Expr::BinaryFloat32Literal(expr) => parts.push(Part::text(format!("{:?}", expr.value))),
+            Expr::BinaryFloat64Literal(expr) => parts.push(Part::text(format!("{:?}", expr.value))),
+            Expr::Addition(expr) => self.push_binary(expr, decomposer, parts, "+", ADDITIVE)?,
+            Expr::Subtraction(expr) => self.push_binary(expr, decomposer, parts, "-", ADDITIVE)?,
+            Expr::Multiplication(expr) => {
+                self.push_binary(expr, decomposer, parts, "*", MULTIPLICATIVE)?
+            }
+            Expr::Division(expr) => {
+                self.push_binary(expr, decomposer, parts, "/", MULTIPLICATIVE)?
+            }
+            Expr::IntDivision(expr) => {
+                self.push_binary(expr, decomposer, parts, "//", MULTIPLICATIVE)?
+            }
+            Expr::Modulo(expr) => self.push_binary(expr, decomposer, parts, "%", MULTIPLICATIVE)?,
+            Expr::Power(expr) => {
+                self.push_child(expr.lhs(), decomposer, parts, ATOM)?;
+                parts.push(Part::text("^"));
+                self.push_child(expr.rhs(), decomposer, parts, PREFIX)?;
+            }
+            Expr::Root(expr) => {
+                self.push_function("root", &[expr.lhs(), expr.rhs()], decomposer, parts)?
+            }
+            Expr::IntRoot(expr) => {
+                self.push_function("introot", &[expr.lhs(), expr.rhs()], decomposer, parts)?
+            }
+            Expr::Negation(expr) => {
+                parts.push(Part::text("-"));
+                // A minus sign directly in front of a number literal would be parsed as a negative literal.
+                let operand = decomposer.decompose_as_ref(expr.inner())?.inner_as_ref();
+                let is_number = matches!(
+                    operand,
+                    Expr::SignedIntLiteral(_)
+                        | Expr::UnsignedIntLiteral(_)
+                        | Expr::BinaryFloat32Literal(_)
+                        | Expr::BinaryFloat64Literal(_)
+                );
+                let minimum = if is_number { ATOM + 1 } else { PREFIX };
+                self.push_child(expr.inner(), decomposer, parts, minimum)?;
+            }
+            Expr::Square(expr) => self.push_unary(expr, decomposer, parts, "square", Some("²"))?,
+            Expr::Cube(expr) => self.push_unary(expr, decomposer, parts, "cube", Some("³"))?,
+            Expr::SquareRoot(expr) => {
+                self.push_unary(expr, decomposer, parts, "sqrt", Some("√"))?
+            }
+            Expr::CubeRoot(expr) => self.push_unary(expr, decomposer, parts, "cbrt", Some("∛"))?,
+            Expr::Reciprocal(expr) => self.push_unary(expr, decomposer, parts, "recip", None)?,
+        };
+        Ok(())
+    }
+}
+
+/// Formats the expression in the [infix notation](crate::v0::text::infix) using the [`Ascii`](InfixStyle::Ascii) style.
+///
+/// Deeply nested trees, which can be read from untrusted input, are formatted without overflowing the stack:
+/// ```rust
+/// # use fef::v0::read::read_expression_into_tree;
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut bytes = vec![0x17; 100_000]; // Negations
+/// bytes.extend([0x04, 0x00]); // Variable 0
+/// let tree = read_expression_into_tree(&mut &bytes[..], &DEFAULT_CONFIG)?;
+///
+/// assert_eq!(tree.to_string(), "-".repeat(100_000) + "x0");
+/// # Ok(())
+/// # }
+/// ```
+impl std::fmt::Display for ExprTree {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        InfixPrinter::default().write_tree(self, f)
+    }
+}
@@ -0,0 +1,218 @@
+use std::{iter::Peekable, str::Chars};
+
+use crate::v0::text::{error::LatexParseError, Position, Span};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Token {
+    Integer(String),
+    Float(String),
+    Letter(char),
+    /// A command, without the leading backslash.
+    Command(String),
+    /// A command with a text argument, e.g. `\mathit{speed}`.
+    Word {
+        command: String,
+        content: String,
+    },
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Caret,
+    Underscore,
+    LeftBrace,
+    RightBrace,
+    LeftParen,
+    RightParen,
+    LeftBracket,
+    RightBracket,
+    End,
+}
+
+/// Formats the token the way it is shown in error messages.
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Integer(text) | Token::Float(text) => write!(f, "number {}", text),
+            Token::Letter(letter) => write!(f, "letter {}", letter),
+            Token::Command(command) => write!(f, "\\{}", command),
+            Token::Word { command, content } => write!(f, "\\{}{{{}}}", command, content),
+            Token::Plus => write!(f, "'+'"),
+            Token::Minus => write!(f, "'-'"),
+            Token::Star => write!(f, "'*'"),
+            Token::Slash => write!(f, "'/'"),
+            Token::Caret => write!(f, "'^'"),
+            Token::Underscore => write!(f, "'_'"),
+            Token::LeftBrace => write!(f, "'{{'"),
+            Token::RightBrace => write!(f, "'}}'"),
+            Token::LeftParen => write!(f, "'('"),
+            Token::RightParen => write!(f, "')'"),
+            Token::LeftBracket => write!(f, "'['"),
+            Token::RightBracket => write!(f, "']'"),
+            Token::End => write!(f, "end of input"),
+        }
+    }
+}
+
+/// Commands that only change spacing and are skipped.
+const SPACING_COMMANDS: [&str; 7] = [",", ":", ";", "!", " ", "quad", "qquad"];
+
+/// Commands whose argument is read as text.
+const WORD_COMMANDS: [&str; 4] = ["mathit", "mathrm", "operatorname", "text"];
+
+/// Escapes written by the [`LatexPrinter`](super::LatexPrinter) inside of `\text`.
+const TEXT_ESCAPES: [(&str, char); 3] = [
+    ("textbackslash", '\\'),
+    ("textasciitilde", '~'),
+    ("textasciicircum", '^'),
+];
+
+pub(super) struct Lexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    position: Position,
+}
+
+impl<'a> Lexer<'a> {
+    pub(super) fn new(text: &'a str) -> Self {
+        Self {
+            chars: text.chars().peekable(),
+            position: Position::START,
+        }
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let character = self.chars.next()?;
+        self.position = self.position.advance(character);
+        Some(character)
+    }
+
+    fn bump_while(&mut self, text: &mut String, predicate: impl Fn(char) -> bool) {
+        while let Some(&character) = self.chars.peek() {
+            if !predicate(character) {
+                break;
+            }
+            text.push(character);
+            self.bump();
+        }
+    }
+
+    /// Lexes the whole text, the last token is always [`Token::End`].
+    pub(super) fn tokenize(mut self) -> Result<Vec<(Token, Span)>, LatexParseError> {
+        let mut tokens = Vec::new();
+        loop {
+            self.bump_while(&mut String::new(), char::is_whitespace);
+            let start = self.position;
+            let character = match self.bump() {
+                Some(character) => character,
+                None => {
+                    tokens.push((Token::End, Span::new(start, start)));
+                    return Ok(tokens);
+                }
+            };
+            let token = match character {
+                '0'..='9' => {
+                    let mut text = character.to_string();
+                    self.bump_while(&mut text, |c| c.is_ascii_digit());
+                    let mut lookahead = self.chars.clone();
+                    if lookahead.next() == Some('.')
+                        && lookahead.peek().is_some_and(char::is_ascii_digit)
+                    {
+                        text.extend(self.bump());
+                        self.bump_while(&mut text, |c| c.is_ascii_digit());
+                        Token::Float(text)
+                    } else {
+                        Token::Integer(text)
+                    }
+                }
+                'a'..='z' | 'A'..='Z' => Token::Letter(character),
+                '\\' => {
+                    let command = self.command();
+                    if SPACING_COMMANDS.contains(&command.as_str()) {
+                        continue;
+                    }
+                    if WORD_COMMANDS.contains(&command.as_str()) {
+                        let content = self.text_argument(start, &command)?;
+                        Token::Word { command, content }
+                    } else {
+                        Token::Command(command)
+                    }
+                }
+                '+' => Token::Plus,
+                '-' => Token::Minus,
+                '*' => Token::Star,
+                '/' => Token::Slash,
+                '^' => Token::Caret,
+                '_' => Token::Underscore,
+                '{' => Token::LeftBrace,
+                '}' => Token::RightBrace,
+                '(' => Token::LeftParen,
+                ')' => Token::RightParen,
+                '[' => Token::LeftBracket,
+                ']' => Token::RightBracket,
+                _ => {
+                    return Err(LatexParseError::UnexpectedCharacter {
+                        character,
+                        span: Span::new(start, self.position),
+                    })
+                }
+            };
+            tokens.push((token, Span::new(start, self.position)));
+        }
+    }
+
+    /// Lexes the name of a command after the backslash, either letters or a single other character.
+    fn command(&mut self) -> String {
+        let mut name = String::new();
+        self.bump_while(&mut name, |c| c.is_ascii_alphabetic());
+        if name.is_empty() {
+            name.extend(self.bump());
+        }
+        name
+    }
+
+    /// Reads the braced argument of a text command as it is, resolving escaped characters.
+    fn text_argument(&mut self, start: Position, command: &str) -> Result<String, LatexParseError> {
+        self.bump_while(&mut String::new(), char::is_whitespace);
+        if self.chars.peek() != Some(&'{') {
+            let found = self
+                .chars
+                .peek()
+                .map_or("end of input".to_string(), |c| format!("'{}'", c));
+            return Err(LatexParseError::UnexpectedToken {
+                expected: format!("'{{' after \\{}", command),
+                found,
+                span: Span::new(start, self.position),
+            });
+        }
+        self.bump();
+        let mut content = String::new();
+        loop {
+            match self.bump() {
+                Some('}') => return Ok(content),
+                Some('\\') => {
+                    let escaped = self.command();
+                    match TEXT_ESCAPES.iter().find(|(name, _)| *name == escaped) {
+                        Some((_, character)) => {
+                            content.push(*character);
+                            if self.chars.peek() == Some(&'{') {
+                                self.bump();
+                                if self.chars.peek() == Some(&'}') {
+                                    self.bump();
+                                }
+                            }
+                        }
+                        None => content.push_str(&escaped),
+                    }
+                }
+                Some(character) => content.push(character),
+                None => {
+                    return Err(LatexParseError::UnexpectedToken {
+                        expected: "'}'".to_string(),
+                        found: "end of input".to_string(),
+                        span: Span::new(self.position, self.position),
+                    })
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! [LaTeX](https://www.latex-project.org/) math notation of expressions.
+//!
+//! Expressions are written by the [`LatexPrinter`] as the content of a math environment (without the surrounding `$` signs).
+//!
+//! | Expression | LaTeX |
+//! |------------|-------|
+//! | [`Addition`](crate::v0::expr::Expr::Addition), [`Subtraction`](crate::v0::expr::Expr::Subtraction) | `a + b`, `a - b` |
+//! | [`Multiplication`](crate::v0::expr::Expr::Multiplication) | `a \cdot b` |
+//! | [`Division`](crate::v0::expr::Expr::Division), [`Reciprocal`](crate::v0::expr::Expr::Reciprocal) | `\frac{a}{b}`, `\frac{1}{a}` |
+//! | [`IntDivision`](crate::v0::expr::Expr::IntDivision) | `\left\lfloor \frac{a}{b} \right\rfloor` |
+//! | [`Modulo`](crate::v0::expr::Expr::Modulo) | `a \bmod b` |
+//! | [`Power`](crate::v0::expr::Expr::Power), [`Square`](crate::v0::expr::Expr::Square), [`Cube`](crate::v0::expr::Expr::Cube) | `a^{b}`, `a^{2}`, `a^{3}` |
+//! | [`SquareRoot`](crate::v0::expr::Expr::SquareRoot), [`CubeRoot`](crate::v0::expr::Expr::CubeRoot) | `\sqrt{a}`, `\sqrt[3]{a}` |
+//! | [`Root`](crate::v0::expr::Expr::Root), [`IntRoot`](crate::v0::expr::Expr::IntRoot) | `\sqrt[n]{a}`, `\sqrt[{n + 1}]{a}` if the degree is not a variable or a number |
+//! | [`Negation`](crate::v0::expr::Expr::Negation) | `-a` |
+//!
+//! # Variables
+//! Variable names are written the following way:
+//! * Names of greek letters are written as the letter, `alpha` as `\alpha` and `Omega` as `\Omega`.
+//! * Names of a single ASCII letter or digit are written as they are, other names are written in italics using `\mathit`, `é` as `\mathit{é}`.
+//! * An underscore starts a subscript, `x_1` is written as `x_{1}`.
+//! * Names containing other characters than letters, digits and underscores are written as text using `\text`.
+//!
+//! Unnamed variables are written as `x` with their identifier in the subscript, e.g. `x_{0}`.
+//!
+//! # Parsing
+//! [`parse_latex`] reads a subset of the LaTeX math notation, which includes everything the [`LatexPrinter`] writes except for
+//! some float literals:
+//! * Integers and decimal numbers. A minus sign directly before a number is part of the literal.
+//! * `+`, `-`, `\cdot`, `\times`, `*`, `/` and `\bmod`. Factors written next to each other are multiplied, `2x` is `2 \cdot x`.
+//! * `\frac{a}{b}` as a [`Division`](crate::v0::expr::Expr::Division) and `\left\lfloor \frac{a}{b} \right\rfloor` as an [`IntDivision`](crate::v0::expr::Expr::IntDivision).
+//!   A floor of anything else than a fraction is not supported.
+//! * `a^{b}` as a [`Power`](crate::v0::expr::Expr::Power). Without braces, the exponent is a single digit or letter.
+//! * `\sqrt{a}` and `\sqrt[3]{a}` as a [`SquareRoot`](crate::v0::expr::Expr::SquareRoot) and a [`CubeRoot`](crate::v0::expr::Expr::CubeRoot),
+//!   any other degree as a [`Root`](crate::v0::expr::Expr::Root).
+//! * Parentheses `(a)`, `\left( a \right)` and braces `{a}` for grouping.
+//! * `\mathrm{true}` and `\mathrm{false}` as boolean literals.
+//! * Variables named as described [above](#variables), with the subscript being a single character or a group of letters and digits,
+//!   e.g. `x_1`, `\alpha_{max}` or `\mathit{speed}_{0}`.
+//!
+//! Spacing commands like `\,` or `\quad` are ignored. Text nested deeper than [`MAX_NESTING_DEPTH`](crate::v0::text::MAX_NESTING_DEPTH)
+//! is [rejected](crate::v0::text::error::LatexParseError::NestingTooDeep), so that untrusted input cannot overflow the stack.
+//!
+//! Float literals written in scientific notation, `1 \times 10^{300}`, are read back as a [`Multiplication`](crate::v0::expr::Expr::Multiplication)
+//! of the mantissa and a [`Power`](crate::v0::expr::Expr::Power) of ten. NaN and infinities, written as `\mathrm{NaN}` and `\infty`,
+//! are read back as the variable named `NaN` and as an unsupported command.
+//!
+//! ```rust
+//! # use fef::v0::text::{infix::{InfixPrinter, InfixStyle}, latex::parse_latex, VariableNames};
+//! # use fef::v0::metadata::VariableNameMetadataRecordObj;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut names = VariableNames::new();
+//! let (tree, records) = parse_latex(r"\frac{a}{b} + \sqrt[3]{x_{1}}", &mut names)?;
+//!
+//! let printer = InfixPrinter::new(InfixStyle::Ascii, Some(&names));
+//! assert_eq!(printer.print(&tree), "a / b + cbrt(x_1)");
+//! assert_eq!(records, vec![
+//!     VariableNameMetadataRecordObj::new("a".to_string(), 0.into()),
+//!     VariableNameMetadataRecordObj::new("b".to_string(), 1.into()),
+//!     VariableNameMetadataRecordObj::new("x_1".to_string(), 2.into()),
+//! ]);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A negated number literal is written in parentheses, so that it isn't read back as a negative literal:
+//! ```rust
+//! # use fef::v0::text::{latex::{parse_latex, LatexPrinter}, VariableNames};
+//! # use fef::v0::text::error::LatexParseError;
+//! # use fef::v0::expr::ExprTree;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let tree = -ExprTree::lit(5u8);
+//! let latex = LatexPrinter::new(None).print(&tree);
+//!
+//! assert_eq!(latex, r"-\left(5\right)");
+//! assert_eq!(parse_latex(&latex, &mut VariableNames::new())?.0, tree);
+//!
+//! let nested = "{".repeat(1000) + "x" + &"}".repeat(1000);
+//! let error = parse_latex(&nested, &mut VariableNames::new()).unwrap_err();
+//! assert!(matches!(error, LatexParseError::NestingTooDeep { .. }));
+//! # Ok(())
+//! # }
+//! ```
+
+mod lexer;
+mod parse;
+mod print;
+
+pub use parse::parse_latex;
+pub use print::LatexPrinter;
+
+/// Names of greek letters that have a LaTeX command and the letters they stand for.
+pub(crate) const GREEK_LETTERS: [(&str, char); 40] = [
+    ("alpha", 'α'),
+    ("beta", 'β'),
+    ("gamma", 'γ'),
+    ("delta", 'δ'),
+    ("epsilon", 'ϵ'),
+    ("zeta", 'ζ'),
+    ("eta", 'η'),
+    ("theta", 'θ'),
+    ("iota", 'ι'),
+    ("kappa", 'κ'),
+    ("lambda", 'λ'),
+    ("mu", 'μ'),
+    ("nu", 'ν'),
+    ("xi", 'ξ'),
+    ("pi", 'π'),
+    ("rho", 'ρ'),
+    ("sigma", 'σ'),
+    ("tau", 'τ'),
+    ("upsilon", 'υ'),
+    ("phi", 'ϕ'),
+    ("chi", 'χ'),
+    ("psi", 'ψ'),
+    ("omega", 'ω'),
+    ("varepsilon", 'ε'),
+    ("vartheta", 'ϑ'),
+    ("varpi", 'ϖ'),
+    ("varrho", 'ϱ'),
+    ("varsigma", 'ς'),
+    ("varphi", 'φ'),
+    ("Gamma", 'Γ'),
+    ("Delta", 'Δ'),
+    ("Theta", 'Θ'),
+    ("Lambda", 'Λ'),
+    ("Xi", 'Ξ'),
+    ("Pi", 'Π'),
+    ("Sigma", 'Σ'),
+    ("Upsilon", 'Υ'),
+    ("Phi", 'Φ'),
+    ("Psi", 'Ψ'),
+    ("Omega", 'Ω'),
+];
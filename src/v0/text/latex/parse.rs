@@ -0,0 +1,394 @@
+use std::collections::BTreeSet;
+
+use crate::v0::{
+    expr::{
+        float_literal, negate_literal, tree, Expr, ExprAddition, ExprCubeRoot, ExprDivision,
+        ExprFalseLiteral, ExprIntDivision, ExprModulo, ExprMultiplication, ExprNegation, ExprPower,
+        ExprRoot, ExprSquareRoot, ExprSubtraction, ExprTree, ExprTrueLiteral,
+        ExprUnsignedIntLiteral, ExprVariable,
+    },
+    metadata::VariableNameMetadataRecordObj,
+    raw::VariableLengthEnum,
+    text::{error::LatexParseError, Span, VariableNames, MAX_NESTING_DEPTH},
+};
+
+use super::{
+    lexer::{Lexer, Token},
+    GREEK_LETTERS,
+};
+
+/// Parses an expression written in the [supported subset](super#parsing) of the LaTeX math notation.
+///
+/// Variables are looked up by name in `variables`. Names that are not mapped yet are assigned new identifiers,
+/// see [`VariableNames::get_or_insert`]. Returns the expression and the variable name metadata records of all variables
+/// occurring in it, ordered by their identifiers.
+///
+/// # Errors
+/// Returns a [`LatexParseError`] with the span of the offending part of the text if the text is not a valid expression
+/// or if it is nested deeper than [`MAX_NESTING_DEPTH`].
+pub fn parse_latex(
+    text: &str,
+    variables: &mut VariableNames,
+) -> Result<(ExprTree, Vec<VariableNameMetadataRecordObj>), LatexParseError> {
+    let tokens = Lexer::new(text).tokenize()?;
+    let mut parser = Parser {
+        tokens,
+        index: 0,
+        depth: 0,
+        variables,
+        used: BTreeSet::new(),
+    };
+    let tree = parser.expression()?;
+    if *parser.peek() != Token::End {
+        return Err(parser.unexpected("an operator or end of input"));
+    }
+    let records = parser
+        .used
+        .iter()
+        .map(|identifier| {
+            let name = parser
+                .variables
+                .name(identifier)
+                .expect("used variables are named");
+            VariableNameMetadataRecordObj::new(name.to_string(), identifier.clone())
+        })
+        .collect();
+    Ok((tree, records))
+}
+
+struct Parser<'a> {
+    tokens: Vec<(Token, Span)>,
+    index: usize,
+    /// Number of operands being parsed, which contain the current one.
+    depth: usize,
+    variables: &'a mut VariableNames,
+    used: BTreeSet<VariableLengthEnum>,
+}
+
+fn unexpected(expected: &str, found: &Token, span: Span) -> LatexParseError {
+    LatexParseError::UnexpectedToken {
+        expected: expected.to_string(),
+        found: found.to_string(),
+        span,
+    }
+}
+
+fn is_greek(command: &str) -> bool {
+    GREEK_LETTERS.iter().any(|(name, _)| *name == command)
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.index].0
+    }
+
+    fn span(&self) -> Span {
+        self.tokens[self.index].1
+    }
+
+    fn next(&mut self) -> (Token, Span) {
+        let token = self.tokens[self.index].clone();
+        if token.0 != Token::End {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn unexpected(&self, expected: &str) -> LatexParseError {
+        unexpected(expected, self.peek(), self.span())
+    }
+
+    fn expect(&mut self, token: Token) -> Result<Span, LatexParseError> {
+        if *self.peek() == token {
+            Ok(self.next().1)
+        } else {
+            Err(self.unexpected(&token.to_string()))
+        }
+    }
+
+    fn is_command(&self, command: &str) -> bool {
+        matches!(self.peek(), Token::Command(name) if name == command)
+    }
+
+    fn expect_command(&mut self, command: &str) -> Result<Span, LatexParseError> {
+        if self.is_command(command) {
+            Ok(self.next().1)
+        } else {
+            Err(self.unexpected(&format!("\\{}", command)))
+        }
+    }
+
+    /// Returns `true` if the next token starts an expression, which makes it an implicit multiplication.
+    fn starts_factor(&self) -> bool {
+        match self.peek() {
+            Token::Integer(_)
+            | Token::Float(_)
+            | Token::Letter(_)
+            | Token::Word { .. }
+            | Token::LeftParen
+            | Token::LeftBrace => true,
+            Token::Command(command) => {
+                is_greek(command) || matches!(command.as_str(), "frac" | "sqrt" | "left" | "lfloor")
+            }
+            _ => false,
+        }
+    }
+
+    /// `expression := term (('+' | '-') term)*`
+    fn expression(&mut self) -> Result<ExprTree, LatexParseError> {
+        let mut lhs = self.term()?;
+        loop {
+            let operator = self.peek().clone();
+            if !matches!(operator, Token::Plus | Token::Minus) {
+                return Ok(lhs);
+            }
+            self.next();
+            let rhs = self.term()?;
+            lhs = match operator {
+                Token::Plus => tree(ExprAddition::from((lhs, rhs))),
+                _ => tree(ExprSubtraction::from((lhs, rhs))),
+            };
+        }
+    }
+
+    /// `term := unary ((\cdot | \times | '*' | '/' | \bmod) unary | power)*`
+    ///
+    /// A factor following another factor without an operator is an implicit multiplication.
+    fn term(&mut self) -> Result<ExprTree, LatexParseError> {
+        let mut lhs = self.unary()?;
+        loop {
+            lhs = match self.peek().clone() {
+                Token::Star => {
+                    self.next();
+                    tree(ExprMultiplication::from((lhs, self.unary()?)))
+                }
+                Token::Slash => {
+                    self.next();
+                    tree(ExprDivision::from((lhs, self.unary()?)))
+                }
+                Token::Command(command) if command == "cdot" || command == "times" => {
+                    self.next();
+                    tree(ExprMultiplication::from((lhs, self.unary()?)))
+                }
+                Token::Command(command) if command == "bmod" => {
+                    self.next();
+                    tree(ExprModulo::from((lhs, self.unary()?)))
+                }
+                _ if self.starts_factor() => tree(ExprMultiplication::from((lhs, self.power()?))),
+                _ => return Ok(lhs),
+            };
+        }
+    }
+
+    /// `unary := '-' unary | power`
+    ///
+    /// Every nested operand is parsed by this rule, so it limits the nesting depth.
+    fn unary(&mut self) -> Result<ExprTree, LatexParseError> {
+        if self.depth >= MAX_NESTING_DEPTH {
+            return Err(LatexParseError::NestingTooDeep {
+                limit: MAX_NESTING_DEPTH,
+                span: self.span(),
+            });
+        }
+        self.depth += 1;
+        let result = self.negation();
+        self.depth -= 1;
+        result
+    }
+
+    /// Parses the `unary` rule once the depth is checked.
+    ///
+    /// A minus sign directly followed by a number is parsed as a negative literal.
+    fn negation(&mut self) -> Result<ExprTree, LatexParseError> {
+        if *self.peek() != Token::Minus {
+            return self.power();
+        }
+        self.next();
+        let is_literal = matches!(self.peek(), Token::Integer(_) | Token::Float(_));
+        let inner = self.unary()?;
+        if is_literal {
+            if let Some(literal) = negate_literal(inner.inner()) {
+                return Ok(literal);
+            }
+        }
+        Ok(tree(ExprNegation::from(inner)))
+    }
+
+    /// `power := primary ('^' script)?`
+    fn power(&mut self) -> Result<ExprTree, LatexParseError> {
+        let base = self.primary()?;
+        if *self.peek() != Token::Caret {
+            return Ok(base);
+        }
+        self.next();
+        let exponent = self.script()?;
+        if *self.peek() == Token::Caret {
+            return Err(self.unexpected("a single superscript, use braces to raise a power"));
+        }
+        Ok(tree(ExprPower::from((base, exponent))))
+    }
+
+    /// `script := '{' expression '}' | digit | letter | greek letter`
+    fn script(&mut self) -> Result<ExprTree, LatexParseError> {
+        match self.peek().clone() {
+            Token::LeftBrace => self.group(),
+            Token::Integer(digits) if digits.len() == 1 => self.primary(),
+            Token::Letter(_) => self.primary(),
+            Token::Command(command) if is_greek(&command) => self.primary(),
+            _ => Err(self.unexpected("'{', a digit or a letter")),
+        }
+    }
+
+    /// `group := '{' expression '}'`
+    fn group(&mut self) -> Result<ExprTree, LatexParseError> {
+        self.expect(Token::LeftBrace)?;
+        let inner = self.expression()?;
+        self.expect(Token::RightBrace)?;
+        Ok(inner)
+    }
+
+    fn variable(&mut self, mut name: String) -> Result<ExprTree, LatexParseError> {
+        if *self.peek() == Token::Underscore {
+            self.next();
+            name.push('_');
+            self.subscript(&mut name)?;
+        }
+        let identifier = self.variables.get_or_insert(&name);
+        self.used.insert(identifier.clone());
+        Ok(tree(ExprVariable::from(identifier)))
+    }
+
+    /// Appends a subscript of a variable to its name. The subscript is either a single character or a group of names and numbers.
+    fn subscript(&mut self, name: &mut String) -> Result<(), LatexParseError> {
+        if *self.peek() != Token::LeftBrace {
+            return match self.next() {
+                (Token::Integer(digits), _) if digits.len() == 1 => {
+                    name.push_str(&digits);
+                    Ok(())
+                }
+                (Token::Letter(letter), _) => {
+                    name.push(letter);
+                    Ok(())
+                }
+                (Token::Command(command), _) if is_greek(&command) => {
+                    name.push_str(&command);
+                    Ok(())
+                }
+                (token, span) => Err(unexpected("a subscript", &token, span)),
+            };
+        }
+        self.next();
+        loop {
+            match self.next() {
+                (Token::RightBrace, _) => return Ok(()),
+                (Token::Integer(text), _) => name.push_str(&text),
+                (Token::Letter(letter), _) => name.push(letter),
+                (Token::Command(command), _) if is_greek(&command) => name.push_str(&command),
+                (Token::Word { content, .. }, _) => name.push_str(&content),
+                (token, span) => return Err(unexpected("a letter, a digit or '}'", &token, span)),
+            }
+        }
+    }
+
+    /// Parses the content of a floor, which must be a fraction, into an integer division.
+    fn floor(&mut self, start: Span) -> Result<ExprTree, LatexParseError> {
+        let content = self.expression()?;
+        let end = if self.is_command("right") {
+            self.next();
+            self.expect_command("rfloor")?
+        } else {
+            self.expect_command("rfloor")?
+        };
+        match content.into_inner() {
+            Expr::Division(division) => {
+                let (lhs, rhs): (ExprTree, ExprTree) = division.into();
+                Ok(tree(ExprIntDivision::from((lhs, rhs))))
+            }
+            _ => Err(LatexParseError::UnsupportedFloor {
+                span: start.join(end),
+            }),
+        }
+    }
+
+    /// `primary := number | variable | \frac group group | \sqrt ('[' expression ']')? group | '(' expression ')' | \left( expression \right) | floor | group`
+    fn primary(&mut self) -> Result<ExprTree, LatexParseError> {
+        let (token, span) = self.next();
+        match token {
+            Token::Integer(text) => match text.parse::<u64>() {
+                Ok(value) => Ok(tree(ExprUnsignedIntLiteral::from(value))),
+                Err(_) => Err(LatexParseError::LiteralOutOfRange {
+                    literal: text,
+                    span,
+                }),
+            },
+            Token::Float(text) => match text.parse::<f64>() {
+                Ok(value) if value.is_finite() => Ok(float_literal(value)),
+                _ => Err(LatexParseError::LiteralOutOfRange {
+                    literal: text,
+                    span,
+                }),
+            },
+            Token::Letter(letter) => self.variable(letter.to_string()),
+            Token::Word { command, content } if command == "mathrm" && content == "true" => {
+                Ok(tree(ExprTrueLiteral::from(())))
+            }
+            Token::Word { command, content } if command == "mathrm" && content == "false" => {
+                Ok(tree(ExprFalseLiteral::from(())))
+            }
+            Token::Word { content, .. } => self.variable(content),
+            Token::LeftParen => {
+                let inner = self.expression()?;
+                self.expect(Token::RightParen)?;
+                Ok(inner)
+            }
+            Token::LeftBrace => {
+                let inner = self.expression()?;
+                self.expect(Token::RightBrace)?;
+                Ok(inner)
+            }
+            Token::Command(command) => self.command(command, span),
+            token => Err(unexpected("an expression", &token, span)),
+        }
+    }
+
+    /// Parses the part of `primary` starting with a command.
+    fn command(&mut self, command: String, span: Span) -> Result<ExprTree, LatexParseError> {
+        match command.as_str() {
+            _ if is_greek(&command) => self.variable(command),
+            "frac" => {
+                let numerator = self.group()?;
+                let denominator = self.group()?;
+                Ok(tree(ExprDivision::from((numerator, denominator))))
+            }
+            "sqrt" => {
+                if *self.peek() != Token::LeftBracket {
+                    return Ok(tree(ExprSquareRoot::from(self.group()?)));
+                }
+                self.next();
+                let degree = self.expression()?;
+                self.expect(Token::RightBracket)?;
+                let radicand = self.group()?;
+                if let Expr::UnsignedIntLiteral(literal) = degree.inner() {
+                    match literal.value {
+                        2 => return Ok(tree(ExprSquareRoot::from(radicand))),
+                        3 => return Ok(tree(ExprCubeRoot::from(radicand))),
+                        _ => {}
+                    }
+                }
+                Ok(tree(ExprRoot::from((radicand, degree))))
+            }
+            "left" => match self.next() {
+                (Token::LeftParen, _) => {
+                    let inner = self.expression()?;
+                    self.expect_command("right")?;
+                    self.expect(Token::RightParen)?;
+                    Ok(inner)
+                }
+                (Token::Command(delimiter), _) if delimiter == "lfloor" => self.floor(span),
+                (token, span) => Err(unexpected("'(' or \\lfloor after \\left", &token, span)),
+            },
+            "lfloor" => self.floor(span),
+            _ => Err(LatexParseError::UnsupportedCommand { command, span }),
+        }
+    }
+}
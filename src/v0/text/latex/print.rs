@@ -0,0 +1,351 @@
+use std::fmt::Write;
+
+use crate::v0::{
+    expr::{
+        traits::{BinaryOperationExpr, Decomposer, DecompositionRefContainer, UnaryOperationExpr},
+        Expr, ExprTree,
+    },
+    raw::VariableLengthEnum,
+    text::{
+        error::TextWriteError,
+        print::{
+            self, is_simple_base, precedence, Notation, Part, ADDITIVE, MULTIPLICATIVE, PREFIX,
+        },
+        VariableNames,
+    },
+};
+
+use super::GREEK_LETTERS;
+
+/// Printer of expressions in the [LaTeX notation](super).
+///
+/// Parentheses (`\left(` and `\right)`) are written only where the precedence and associativity of operators require them.
+/// Bases of powers are parenthesized unless they are a variable, a non-negative number or already delimited.
+/// Negated number literals are parenthesized too, `-\left(5\right)`, as `-5` is read back as a negative literal.
+/// Variables are written by their names from the given [`VariableNames`], see [variables](super#variables).
+///
+/// Expressions are written without recursion, so deeply nested expressions, which can be read from untrusted input,
+/// cannot overflow the stack.
+///
+/// # Examples
+/// ```rust
+/// # use fef::v0::text::{infix::parse_infix, latex::LatexPrinter, VariableNames};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut names = VariableNames::new();
+/// let tree = parse_infix("sqrt(x_1^2 + alpha^2) / (2 * root(y, 3))", &mut names)?;
+///
+/// let printer = LatexPrinter::new(Some(&names));
+/// assert_eq!(
+///     printer.print(&tree),
+///     r"\frac{\sqrt{x_{1}^{2} + \alpha^{2}}}{2 \cdot \sqrt[3]{y}}"
+/// );
+///
+/// let tree = parse_infix("root(x, root(y, n))", &mut names)?;
+/// let printer = LatexPrinter::new(Some(&names));
+/// assert_eq!(printer.print(&tree), r"\sqrt[{\sqrt[n]{y}}]{x}");
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ```rust
+/// # use fef::v0::read::read_expression_into_tree;
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # use fef::v0::text::latex::LatexPrinter;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut bytes = vec![0x22; 100_000]; // Square roots
+/// bytes.extend([0x04, 0x00]); // Variable 0
+/// let tree = read_expression_into_tree(&mut &bytes[..], &DEFAULT_CONFIG)?;
+///
+/// let latex = LatexPrinter::default().print(&tree);
+/// assert_eq!(latex, r"\sqrt{".repeat(100_000) + "x_{0}" + &"}".repeat(100_000));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LatexPrinter<'a> {
+    names: Option<&'a VariableNames>,
+}
+
+/// Writes a float from its shortest representation, the exponent is written as a power of ten.
+fn write_float<W: ?Sized + Write>(float: String, writer: &mut W) -> std::fmt::Result {
+    match float.as_str() {
+        "NaN" => writer.write_str(r"\mathrm{NaN}"),
+        "inf" => writer.write_str(r"\infty"),
+        "-inf" => writer.write_str(r"-\infty"),
+        _ => match float.split_once('e') {
+            Some((mantissa, exponent)) => {
+                write!(writer, r"{} \times 10^{{{}}}", mantissa, exponent)
+            }
+            None => writer.write_str(&float),
+        },
+    }
+}
+
+/// Writes a variable name, see [variables](super#variables).
+pub(crate) fn write_name<W: ?Sized + Write>(name: &str, writer: &mut W) -> std::fmt::Result {
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        writer.write_str(r"\text{")?;
+        for character in name.chars() {
+            match character {
+                '\\' => writer.write_str(r"\textbackslash{}")?,
+                '~' => writer.write_str(r"\textasciitilde{}")?,
+                '^' => writer.write_str(r"\textasciicircum{}")?,
+                '#' | '$' | '%' | '&' | '_' | '{' | '}' => write!(writer, r"\{}", character)?,
+                _ => writer.write_char(character)?,
+            }
+        }
+        return writer.write_char('}');
+    }
+    let (base, subscript) = match name.split_once('_') {
+        Some((base, subscript)) if !base.is_empty() && !subscript.is_empty() => {
+            (base, Some(subscript))
+        }
+        _ => (name, None),
+    };
+    if GREEK_LETTERS.iter().any(|(name, _)| *name == base) {
+        write!(writer, r"\{}", base)?;
+    } else if base.len() == 1 && base.is_ascii() {
+        writer.write_str(base)?;
+    } else {
+        write!(writer, r"\mathit{{{}}}", base)?;
+    }
+    if let Some(subscript) = subscript {
+        writer.write_str("_{")?;
+        write_name(subscript, writer)?;
+        writer.write_char('}')?;
+    }
+    Ok(())
+}
+
+impl<'a> LatexPrinter<'a> {
+    /// Creates a printer resolving variable names from `names`.
+    pub fn new(names: Option<&'a VariableNames>) -> Self {
+        Self { names }
+    }
+
+    /// Writes an expression using a [`Decomposer`] to access child expressions.
+    ///
+    /// The expression is written without recursion, so arbitrarily deep expressions cannot overflow the stack.
+    pub fn write<S: Sized, DP: ?Sized + Decomposer<S>, W: ?Sized + Write>(
+        &self,
+        storage: &S,
+        decomposer: &mut DP,
+        writer: &mut W,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        let expr = decomposer.decompose_as_ref(storage)?.inner_as_ref();
+        print::write_parts(expr, writer, |expr, parts| {
+            self.push_expr(expr, decomposer, parts)
+        })
+    }
+
+    /// Writes an [`ExprTree`] into a [`String`].
+    pub fn print(&self, tree: &ExprTree) -> String {
+        let mut output = String::new();
+        self.write_tree(tree, &mut output)
+            .expect("writing into a string never fails");
+        output
+    }
+
+    /// Writes an [`ExprTree`].
+    ///
+    /// This function is a convenience function that simplifies calling [`write`](LatexPrinter::write) with a decomposer for [`ExprTree`].
+    pub fn write_tree<W: ?Sized + Write>(
+        &self,
+        tree: &ExprTree,
+        writer: &mut W,
+    ) -> std::fmt::Result {
+        print::write_tree(tree, writer, |tree, decomposer, writer| {
+            self.write(tree, decomposer, writer)
+        })
+    }
+
+    fn push_parenthesized<'e, S: Sized>(
+        &self,
+        expr: &'e Expr<S>,
+        parts: &mut Vec<Part<'e, S>>,
+        parenthesize: bool,
+    ) {
+        if parenthesize {
+            parts.extend([
+                Part::text(r"\left("),
+                Part::Expr(expr),
+                Part::text(r"\right)"),
+            ]);
+        } else {
+            parts.push(Part::Expr(expr));
+        }
+    }
+
+    /// Pushes an operand of an infix operator, in parentheses if its precedence is lower than `minimum`.
+    ///
+    /// Negative right operands are parenthesized, e.g. `a - \left(-b\right)`.
+    fn push_operand<'e, S: Sized, DP: ?Sized + Decomposer<S>>(
+        &self,
+        storage: &'e S,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+        minimum: u8,
+        is_right: bool,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        let expr = decomposer.decompose_as_ref(storage)?.inner_as_ref();
+        let precedence = precedence(expr, Notation::Layout);
+        let parenthesize = precedence < minimum || (is_right && precedence == PREFIX);
+        self.push_parenthesized(expr, parts, parenthesize);
+        Ok(())
+    }
+
+    /// Pushes an expression inside of braces, where it needs no parentheses.
+    fn push_group<'e, S: Sized, DP: ?Sized + Decomposer<S>>(
+        &self,
+        storage: &'e S,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        let expr = decomposer.decompose_as_ref(storage)?.inner_as_ref();
+        parts.extend([Part::text("{"), Part::Expr(expr), Part::text("}")]);
+        Ok(())
+    }
+
+    fn push_binary<'e, S: Sized, B: BinaryOperationExpr<S>, DP: ?Sized + Decomposer<S>>(
+        &self,
+        expr: &'e B,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+        operator: &str,
+        precedence: u8,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        self.push_operand(expr.lhs(), decomposer, parts, precedence, false)?;
+        parts.push(Part::text(format!(" {} ", operator)));
+        self.push_operand(expr.rhs(), decomposer, parts, precedence + 1, true)
+    }
+
+    fn push_power<'e, S: Sized, DP: ?Sized + Decomposer<S>>(
+        &self,
+        base: &'e S,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        let base = decomposer.decompose_as_ref(base)?.inner_as_ref();
+        self.push_parenthesized(base, parts, !is_simple_base(base));
+        parts.push(Part::text("^"));
+        Ok(())
+    }
+
+    /// Pushes a root, degrees other than atoms are written in braces, so that a `]` inside the degree doesn't end it.
+    fn push_root<'e, S: Sized, DP: ?Sized + Decomposer<S>>(
+        &self,
+        radicand: &'e S,
+        degree: &'e S,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        parts.push(Part::text(r"\sqrt["));
+        let degree = decomposer.decompose_as_ref(degree)?.inner_as_ref();
+        if is_simple_base(degree) {
+            parts.push(Part::Expr(degree));
+        } else {
+            parts.extend([Part::text("{"), Part::Expr(degree), Part::text("}")]);
+        }
+        parts.push(Part::text("]"));
+        self.push_group(radicand, decomposer, parts)
+    }
+
+    fn write_variable<W: ?Sized + Write>(
+        &self,
+        variable: &VariableLengthEnum,
+        writer: &mut W,
+    ) -> std::fmt::Result {
+        match self.names.and_then(|names| names.name(variable)) {
+            Some(name) => write_name(name, writer),
+            None => write!(writer, "x_{{{}}}", variable),
+        }
+    }
+
+    /// Pushes the text and the operands of an expression in the order they are written.
+    fn push_expr<'e, S: Sized, DP: ?Sized + Decomposer<S>>(
+        &self,
+        expr: &'e Expr<S>,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        let mut leaf = String::new();
+        match expr {
+            Expr::Variable(expr) => self.write_variable(expr.as_ref(), &mut leaf)?,
+            Expr::TrueLiteral(_) => leaf.push_str(r"\mathrm{true}"),
+            Expr::FalseLiteral(_) => leaf.push_str(r"\mathrm{false}"),
+            Expr::SignedIntLiteral(expr) => write!(leaf, "{}", expr.value)?,
+            Expr::UnsignedIntLiteral(expr) => write!(leaf, "{}", expr.value)?,
+            Expr::BinaryFloat32Literal(expr) => {
+                write_float(format!("{:?}", expr.value), &mut leaf)?
+            }
+            Expr::BinaryFloat64Literal(expr) => {
+                write_float(format!("{:?}", expr.value), &mut leaf)?
+            }
+            Expr::Addition(expr) => self.push_binary(expr, decomposer, parts, "+", ADDITIVE)?,
+            Expr::Subtraction(expr) => self.push_binary(expr, decomposer, parts, "-", ADDITIVE)?,
+            Expr::Multiplication(expr) => {
+                self.push_binary(expr, decomposer, parts, r"\cdot", MULTIPLICATIVE)?
+            }
+            Expr::Modulo(expr) => {
+                self.push_binary(expr, decomposer, parts, r"\bmod", MULTIPLICATIVE)?
+            }
+            Expr::Division(expr) => {
+                parts.push(Part::text(r"\frac"));
+                self.push_group(expr.lhs(), decomposer, parts)?;
+                self.push_group(expr.rhs(), decomposer, parts)?;
+            }
+            Expr::IntDivision(expr) => {
+                parts.push(Part::text(r"\left\lfloor \frac"));
+                self.push_group(expr.lhs(), decomposer, parts)?;
+                self.push_group(expr.rhs(), decomposer, parts)?;
+                parts.push(Part::text(r" \right\rfloor"));
+            }
+            Expr::Reciprocal(expr) => {
+                parts.push(Part::text(r"\frac{1}"));
+                self.push_group(expr.inner(), decomposer, parts)?;
+            }
+            Expr::Power(expr) => {
+                self.push_power(expr.lhs(), decomposer, parts)?;
+                self.push_group(expr.rhs(), decomposer, parts)?;
+            }
+            Expr::Square(expr) => {
+                self.push_power(expr.inner(), decomposer, parts)?;
+                parts.push(Part::text("{2}"));
+            }
+            Expr::Cube(expr) => {
+                self.push_power(expr.inner(), decomposer, parts)?;
+                parts.push(Part::text("{3}"));
+            }
+            Expr::SquareRoot(expr) => {
+                parts.push(Part::text(r"\sqrt"));
+                self.push_group(expr.inner(), decomposer, parts)?;
+            }
+            Expr::CubeRoot(expr) => {
+                parts.push(Part::text(r"\sqrt[3]"));
+                self.push_group(expr.inner(), decomposer, parts)?;
+            }
+            Expr::Root(expr) => self.push_root(expr.lhs(), expr.rhs(), decomposer, parts)?,
+            Expr::IntRoot(expr) => self.push_root(expr.lhs(), expr.rhs(), decomposer, parts)?,
+            Expr::Negation(expr) => {
+                parts.push(Part::text("-"));
+                // A minus sign directly in front of a number literal would be parsed as a negative literal.
+                let operand = decomposer.decompose_as_ref(expr.inner())?.inner_as_ref();
+                if matches!(
+                    operand,
+                    Expr::SignedIntLiteral(_)
+                        | Expr::UnsignedIntLiteral(_)
+                        | Expr::BinaryFloat32Literal(_)
+                        | Expr::BinaryFloat64Literal(_)
+                ) {
+                    self.push_parenthesized(operand, parts, true);
+                } else {
+                    self.push_operand(expr.inner(), decomposer, parts, PREFIX, true)?;
+                }
+            }
+        };
+        if !leaf.is_empty() {
+            parts.push(Part::text(leaf));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,261 @@
+use std::fmt::Write;
+
+use crate::v0::{
+    expr::{
+        traits::{BinaryOperationExpr, Decomposer, DecompositionRefContainer, UnaryOperationExpr},
+        Expr, ExprTree,
+    },
+    raw::VariableLengthEnum,
+    text::{
+        error::TextWriteError,
+        print::{self, Part},
+        VariableNames,
+    },
+};
+
+use super::{write_escaped, MATH_END_TAG, MATH_START_TAG};
+
+/// Printer of expressions in [Content MathML](super).
+///
+/// Every operation is written as an `<apply>` element with the matching operator element, see the [mapping of expressions](super).
+/// Variables are written by their names from the given [`VariableNames`], see [literals and variables](super#literals-and-variables).
+///
+/// # Examples
+/// ```rust
+/// # use fef::v0::text::{infix::parse_infix, mathml::ContentMathmlPrinter, VariableNames};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut names = VariableNames::new();
+/// let tree = parse_infix("root(x, 3) - -2.5", &mut names)?;
+///
+/// let printer = ContentMathmlPrinter::new(Some(&names));
+/// assert_eq!(
+///     printer.print(&tree),
+///     concat!(
+///         r#"<math xmlns="http://www.w3.org/1998/Math/MathML">"#,
+///         "<apply><minus/>",
+///         r#"<apply><root/><degree><cn type="integer">3</cn></degree><ci>x</ci></apply>"#,
+///         r#"<cn type="real">-2.5</cn>"#,
+///         "</apply>",
+///         "</math>",
+///     )
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ContentMathmlPrinter<'a> {
+    names: Option<&'a VariableNames>,
+}
+
+/// Writes a float from its shortest representation, using the e-notation for the scientific notation.
+fn write_float<W: ?Sized + Write>(float: String, writer: &mut W) -> std::fmt::Result {
+    match float.as_str() {
+        "NaN" => writer.write_str("<notanumber/>"),
+        "inf" => writer.write_str("<infinity/>"),
+        "-inf" => writer.write_str("<apply><minus/><infinity/></apply>"),
+        _ => match float.split_once('e') {
+            Some((mantissa, exponent)) => write!(
+                writer,
+                r#"<cn type="e-notation">{}<sep/>{}</cn>"#,
+                mantissa, exponent
+            ),
+            None => write!(writer, r#"<cn type="real">{}</cn>"#, float),
+        },
+    }
+}
+
+impl<'a> ContentMathmlPrinter<'a> {
+    /// Creates a printer resolving variable names from `names`.
+    pub fn new(names: Option<&'a VariableNames>) -> Self {
+        Self { names }
+    }
+
+    /// Writes an expression as a `<math>` element using a [`Decomposer`] to access child expressions.
+    ///
+    /// The expression is written without recursion, so arbitrarily deep expressions cannot overflow the stack.
+    pub fn write<S: Sized, DP: ?Sized + Decomposer<S>, W: ?Sized + Write>(
+        &self,
+        storage: &S,
+        decomposer: &mut DP,
+        writer: &mut W,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        writer.write_str(MATH_START_TAG)?;
+        let expr = decomposer.decompose_as_ref(storage)?.inner_as_ref();
+        print::write_parts(expr, writer, |expr, parts| {
+            self.push_expr(expr, decomposer, parts)
+        })?;
+        writer.write_str(MATH_END_TAG)?;
+        Ok(())
+    }
+
+    /// Writes an [`ExprTree`] into a [`String`].
+    pub fn print(&self, tree: &ExprTree) -> String {
+        let mut output = String::new();
+        self.write_tree(tree, &mut output)
+            .expect("writing into a string never fails");
+        output
+    }
+
+    /// Writes an [`ExprTree`].
+    ///
+    /// This function is a convenience function that simplifies calling [`write`](ContentMathmlPrinter::write) with a decomposer for [`ExprTree`].
+    pub fn write_tree<W: ?Sized + Write>(
+        &self,
+        tree: &ExprTree,
+        writer: &mut W,
+    ) -> std::fmt::Result {
+        print::write_tree(tree, writer, |tree, decomposer, writer| {
+            self.write(tree, decomposer, writer)
+        })
+    }
+
+    fn push_element<'e, S: Sized, DP: ?Sized + Decomposer<S>>(
+        &self,
+        storage: &'e S,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        let expr = decomposer.decompose_as_ref(storage)?.inner_as_ref();
+        parts.push(Part::Expr(expr));
+        Ok(())
+    }
+
+    /// Pushes an `<apply>` element of the `operator` element on the given operands.
+    fn push_apply<'e, S: Sized, DP: ?Sized + Decomposer<S>>(
+        &self,
+        operator: &str,
+        operands: &[&'e S],
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        parts.push(Part::text(format!("<apply><{}/>", operator)));
+        for operand in operands {
+            self.push_element(*operand, decomposer, parts)?;
+        }
+        parts.push(Part::text("</apply>"));
+        Ok(())
+    }
+
+    fn push_binary<'e, S: Sized, B: BinaryOperationExpr<S>, DP: ?Sized + Decomposer<S>>(
+        &self,
+        expr: &'e B,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+        operator: &str,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        self.push_apply(operator, &[expr.lhs(), expr.rhs()], decomposer, parts)
+    }
+
+    /// Pushes an `<apply>` element of the `operator` element on the operand and an integer.
+    fn push_with_integer<'e, S: Sized, DP: ?Sized + Decomposer<S>>(
+        &self,
+        operator: &str,
+        operand: &'e S,
+        integer: u8,
+        integer_first: bool,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        let integer = format!(r#"<cn type="integer">{}</cn>"#, integer);
+        if integer_first {
+            parts.push(Part::text(format!("<apply><{}/>{}", operator, integer)));
+            self.push_element(operand, decomposer, parts)?;
+            parts.push(Part::text("</apply>"));
+        } else {
+            parts.push(Part::text(format!("<apply><{}/>", operator)));
+            self.push_element(operand, decomposer, parts)?;
+            parts.push(Part::text(integer + "</apply>"));
+        }
+        Ok(())
+    }
+
+    fn push_root<'e, S: Sized, DP: ?Sized + Decomposer<S>>(
+        &self,
+        radicand: &'e S,
+        degree: &'e S,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        parts.push(Part::text("<apply><root/><degree>"));
+        self.push_element(degree, decomposer, parts)?;
+        parts.push(Part::text("</degree>"));
+        self.push_element(radicand, decomposer, parts)?;
+        parts.push(Part::text("</apply>"));
+        Ok(())
+    }
+
+    fn write_variable<W: ?Sized + Write>(
+        &self,
+        variable: &VariableLengthEnum,
+        writer: &mut W,
+    ) -> std::fmt::Result {
+        match self.names.and_then(|names| names.name(variable)) {
+            Some(name) => {
+                writer.write_str("<ci>")?;
+                write_escaped(name, writer)?;
+                writer.write_str("</ci>")
+            }
+            None => write!(writer, "<ci>x_{}</ci>", variable),
+        }
+    }
+
+    /// Pushes the markup and the operands of an expression in the order they are written.
+    fn push_expr<'e, S: Sized, DP: ?Sized + Decomposer<S>>(
+        &self,
+        expr: &'e Expr<S>,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        let mut leaf = String::new();
+        match expr {
+            Expr::Variable(expr) => self.write_variable(expr.as_ref(), &mut leaf)?,
+            Expr::TrueLiteral(_) => leaf.push_str("<true/>"),
+            Expr::FalseLiteral(_) => leaf.push_str("<false/>"),
+            Expr::SignedIntLiteral(expr) => {
+                write!(leaf, r#"<cn type="integer">{}</cn>"#, expr.value)?
+            }
+            Expr::UnsignedIntLiteral(expr) => {
+                write!(leaf, r#"<cn type="integer">{}</cn>"#, expr.value)?
+            }
+            Expr::BinaryFloat32Literal(expr) => {
+                write_float(format!("{:?}", expr.value), &mut leaf)?
+            }
+            Expr::BinaryFloat64Literal(expr) => {
+                write_float(format!("{:?}", expr.value), &mut leaf)?
+            }
+            Expr::Addition(expr) => self.push_binary(expr, decomposer, parts, "plus")?,
+            Expr::Subtraction(expr) => self.push_binary(expr, decomposer, parts, "minus")?,
+            Expr::Multiplication(expr) => self.push_binary(expr, decomposer, parts, "times")?,
+            Expr::Division(expr) => self.push_binary(expr, decomposer, parts, "divide")?,
+            Expr::IntDivision(expr) => self.push_binary(expr, decomposer, parts, "quotient")?,
+            Expr::Modulo(expr) => self.push_binary(expr, decomposer, parts, "rem")?,
+            Expr::Power(expr) => self.push_binary(expr, decomposer, parts, "power")?,
+            Expr::Reciprocal(expr) => {
+                self.push_with_integer("divide", expr.inner(), 1, true, decomposer, parts)?
+            }
+            Expr::Square(expr) => {
+                self.push_with_integer("power", expr.inner(), 2, false, decomposer, parts)?
+            }
+            Expr::Cube(expr) => {
+                self.push_with_integer("power", expr.inner(), 3, false, decomposer, parts)?
+            }
+            Expr::SquareRoot(expr) => {
+                self.push_apply("root", &[expr.inner()], decomposer, parts)?
+            }
+            Expr::CubeRoot(expr) => {
+                parts.push(Part::text(
+                    r#"<apply><root/><degree><cn type="integer">3</cn></degree>"#,
+                ));
+                self.push_element(expr.inner(), decomposer, parts)?;
+                parts.push(Part::text("</apply>"));
+            }
+            Expr::Root(expr) => self.push_root(expr.lhs(), expr.rhs(), decomposer, parts)?,
+            Expr::IntRoot(expr) => self.push_root(expr.lhs(), expr.rhs(), decomposer, parts)?,
+            Expr::Negation(expr) => self.push_apply("minus", &[expr.inner()], decomposer, parts)?,
+        };
+        if !leaf.is_empty() {
+            parts.push(Part::text(leaf));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+//! [MathML](https://www.w3.org/TR/MathML3/) markup of expressions.
+//!
+//! MathML has two flavors, which are written by separate printers:
+//! * [Presentation MathML](https://www.w3.org/TR/MathML3/chapter3.html), written by the [`PresentationMathmlPrinter`], describes how an expression looks.
+//!   It is meant for displaying expressions, e.g. in web browsers.
+//! * [Content MathML](https://www.w3.org/TR/MathML3/chapter4.html), written by the [`ContentMathmlPrinter`], describes what an expression means.
+//!   It is meant for exchanging expressions with other software.
+//!
+//! Both printers write a single `<math>` element in the MathML namespace without any whitespace between the elements.
+//! They write expressions without recursion, so deeply nested expressions, which can be read from untrusted input, cannot overflow the stack:
+//! ```rust
+//! # use fef::v0::read::read_expression_into_tree;
+//! # use fef::v0::config::DEFAULT_CONFIG;
+//! # use fef::v0::text::mathml::{ContentMathmlPrinter, PresentationMathmlPrinter};
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut bytes = vec![0x17; 100_000]; // Negations
+//! bytes.extend([0x04, 0x00]); // Variable 0
+//! let tree = read_expression_into_tree(&mut &bytes[..], &DEFAULT_CONFIG)?;
+//!
+//! let presentation = PresentationMathmlPrinter::default().print(&tree);
+//! assert_eq!(presentation.matches("<mo>−</mo>").count(), 100_000);
+//!
+//! let content = ContentMathmlPrinter::default().print(&tree);
+//! assert!(content.ends_with(&("<ci>x_0</ci>".to_string() + &"</apply>".repeat(100_000) + "</math>")));
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! | Expression | Presentation MathML | Content MathML |
+//! |------------|---------------------|----------------|
+//! | [`Addition`](crate::v0::expr::Expr::Addition) | `<mo>+</mo>` | `<plus/>` |
+//! | [`Subtraction`](crate::v0::expr::Expr::Subtraction), [`Negation`](crate::v0::expr::Expr::Negation) | `<mo>−</mo>` | `<minus/>` |
+//! | [`Multiplication`](crate::v0::expr::Expr::Multiplication) | `<mo>⋅</mo>` | `<times/>` |
+//! | [`Division`](crate::v0::expr::Expr::Division) | `<mfrac>` | `<divide/>` |
+//! | [`IntDivision`](crate::v0::expr::Expr::IntDivision) | `<mo>⌊</mo><mfrac>…</mfrac><mo>⌋</mo>` | `<quotient/>` |
+//! | [`Modulo`](crate::v0::expr::Expr::Modulo) | `<mo>mod</mo>` | `<rem/>` |
+//! | [`Power`](crate::v0::expr::Expr::Power), [`Square`](crate::v0::expr::Expr::Square), [`Cube`](crate::v0::expr::Expr::Cube) | `<msup>` | `<power/>` |
+//! | [`SquareRoot`](crate::v0::expr::Expr::SquareRoot) | `<msqrt>` | `<root/>` |
+//! | [`CubeRoot`](crate::v0::expr::Expr::CubeRoot), [`Root`](crate::v0::expr::Expr::Root), [`IntRoot`](crate::v0::expr::Expr::IntRoot) | `<mroot>` | `<root/>` with `<degree>` |
+//! | [`Reciprocal`](crate::v0::expr::Expr::Reciprocal) | `<mfrac>` with `<mn>1</mn>` | `<divide/>` with `<cn>1</cn>` |
+//!
+//! Content MathML leaves the exact semantics of `<quotient/>` and `<rem/>` to the application, the FEF semantics apply:
+//! the quotient is rounded towards negative infinity and the remainder has the sign of the divisor.
+//!
+//! # Literals and variables
+//! * Numbers are written as `<mn>` and `<cn>` elements. Floats in the scientific notation are written as
+//!   `m × 10`<sup>`e`</sup> in Presentation MathML and as `<cn type="e-notation">` in Content MathML.
+//!   Infinities and NaN are written as `∞` and `NaN` or as `<infinity/>` and `<notanumber/>`.
+//! * Boolean literals are written as `<mtext>true</mtext>` or `<true/>` and their false counterparts.
+//! * Variables are written as `<mi>` and `<ci>` elements with their names from the given [`VariableNames`](crate::v0::text::VariableNames).
+//!   In Presentation MathML, greek letter names are written as the letter and an underscore starts a subscript
+//!   the same way as in [LaTeX](super::latex#variables). In Content MathML, the name is written as it is.
+//!   Unnamed variables are named `x` with their identifier in the subscript (`x_0`).
+//!
+//! # Parsing
+//! [`parse_content_mathml`] reads Content MathML with the operators from the table above, which includes everything the [`ContentMathmlPrinter`] writes.
+//! The XML is read by a small reader built into this crate, which supports elements, attributes, comments, CDATA sections
+//! and the predefined and numeric character references, but not named entities like `&InvisibleTimes;`.
+//! * `<plus/>` and `<times/>` take two or more operands, which are added or multiplied from the left.
+//! * `<minus/>` with one operand is a [`Negation`](crate::v0::expr::Expr::Negation), with two operands it is a [`Subtraction`](crate::v0::expr::Expr::Subtraction).
+//! * `<root/>` without a `<degree>` or with the degree `2` is a [`SquareRoot`](crate::v0::expr::Expr::SquareRoot), the degree `3` makes it a
+//!   [`CubeRoot`](crate::v0::expr::Expr::CubeRoot) and any other degree a [`Root`](crate::v0::expr::Expr::Root).
+//! * `<cn>` elements of the types `integer` (with an optional `base`), `real`, `double` and `e-notation` are number literals.
+//!   A `<cn>` without a type is an integer literal if it contains only digits, otherwise it is a float literal.
+//!   Floats use the [32-bit float](crate::v0::expr::ExprBinaryFloat32Literal) if it represents the number exactly.
+//! * `<ci>` elements are variables named by their content.
+//! * Of `<semantics>` elements, only the first child is read.
+//!
+//! Other elements, e.g. functions like `<sin/>`, constants like `<pi/>`, qualifiers like `<bvar>` or any Presentation MathML,
+//! have no equivalent expression and are reported as [unsupported](crate::v0::text::error::MathmlParseError::UnsupportedElement).
+//! Elements nested deeper than [`MAX_NESTING_DEPTH`](crate::v0::text::MAX_NESTING_DEPTH) are [rejected](crate::v0::text::error::MathmlParseError::NestingTooDeep),
+//! so that untrusted documents cannot overflow the stack.
+//!
+//! ```rust
+//! # use fef::v0::text::{infix::{InfixPrinter, InfixStyle}, mathml::parse_content_mathml, VariableNames};
+//! # use fef::v0::text::error::MathmlParseError;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mut names = VariableNames::new();
+//! let (tree, records) = parse_content_mathml(
+//!     r#"<math xmlns="http://www.w3.org/1998/Math/MathML">
+//!         <apply><plus/>
+//!             <apply><power/><ci>x</ci><cn type="integer">2</cn></apply>
+//!             <apply><minus/><ci>y</ci></apply>
+//!         </apply>
+//!     </math>"#,
+//!     &mut names,
+//! )?;
+//!
+//! let printer = InfixPrinter::new(InfixStyle::Ascii, Some(&names));
+//! assert_eq!(printer.print(&tree), "x^2 + -y");
+//! assert_eq!(records.len(), 2);
+//!
+//! let error = parse_content_mathml("<apply><sin/><ci>x</ci></apply>", &mut names).unwrap_err();
+//! assert!(matches!(error, MathmlParseError::UnsupportedElement { ref element, .. } if element == "sin"));
+//!
+//! let nested = "<apply><minus/>".repeat(2000) + "<ci>x</ci>" + &"</apply>".repeat(2000);
+//! let error = parse_content_mathml(&nested, &mut names).unwrap_err();
+//! assert!(matches!(error, MathmlParseError::NestingTooDeep { .. }));
+//! # Ok(())
+//! # }
+//! ```
+
+mod content;
+mod parse;
+mod presentation;
+mod xml;
+
+pub use content::ContentMathmlPrinter;
+pub use parse::parse_content_mathml;
+pub use presentation::PresentationMathmlPrinter;
+
+/// Start tag of the root element of MathML markup.
+const MATH_START_TAG: &str = r#"<math xmlns="http://www.w3.org/1998/Math/MathML">"#;
+
+/// End tag of the root element of MathML markup.
+const MATH_END_TAG: &str = "</math>";
+
+/// Writes text content of an element, escaping characters with a special meaning in XML.
+fn write_escaped<W: ?Sized + std::fmt::Write>(text: &str, writer: &mut W) -> std::fmt::Result {
+    for character in text.chars() {
+        match character {
+            '&' => writer.write_str("&amp;")?,
+            '<' => writer.write_str("&lt;")?,
+            '>' => writer.write_str("&gt;")?,
+            '"' => writer.write_str("&quot;")?,
+            '\'' => writer.write_str("&apos;")?,
+            _ => writer.write_char(character)?,
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,387 @@
+use std::{collections::BTreeSet, num::IntErrorKind};
+
+use crate::v0::{
+    expr::{
+        float_literal, tree, Expr, ExprAddition, ExprBinaryFloat32Literal, ExprCubeRoot,
+        ExprDivision, ExprFalseLiteral, ExprIntDivision, ExprModulo, ExprMultiplication,
+        ExprNegation, ExprPower, ExprRoot, ExprSignedIntLiteral, ExprSquareRoot, ExprSubtraction,
+        ExprTree, ExprTrueLiteral, ExprUnsignedIntLiteral, ExprVariable,
+    },
+    metadata::VariableNameMetadataRecordObj,
+    raw::VariableLengthEnum,
+    text::{error::MathmlParseError, Span, VariableNames},
+};
+
+use super::xml::{read_document, Element, Node};
+
+/// Parses an expression written in [Content MathML](super#parsing).
+///
+/// The text must be an XML document whose root is either a `<math>` element containing a single expression or the expression itself.
+/// Variables are looked up by name in `variables`. Names that are not mapped yet are assigned new identifiers,
+/// see [`VariableNames::get_or_insert`]. Returns the expression and the variable name metadata records of all variables
+/// occurring in it, ordered by their identifiers.
+///
+/// # Errors
+/// Returns a [`MathmlParseError`] with the span of the offending part of the text if the text is not well-formed XML
+/// or contains MathML without an equivalent expression.
+pub fn parse_content_mathml(
+    text: &str,
+    variables: &mut VariableNames,
+) -> Result<(ExprTree, Vec<VariableNameMetadataRecordObj>), MathmlParseError> {
+    let root = read_document(text)?;
+    let mut parser = Parser {
+        variables,
+        used: BTreeSet::new(),
+    };
+    let tree = if root.name == "math" {
+        parser.expression(single_child(&root)?)?
+    } else {
+        parser.expression(&root)?
+    };
+    let records = parser
+        .used
+        .iter()
+        .map(|identifier| {
+            let name = parser
+                .variables
+                .name(identifier)
+                .expect("used variables are named");
+            VariableNameMetadataRecordObj::new(name.to_string(), identifier.clone())
+        })
+        .collect();
+    Ok((tree, records))
+}
+
+struct Parser<'a> {
+    variables: &'a mut VariableNames,
+    used: BTreeSet<VariableLengthEnum>,
+}
+
+/// Span of the end of an element, used by errors about missing content.
+fn end_of(element: &Element) -> Span {
+    let end = element
+        .children
+        .last()
+        .map_or(element.span.end, |child| match child {
+            Node::Element(child) => end_of(child).end,
+            Node::Text { span, .. } => span.end,
+        });
+    Span::new(end, end)
+}
+
+/// Returns the child elements, text other than whitespace is an error.
+fn child_elements(element: &Element) -> Result<Vec<&Element>, MathmlParseError> {
+    element
+        .children
+        .iter()
+        .filter_map(|child| match child {
+            Node::Element(child) => Some(Ok(child)),
+            Node::Text { text, .. } if text.trim().is_empty() => None,
+            Node::Text { text, span } => Some(Err(MathmlParseError::UnexpectedContent {
+                expected: "an element".to_string(),
+                found: format!("text \"{}\"", text.trim()),
+                span: *span,
+            })),
+        })
+        .collect()
+}
+
+/// Returns the only child element.
+fn single_child(element: &Element) -> Result<&Element, MathmlParseError> {
+    let children = child_elements(element)?;
+    match children.as_slice() {
+        [child] => Ok(child),
+        [] => Err(MathmlParseError::UnexpectedContent {
+            expected: "an expression".to_string(),
+            found: format!("</{}>", element.name),
+            span: end_of(element),
+        }),
+        [_, extra, ..] => Err(MathmlParseError::UnexpectedContent {
+            expected: format!("</{}>", element.name),
+            found: format!("<{}>", extra.name),
+            span: extra.span,
+        }),
+    }
+}
+
+/// Returns the text parts of a token element separated by `<sep/>` elements, with the span of each part.
+fn text_parts(element: &Element) -> Result<Vec<(String, Span)>, MathmlParseError> {
+    let mut parts = vec![(String::new(), end_of(element))];
+    let mut is_empty = true;
+    for child in &element.children {
+        match child {
+            Node::Text { text, span } => {
+                let part = parts.last_mut().expect("there is always a part");
+                if is_empty {
+                    part.1 = *span;
+                    is_empty = false;
+                } else {
+                    part.1 = part.1.join(*span);
+                }
+                part.0.push_str(text);
+            }
+            Node::Element(child) if child.name == "sep" => {
+                parts.push((String::new(), end_of(child)));
+                is_empty = true;
+            }
+            Node::Element(child) => {
+                return Err(MathmlParseError::UnexpectedContent {
+                    expected: "text".to_string(),
+                    found: format!("<{}>", child.name),
+                    span: child.span,
+                })
+            }
+        }
+    }
+    Ok(parts
+        .into_iter()
+        .map(|(text, span)| (text.trim().to_string(), span))
+        .collect())
+}
+
+fn integer_literal(text: &str, radix: u32, span: Span) -> Result<ExprTree, MathmlParseError> {
+    let error = |kind: &IntErrorKind| match kind {
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+            MathmlParseError::LiteralOutOfRange {
+                literal: text.to_string(),
+                span,
+            }
+        }
+        _ => MathmlParseError::InvalidNumber {
+            text: text.to_string(),
+            span,
+        },
+    };
+    if text.starts_with('-') {
+        i64::from_str_radix(text, radix)
+            .map(|value| tree(ExprSignedIntLiteral::from(value)))
+            .map_err(|e| error(e.kind()))
+    } else {
+        u64::from_str_radix(text, radix)
+            .map(|value| tree(ExprUnsignedIntLiteral::from(value)))
+            .map_err(|e| error(e.kind()))
+    }
+}
+
+fn real_literal(text: &str, span: Span) -> Result<ExprTree, MathmlParseError> {
+    let value: f64 = text.parse().map_err(|_| MathmlParseError::InvalidNumber {
+        text: text.to_string(),
+        span,
+    })?;
+    if value.is_infinite() && !text.to_ascii_lowercase().contains("inf") {
+        return Err(MathmlParseError::LiteralOutOfRange {
+            literal: text.to_string(),
+            span,
+        });
+    }
+    Ok(float_literal(value))
+}
+
+/// Converts a `<cn>` element to a number literal.
+fn number(element: &Element) -> Result<ExprTree, MathmlParseError> {
+    let number_type = element.attribute("type");
+    if let Some(number_type) = number_type {
+        if !matches!(number_type, "integer" | "real" | "double" | "e-notation") {
+            return Err(MathmlParseError::UnsupportedNumberType {
+                number_type: number_type.to_string(),
+                span: element.span,
+            });
+        }
+    }
+    let parts = text_parts(element)?;
+    if number_type == Some("e-notation") {
+        return match parts.as_slice() {
+            [(mantissa, mantissa_span), (exponent, exponent_span)] => real_literal(
+                &format!("{}e{}", mantissa, exponent),
+                mantissa_span.join(*exponent_span),
+            ),
+            _ => Err(MathmlParseError::UnexpectedContent {
+                expected: "a mantissa and an exponent separated by <sep/>".to_string(),
+                found: format!("{} parts", parts.len()),
+                span: element.span,
+            }),
+        };
+    }
+    let (text, span) = match parts.as_slice() {
+        [part] => part,
+        _ => {
+            return Err(MathmlParseError::UnexpectedContent {
+                expected: "a single number".to_string(),
+                found: "<sep/>".to_string(),
+                span: element.span,
+            })
+        }
+    };
+    match number_type {
+        Some("integer") => {
+            let radix = match element.attribute("base") {
+                Some(base) => match base.trim().parse() {
+                    Ok(radix @ 2..=36) => radix,
+                    _ => {
+                        return Err(MathmlParseError::InvalidNumber {
+                            text: format!("base {}", base),
+                            span: element.span,
+                        })
+                    }
+                },
+                None => 10,
+            };
+            integer_literal(text, radix, *span)
+        }
+        Some("real") | Some("double") => real_literal(text, *span),
+        None if text
+            .trim_start_matches('-')
+            .chars()
+            .all(|c| c.is_ascii_digit()) =>
+        {
+            integer_literal(text, 10, *span)
+        }
+        _ => real_literal(text, *span),
+    }
+}
+
+/// Number of operands an operator takes.
+enum Arity {
+    Exactly(usize),
+    OneOrTwo,
+    AtLeastTwo,
+}
+
+impl Arity {
+    fn accepts(&self, count: usize) -> bool {
+        match self {
+            Arity::Exactly(expected) => count == *expected,
+            Arity::OneOrTwo => count == 1 || count == 2,
+            Arity::AtLeastTwo => count >= 2,
+        }
+    }
+}
+
+impl std::fmt::Display for Arity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Arity::Exactly(expected) => write!(f, "{}", expected),
+            Arity::OneOrTwo => write!(f, "1 or 2"),
+            Arity::AtLeastTwo => write!(f, "at least 2"),
+        }
+    }
+}
+
+impl Parser<'_> {
+    fn expression(&mut self, element: &Element) -> Result<ExprTree, MathmlParseError> {
+        match element.name.as_str() {
+            "ci" => {
+                let parts = text_parts(element)?;
+                match parts.as_slice() {
+                    [(name, _)] if !name.is_empty() => {
+                        let identifier = self.variables.get_or_insert(name);
+                        self.used.insert(identifier.clone());
+                        Ok(tree(ExprVariable::from(identifier)))
+                    }
+                    _ => Err(MathmlParseError::UnexpectedContent {
+                        expected: "a variable name".to_string(),
+                        found: "</ci>".to_string(),
+                        span: element.span,
+                    }),
+                }
+            }
+            "cn" => number(element),
+            "true" => Ok(tree(ExprTrueLiteral::from(()))),
+            "false" => Ok(tree(ExprFalseLiteral::from(()))),
+            "infinity" => Ok(tree(ExprBinaryFloat32Literal::from(f32::INFINITY))),
+            "notanumber" => Ok(tree(ExprBinaryFloat32Literal::from(f32::NAN))),
+            "apply" => self.apply(element),
+            "semantics" => match child_elements(element)?.first() {
+                Some(child) => self.expression(child),
+                None => Err(MathmlParseError::UnexpectedContent {
+                    expected: "an expression".to_string(),
+                    found: "</semantics>".to_string(),
+                    span: end_of(element),
+                }),
+            },
+            _ => Err(MathmlParseError::UnsupportedElement {
+                element: element.name.clone(),
+                span: element.span,
+            }),
+        }
+    }
+
+    fn apply(&mut self, element: &Element) -> Result<ExprTree, MathmlParseError> {
+        let children = child_elements(element)?;
+        let (operator, mut operands) = match children.split_first() {
+            Some((operator, operands)) => (*operator, operands),
+            None => {
+                return Err(MathmlParseError::UnexpectedContent {
+                    expected: "an operator".to_string(),
+                    found: "</apply>".to_string(),
+                    span: end_of(element),
+                })
+            }
+        };
+        let arity = match operator.name.as_str() {
+            "plus" | "times" => Arity::AtLeastTwo,
+            "minus" => Arity::OneOrTwo,
+            "divide" | "quotient" | "rem" | "power" => Arity::Exactly(2),
+            "root" => Arity::Exactly(1),
+            _ => {
+                return Err(MathmlParseError::UnsupportedElement {
+                    element: operator.name.clone(),
+                    span: operator.span,
+                })
+            }
+        };
+        let mut degree = None;
+        if operator.name == "root" {
+            if let Some((first, rest)) = operands.split_first() {
+                if first.name == "degree" {
+                    degree = Some(self.expression(single_child(first)?)?);
+                    operands = rest;
+                }
+            }
+        }
+        if !arity.accepts(operands.len()) {
+            return Err(MathmlParseError::ArgumentCount {
+                operator: operator.name.clone(),
+                expected: arity.to_string(),
+                found: operands.len(),
+                span: operator.span,
+            });
+        }
+        let mut operands = operands
+            .iter()
+            .map(|operand| self.expression(operand))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter();
+        let lhs = operands.next().expect("the number of operands is checked");
+        let expr = match (operator.name.as_str(), operands.next()) {
+            ("plus", Some(rhs)) => operands
+                .fold(tree(ExprAddition::from((lhs, rhs))), |lhs, rhs| {
+                    tree(ExprAddition::from((lhs, rhs)))
+                }),
+            ("times", Some(rhs)) => operands
+                .fold(tree(ExprMultiplication::from((lhs, rhs))), |lhs, rhs| {
+                    tree(ExprMultiplication::from((lhs, rhs)))
+                }),
+            ("minus", Some(rhs)) => tree(ExprSubtraction::from((lhs, rhs))),
+            ("minus", None) => tree(ExprNegation::from(lhs)),
+            ("divide", Some(rhs)) => tree(ExprDivision::from((lhs, rhs))),
+            ("quotient", Some(rhs)) => tree(ExprIntDivision::from((lhs, rhs))),
+            ("rem", Some(rhs)) => tree(ExprModulo::from((lhs, rhs))),
+            ("power", Some(rhs)) => tree(ExprPower::from((lhs, rhs))),
+            ("root", None) => match degree {
+                None => tree(ExprSquareRoot::from(lhs)),
+                Some(degree) => match degree.inner() {
+                    Expr::UnsignedIntLiteral(literal) if literal.value == 2 => {
+                        tree(ExprSquareRoot::from(lhs))
+                    }
+                    Expr::UnsignedIntLiteral(literal) if literal.value == 3 => {
+                        tree(ExprCubeRoot::from(lhs))
+                    }
+                    _ => tree(ExprRoot::from((lhs, degree))),
+                },
+            },
+            _ => unreachable!("the number of operands is checked"),
+        };
+        Ok(expr)
+    }
+}
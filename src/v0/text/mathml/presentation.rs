@@ -0,0 +1,349 @@
+use std::fmt::Write;
+
+use crate::v0::{
+    expr::{
+        traits::{BinaryOperationExpr, Decomposer, DecompositionRefContainer, UnaryOperationExpr},
+        Expr, ExprTree,
+    },
+    raw::VariableLengthEnum,
+    text::{
+        error::TextWriteError,
+        latex::GREEK_LETTERS,
+        print::{
+            self, is_simple_base, precedence, Notation, Part, ADDITIVE, MULTIPLICATIVE, PREFIX,
+        },
+        VariableNames,
+    },
+};
+
+use super::{write_escaped, MATH_END_TAG, MATH_START_TAG};
+
+/// Printer of expressions in [Presentation MathML](super).
+///
+/// Every operation is written as an `<mrow>` element, parentheses are added only where the precedence and associativity of operators require them.
+/// Variables are written by their names from the given [`VariableNames`], see [literals and variables](super#literals-and-variables).
+///
+/// # Examples
+/// ```rust
+/// # use fef::v0::text::{infix::parse_infix, mathml::PresentationMathmlPrinter, VariableNames};
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut names = VariableNames::new();
+/// let tree = parse_infix("sqrt(x_1) / (2 * alpha)", &mut names)?;
+///
+/// let printer = PresentationMathmlPrinter::new(Some(&names));
+/// assert_eq!(
+///     printer.print(&tree),
+///     concat!(
+///         r#"<math xmlns="http://www.w3.org/1998/Math/MathML">"#,
+///         "<mfrac>",
+///         "<msqrt><msub><mi>x</mi><mn>1</mn></msub></msqrt>",
+///         "<mrow><mn>2</mn><mo>⋅</mo><mi>α</mi></mrow>",
+///         "</mfrac>",
+///         "</math>",
+///     )
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PresentationMathmlPrinter<'a> {
+    names: Option<&'a VariableNames>,
+}
+
+/// Writes a number, a minus sign is written as an operator.
+fn write_number<W: ?Sized + Write>(number: &str, writer: &mut W) -> std::fmt::Result {
+    match number.strip_prefix('-') {
+        Some(magnitude) => write!(writer, "<mrow><mo>−</mo><mn>{}</mn></mrow>", magnitude),
+        None => write!(writer, "<mn>{}</mn>", number),
+    }
+}
+
+/// Writes a float from its shortest representation, the exponent is written as a power of ten.
+fn write_float<W: ?Sized + Write>(float: String, writer: &mut W) -> std::fmt::Result {
+    match float.as_str() {
+        "NaN" => writer.write_str("<mi>NaN</mi>"),
+        "inf" => writer.write_str("<mi>∞</mi>"),
+        "-inf" => writer.write_str("<mrow><mo>−</mo><mi>∞</mi></mrow>"),
+        _ => match float.split_once('e') {
+            Some((mantissa, exponent)) => {
+                writer.write_str("<mrow>")?;
+                write_number(mantissa, writer)?;
+                writer.write_str("<mo>×</mo><msup><mn>10</mn>")?;
+                write_number(exponent, writer)?;
+                writer.write_str("</msup></mrow>")
+            }
+            None => write_number(&float, writer),
+        },
+    }
+}
+
+/// Writes a variable name as a single element, see [literals and variables](super#literals-and-variables).
+fn write_name<W: ?Sized + Write>(name: &str, writer: &mut W) -> std::fmt::Result {
+    if name.is_empty() || !name.chars().all(|c| c.is_alphanumeric() || c == '_') {
+        writer.write_str("<mi>")?;
+        write_escaped(name, writer)?;
+        return writer.write_str("</mi>");
+    }
+    let (base, subscript) = match name.split_once('_') {
+        Some((base, subscript)) if !base.is_empty() && !subscript.is_empty() => {
+            (base, Some(subscript))
+        }
+        _ => (name, None),
+    };
+    if subscript.is_some() {
+        writer.write_str("<msub>")?;
+    }
+    match GREEK_LETTERS.iter().find(|(name, _)| *name == base) {
+        Some((_, character)) => write!(writer, "<mi>{}</mi>", character)?,
+        None if base.chars().count() == 1 => write!(writer, "<mi>{}</mi>", base)?,
+        None => write!(writer, r#"<mi mathvariant="italic">{}</mi>"#, base)?,
+    }
+    if let Some(subscript) = subscript {
+        if subscript.chars().all(|c| c.is_ascii_digit()) {
+            write!(writer, "<mn>{}</mn>", subscript)?;
+        } else {
+            write_name(subscript, writer)?;
+        }
+        writer.write_str("</msub>")?;
+    }
+    Ok(())
+}
+
+impl<'a> PresentationMathmlPrinter<'a> {
+    /// Creates a printer resolving variable names from `names`.
+    pub fn new(names: Option<&'a VariableNames>) -> Self {
+        Self { names }
+    }
+
+    /// Writes an expression as a `<math>` element using a [`Decomposer`] to access child expressions.
+    ///
+    /// The expression is written without recursion, so arbitrarily deep expressions cannot overflow the stack.
+    pub fn write<S: Sized, DP: ?Sized + Decomposer<S>, W: ?Sized + Write>(
+        &self,
+        storage: &S,
+        decomposer: &mut DP,
+        writer: &mut W,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        writer.write_str(MATH_START_TAG)?;
+        let expr = decomposer.decompose_as_ref(storage)?.inner_as_ref();
+        print::write_parts(expr, writer, |expr, parts| {
+            self.push_expr(expr, decomposer, parts)
+        })?;
+        writer.write_str(MATH_END_TAG)?;
+        Ok(())
+    }
+
+    /// Writes an [`ExprTree`] into a [`String`].
+    pub fn print(&self, tree: &ExprTree) -> String {
+        let mut output = String::new();
+        self.write_tree(tree, &mut output)
+            .expect("writing into a string never fails");
+        output
+    }
+
+    /// Writes an [`ExprTree`].
+    ///
+    /// This function is a convenience function that simplifies calling [`write`](PresentationMathmlPrinter::write) with a decomposer for [`ExprTree`].
+    pub fn write_tree<W: ?Sized + Write>(
+        &self,
+        tree: &ExprTree,
+        writer: &mut W,
+    ) -> std::fmt::Result {
+        print::write_tree(tree, writer, |tree, decomposer, writer| {
+            self.write(tree, decomposer, writer)
+        })
+    }
+
+    /// Pushes an expression as a single element, where it needs no parentheses.
+    fn push_element<'e, S: Sized, DP: ?Sized + Decomposer<S>>(
+        &self,
+        storage: &'e S,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        let expr = decomposer.decompose_as_ref(storage)?.inner_as_ref();
+        parts.push(Part::Expr(expr));
+        Ok(())
+    }
+
+    fn push_parenthesized<'e, S: Sized>(
+        &self,
+        expr: &'e Expr<S>,
+        parts: &mut Vec<Part<'e, S>>,
+        parenthesize: bool,
+    ) {
+        if parenthesize {
+            parts.extend([
+                Part::text("<mrow><mo>(</mo>"),
+                Part::Expr(expr),
+                Part::text("<mo>)</mo></mrow>"),
+            ]);
+        } else {
+            parts.push(Part::Expr(expr));
+        }
+    }
+
+    /// Pushes an operand of an infix operator, in parentheses if its precedence is lower than `minimum`.
+    ///
+    /// Negative right operands are parenthesized, e.g. `a − (−b)`.
+    fn push_operand<'e, S: Sized, DP: ?Sized + Decomposer<S>>(
+        &self,
+        storage: &'e S,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+        minimum: u8,
+        is_right: bool,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        let expr = decomposer.decompose_as_ref(storage)?.inner_as_ref();
+        let precedence = precedence(expr, Notation::Layout);
+        let parenthesize = precedence < minimum || (is_right && precedence == PREFIX);
+        self.push_parenthesized(expr, parts, parenthesize);
+        Ok(())
+    }
+
+    fn push_binary<'e, S: Sized, B: BinaryOperationExpr<S>, DP: ?Sized + Decomposer<S>>(
+        &self,
+        expr: &'e B,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+        operator: &str,
+        precedence: u8,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        parts.push(Part::text("<mrow>"));
+        self.push_operand(expr.lhs(), decomposer, parts, precedence, false)?;
+        parts.push(Part::text(format!("<mo>{}</mo>", operator)));
+        self.push_operand(expr.rhs(), decomposer, parts, precedence + 1, true)?;
+        parts.push(Part::text("</mrow>"));
+        Ok(())
+    }
+
+    fn push_fraction<'e, S: Sized, DP: ?Sized + Decomposer<S>>(
+        &self,
+        numerator: &'e S,
+        denominator: &'e S,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        parts.push(Part::text("<mfrac>"));
+        self.push_element(numerator, decomposer, parts)?;
+        self.push_element(denominator, decomposer, parts)?;
+        parts.push(Part::text("</mfrac>"));
+        Ok(())
+    }
+
+    /// Pushes the start of a power and its base, the exponent must be pushed after it and followed by `</msup>`.
+    fn push_power<'e, S: Sized, DP: ?Sized + Decomposer<S>>(
+        &self,
+        base: &'e S,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        let base = decomposer.decompose_as_ref(base)?.inner_as_ref();
+        parts.push(Part::text("<msup>"));
+        self.push_parenthesized(base, parts, !is_simple_base(base));
+        Ok(())
+    }
+
+    fn push_root<'e, S: Sized, DP: ?Sized + Decomposer<S>>(
+        &self,
+        radicand: &'e S,
+        degree: &'e S,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        parts.push(Part::text("<mroot>"));
+        self.push_element(radicand, decomposer, parts)?;
+        self.push_element(degree, decomposer, parts)?;
+        parts.push(Part::text("</mroot>"));
+        Ok(())
+    }
+
+    fn write_variable<W: ?Sized + Write>(
+        &self,
+        variable: &VariableLengthEnum,
+        writer: &mut W,
+    ) -> std::fmt::Result {
+        match self.names.and_then(|names| names.name(variable)) {
+            Some(name) => write_name(name, writer),
+            None => write!(writer, "<msub><mi>x</mi><mn>{}</mn></msub>", variable),
+        }
+    }
+
+    /// Pushes the markup and the operands of an expression in the order they are written.
+    fn push_expr<'e, S: Sized, DP: ?Sized + Decomposer<S>>(
+        &self,
+        expr: &'e Expr<S>,
+        decomposer: &mut DP,
+        parts: &mut Vec<Part<'e, S>>,
+    ) -> Result<(), TextWriteError<DP::Error>> {
+        let mut leaf = String::new();
+        match expr {
+            Expr::Variable(expr) => self.write_variable(expr.as_ref(), &mut leaf)?,
+            Expr::TrueLiteral(_) => leaf.push_str("<mtext>true</mtext>"),
+            Expr::FalseLiteral(_) => leaf.push_str("<mtext>false</mtext>"),
+            Expr::SignedIntLiteral(expr) => write_number(&expr.value.to_string(), &mut leaf)?,
+            Expr::UnsignedIntLiteral(expr) => write_number(&expr.value.to_string(), &mut leaf)?,
+            Expr::BinaryFloat32Literal(expr) => {
+                write_float(format!("{:?}", expr.value), &mut leaf)?
+            }
+            Expr::BinaryFloat64Literal(expr) => {
+                write_float(format!("{:?}", expr.value), &mut leaf)?
+            }
+            Expr::Addition(expr) => self.push_binary(expr, decomposer, parts, "+", ADDITIVE)?,
+            Expr::Subtraction(expr) => self.push_binary(expr, decomposer, parts, "−", ADDITIVE)?,
+            Expr::Multiplication(expr) => {
+                self.push_binary(expr, decomposer, parts, "⋅", MULTIPLICATIVE)?
+            }
+            Expr::Modulo(expr) => {
+                self.push_binary(expr, decomposer, parts, "mod", MULTIPLICATIVE)?
+            }
+            Expr::Division(expr) => {
+                self.push_fraction(expr.lhs(), expr.rhs(), decomposer, parts)?
+            }
+            Expr::IntDivision(expr) => {
+                parts.push(Part::text("<mrow><mo>⌊</mo>"));
+                self.push_fraction(expr.lhs(), expr.rhs(), decomposer, parts)?;
+                parts.push(Part::text("<mo>⌋</mo></mrow>"));
+            }
+            Expr::Reciprocal(expr) => {
+                parts.push(Part::text("<mfrac><mn>1</mn>"));
+                self.push_element(expr.inner(), decomposer, parts)?;
+                parts.push(Part::text("</mfrac>"));
+            }
+            Expr::Power(expr) => {
+                self.push_power(expr.lhs(), decomposer, parts)?;
+                self.push_element(expr.rhs(), decomposer, parts)?;
+                parts.push(Part::text("</msup>"));
+            }
+            Expr::Square(expr) => {
+                self.push_power(expr.inner(), decomposer, parts)?;
+                parts.push(Part::text("<mn>2</mn></msup>"));
+            }
+            Expr::Cube(expr) => {
+                self.push_power(expr.inner(), decomposer, parts)?;
+                parts.push(Part::text("<mn>3</mn></msup>"));
+            }
+            Expr::SquareRoot(expr) => {
+                parts.push(Part::text("<msqrt>"));
+                self.push_element(expr.inner(), decomposer, parts)?;
+                parts.push(Part::text("</msqrt>"));
+            }
+            Expr::CubeRoot(expr) => {
+                parts.push(Part::text("<mroot>"));
+                self.push_element(expr.inner(), decomposer, parts)?;
+                parts.push(Part::text("<mn>3</mn></mroot>"));
+            }
+            Expr::Root(expr) => self.push_root(expr.lhs(), expr.rhs(), decomposer, parts)?,
+            Expr::IntRoot(expr) => self.push_root(expr.lhs(), expr.rhs(), decomposer, parts)?,
+            Expr::Negation(expr) => {
+                parts.push(Part::text("<mrow><mo>−</mo>"));
+                self.push_operand(expr.inner(), decomposer, parts, PREFIX, true)?;
+                parts.push(Part::text("</mrow>"));
+            }
+        };
+        if !leaf.is_empty() {
+            parts.push(Part::text(leaf));
+        }
+        Ok(())
+    }
+}
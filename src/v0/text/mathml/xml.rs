@@ -0,0 +1,379 @@
+//! Minimal XML reader for MathML documents.
+//!
+//! Reads elements, attributes and text with the predefined and numeric character references.
+//! Comments, processing instructions and the document type declaration are skipped, CDATA sections are read as text.
+//! Namespace prefixes are removed from names of elements, namespaces themselves are not checked.
+//! Elements nested deeper than [`MAX_NESTING_DEPTH`] are rejected, as the parser and the drop of [`Element`] are recursive.
+
+use std::{iter::Peekable, str::Chars};
+
+use crate::v0::text::{error::MathmlParseError, Position, Span, MAX_NESTING_DEPTH};
+
+/// Element of an XML document.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Element {
+    /// Name of the element without a namespace prefix.
+    pub(super) name: String,
+    pub(super) attributes: Vec<(String, String)>,
+    pub(super) children: Vec<Node>,
+    /// Span of the start tag.
+    pub(super) span: Span,
+}
+
+/// Content of an XML element.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Node {
+    Element(Element),
+    Text { text: String, span: Span },
+}
+
+impl Element {
+    /// Returns the value of an attribute, namespace prefixes of attribute names are ignored.
+    pub(super) fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| local_name(attribute) == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Removes the namespace prefix from a name.
+fn local_name(name: &str) -> &str {
+    name.rsplit_once(':').map_or(name, |(_, local)| local)
+}
+
+fn is_name_character(character: char) -> bool {
+    character.is_alphanumeric() || matches!(character, '_' | '-' | '.' | ':')
+}
+
+/// Element whose end tag wasn't read yet, with its name as written in the start tag.
+struct OpenElement {
+    qualified_name: String,
+    element: Element,
+}
+
+struct Reader<'a> {
+    chars: Peekable<Chars<'a>>,
+    position: Position,
+}
+
+/// Reads an XML document and returns its root element.
+pub(super) fn read_document(text: &str) -> Result<Element, MathmlParseError> {
+    Reader {
+        chars: text.chars().peekable(),
+        position: Position::START,
+    }
+    .document()
+}
+
+impl Reader<'_> {
+    fn bump(&mut self) -> Option<char> {
+        let character = self.chars.next()?;
+        self.position = self.position.advance(character);
+        Some(character)
+    }
+
+    fn starts_with(&self, text: &str) -> bool {
+        let mut chars = self.chars.clone();
+        text.chars()
+            .all(|character| chars.next() == Some(character))
+    }
+
+    fn unexpected_end(&self) -> MathmlParseError {
+        MathmlParseError::UnexpectedEnd {
+            span: Span::new(self.position, self.position),
+        }
+    }
+
+    /// Reads the next character, which must be `expected`.
+    fn expect(&mut self, expected: char) -> Result<(), MathmlParseError> {
+        let start = self.position;
+        match self.bump() {
+            Some(character) if character == expected => Ok(()),
+            Some(character) => Err(MathmlParseError::UnexpectedCharacter {
+                character,
+                span: Span::new(start, self.position),
+            }),
+            None => Err(self.unexpected_end()),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    /// Skips everything up to and including `terminator`.
+    fn skip_past(&mut self, terminator: &str) -> Result<(), MathmlParseError> {
+        while !self.starts_with(terminator) {
+            self.bump().ok_or_else(|| self.unexpected_end())?;
+        }
+        for _ in terminator.chars() {
+            self.bump();
+        }
+        Ok(())
+    }
+
+    /// Skips a comment, a processing instruction or a document type declaration, if one follows.
+    ///
+    /// Returns `true` if anything was skipped.
+    fn skip_markup(&mut self) -> Result<bool, MathmlParseError> {
+        if self.starts_with("<!--") {
+            self.skip_past("-->")?;
+        } else if self.starts_with("<?") {
+            self.skip_past("?>")?;
+        } else if self.starts_with("<!DOCTYPE") {
+            let mut depth = 0usize;
+            loop {
+                match self.bump().ok_or_else(|| self.unexpected_end())? {
+                    '[' => depth += 1,
+                    ']' => depth = depth.saturating_sub(1),
+                    '>' if depth == 0 => break,
+                    _ => {}
+                }
+            }
+        } else {
+            return Ok(false);
+        }
+        Ok(true)
+    }
+
+    fn name(&mut self) -> Result<String, MathmlParseError> {
+        let start = self.position;
+        let mut name = String::new();
+        while let Some(&character) = self.chars.peek() {
+            if !is_name_character(character) {
+                break;
+            }
+            name.push(character);
+            self.bump();
+        }
+        if name.is_empty() {
+            return match self.bump() {
+                Some(character) => Err(MathmlParseError::UnexpectedCharacter {
+                    character,
+                    span: Span::new(start, self.position),
+                }),
+                None => Err(self.unexpected_end()),
+            };
+        }
+        Ok(name)
+    }
+
+    /// Reads a character reference after the `&`.
+    fn reference(&mut self, start: Position) -> Result<char, MathmlParseError> {
+        let mut entity = String::new();
+        loop {
+            match self.bump() {
+                Some(';') => break,
+                Some(character) if is_name_character(character) || character == '#' => {
+                    entity.push(character)
+                }
+                Some(_) | None => {
+                    return Err(MathmlParseError::UnknownEntity {
+                        entity,
+                        span: Span::new(start, self.position),
+                    })
+                }
+            }
+        }
+        let character = match entity.as_str() {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            _ => match entity.strip_prefix('#') {
+                Some(code) => match code.strip_prefix('x') {
+                    Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                    None => code.parse().ok(),
+                }
+                .and_then(char::from_u32),
+                None => None,
+            },
+        };
+        character.ok_or_else(|| MathmlParseError::UnknownEntity {
+            entity,
+            span: Span::new(start, self.position),
+        })
+    }
+
+    fn attribute_value(&mut self) -> Result<String, MathmlParseError> {
+        let start = self.position;
+        let quote = match self.bump() {
+            Some(quote @ ('"' | '\'')) => quote,
+            Some(character) => {
+                return Err(MathmlParseError::UnexpectedCharacter {
+                    character,
+                    span: Span::new(start, self.position),
+                })
+            }
+            None => return Err(self.unexpected_end()),
+        };
+        let mut value = String::new();
+        loop {
+            let start = self.position;
+            match self.bump() {
+                Some(character) if character == quote => return Ok(value),
+                Some('&') => value.push(self.reference(start)?),
+                Some('<') => {
+                    return Err(MathmlParseError::UnexpectedCharacter {
+                        character: '<',
+                        span: Span::new(start, self.position),
+                    })
+                }
+                Some(character) => value.push(character),
+                None => return Err(self.unexpected_end()),
+            }
+        }
+    }
+
+    /// Reads a start tag after the `<`. Returns the element and whether it is empty (`<name/>`).
+    fn start_tag(&mut self, start: Position) -> Result<(OpenElement, bool), MathmlParseError> {
+        let qualified_name = self.name()?;
+        let mut attributes = Vec::new();
+        let is_empty = loop {
+            self.skip_whitespace();
+            if self.starts_with("/") {
+                self.bump();
+                self.expect('>')?;
+                break true;
+            }
+            if self.starts_with(">") {
+                self.bump();
+                break false;
+            }
+            let name = self.name()?;
+            self.skip_whitespace();
+            self.expect('=')?;
+            self.skip_whitespace();
+            attributes.push((name, self.attribute_value()?));
+        };
+        let element = Element {
+            name: local_name(&qualified_name).to_string(),
+            attributes,
+            children: Vec::new(),
+            span: Span::new(start, self.position),
+        };
+        Ok((
+            OpenElement {
+                qualified_name,
+                element,
+            },
+            is_empty,
+        ))
+    }
+
+    /// Reads text up to the next tag.
+    fn text(&mut self) -> Result<String, MathmlParseError> {
+        let mut text = String::new();
+        while let Some(&character) = self.chars.peek() {
+            if character == '<' {
+                if !self.starts_with("<![CDATA[") {
+                    break;
+                }
+                for _ in "<![CDATA[".chars() {
+                    self.bump();
+                }
+                while !self.starts_with("]]>") {
+                    text.extend(self.bump());
+                    if self.chars.peek().is_none() {
+                        return Err(self.unexpected_end());
+                    }
+                }
+                self.skip_past("]]>")?;
+                continue;
+            }
+            let start = self.position;
+            self.bump();
+            match character {
+                '&' => text.push(self.reference(start)?),
+                _ => text.push(character),
+            }
+        }
+        Ok(text)
+    }
+
+    fn document(mut self) -> Result<Element, MathmlParseError> {
+        let mut open: Vec<OpenElement> = Vec::new();
+        let mut root = None;
+        loop {
+            if open.is_empty() {
+                self.skip_whitespace();
+                if self.skip_markup()? {
+                    continue;
+                }
+            }
+            let start = self.position;
+            match self.chars.peek().copied() {
+                None if open.is_empty() => return root.ok_or_else(|| self.unexpected_end()),
+                None => return Err(self.unexpected_end()),
+                Some('<') if !self.starts_with("<![CDATA[") => {
+                    if self.skip_markup()? {
+                        continue;
+                    }
+                    self.bump();
+                    let element = if self.starts_with("/") {
+                        self.bump();
+                        let name = self.name()?;
+                        self.skip_whitespace();
+                        self.expect('>')?;
+                        let span = Span::new(start, self.position);
+                        let element = open.pop().ok_or(MathmlParseError::UnexpectedContent {
+                            expected: "a start tag".to_string(),
+                            found: format!("</{}>", name),
+                            span,
+                        })?;
+                        if element.qualified_name != name {
+                            return Err(MathmlParseError::MismatchedEndTag {
+                                expected: element.qualified_name,
+                                found: name,
+                                span,
+                            });
+                        }
+                        element.element
+                    } else {
+                        if root.is_some() {
+                            return Err(MathmlParseError::UnexpectedContent {
+                                expected: "end of input".to_string(),
+                                found: "a second root element".to_string(),
+                                span: Span::new(start, self.position),
+                            });
+                        }
+                        let (element, is_empty) = self.start_tag(start)?;
+                        if open.len() >= MAX_NESTING_DEPTH {
+                            return Err(MathmlParseError::NestingTooDeep {
+                                limit: MAX_NESTING_DEPTH,
+                                span: element.element.span,
+                            });
+                        }
+                        if !is_empty {
+                            open.push(element);
+                            continue;
+                        }
+                        element.element
+                    };
+                    match open.last_mut() {
+                        Some(parent) => parent.element.children.push(Node::Element(element)),
+                        None => root = Some(element),
+                    }
+                }
+                Some(character) if open.is_empty() => {
+                    self.bump();
+                    return Err(MathmlParseError::UnexpectedCharacter {
+                        character,
+                        span: Span::new(start, self.position),
+                    });
+                }
+                Some(_) => {
+                    let text = self.text()?;
+                    let span = Span::new(start, self.position);
+                    if let Some(parent) = open.last_mut() {
+                        parent.element.children.push(Node::Text { text, span });
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! Text formats of expressions.
+//!
+//! The binary FEF format is not meant to be read or written by people. This module converts expressions from and to text formats:
+//! * [`infix`] - the notation used by calculators and programming languages, e.g. `sqrt(x^2 + y^2) / 2`.
+//! * [`latex`] - the math notation of LaTeX, e.g. `\frac{\sqrt{x^{2} + y^{2}}}{2}`.
+//! * [`mathml`] - Presentation and Content MathML markup, e.g. `<mfrac><msqrt>…</msqrt><mn>2</mn></mfrac>`.
+//! * [`sexpr`] - S-expressions mapping 1:1 to the binary format, e.g. `(div (sqrt (add (square (var 0)) (square (var 1)))) (u8 2))`.
+//!
+//! Text formats refer to variables by names, which are mapped to the identifiers of variables by [`VariableNames`].
+//! Errors of parsers carry the [span](Span) of the text they refer to.
+//!
+//! The infix, LaTeX and Content MathML parsers are recursive, so they reject text nested deeper than [`MAX_NESTING_DEPTH`]
+//! instead of overflowing the stack. S-expressions are parsed without recursion and can be nested arbitrarily deep.
+//! The printers write expressions without recursion, so printing has no depth limit.
+
+mod print;
+mod span;
+mod variables;
+
+pub mod error;
+pub mod infix;
+pub mod latex;
+pub mod mathml;
+pub mod sexpr;
+
+pub use span::{Position, Span};
+
+/// Maximum nesting depth of the text accepted by the recursive parsers of text formats.
+///
+/// Deeper text is rejected with an error, so that untrusted input cannot overflow the stack.
+/// The limit applies only to parsing, the printers write expressions of any depth.
+pub const MAX_NESTING_DEPTH: usize = 128;
+pub use variables::VariableNames;
@@ -0,0 +1,132 @@
+//! Parts shared by the printers of text formats.
+
+use std::{borrow::Cow, convert::Infallible, fmt::Write};
+
+use crate::v0::{
+    expr::{Expr, ExprTree},
+    write::ExprTreeDecomposer,
+};
+
+use super::error::TextWriteError;
+
+/// Precedence levels of expressions, a higher level binds tighter.
+pub(crate) const ADDITIVE: u8 = 1;
+pub(crate) const MULTIPLICATIVE: u8 = 2;
+pub(crate) const PREFIX: u8 = 3;
+pub(crate) const POWER: u8 = 4;
+pub(crate) const ATOM: u8 = 5;
+
+/// Way a printer writes the expressions, that don't have an operator in every notation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Notation {
+    /// Roots, squares and cubes are functions, e.g. `sqrt(x)` and `square(x)`.
+    Functions,
+    /// Roots are prefix operators and squares and cubes are powers, e.g. `√x` and `x²`.
+    Symbols,
+    /// Fractions and roots are laid out in two dimensions, so they need no parentheses.
+    /// Squares and cubes are powers and floats in scientific notation are products, e.g. `1.5 \times 10^{20}`.
+    Layout,
+}
+
+/// Returns the precedence level of the expression written in the notation.
+pub(crate) fn precedence<S: Sized>(expr: &Expr<S>, notation: Notation) -> u8 {
+    let is_layout = notation == Notation::Layout;
+    match expr {
+        Expr::Addition(_) | Expr::Subtraction(_) => ADDITIVE,
+        Expr::Multiplication(_) | Expr::Modulo(_) => MULTIPLICATIVE,
+        Expr::Division(_) | Expr::IntDivision(_) if !is_layout => MULTIPLICATIVE,
+        Expr::BinaryFloat32Literal(literal)
+            if is_layout && is_scientific(&format!("{:?}", literal.value)) =>
+        {
+            MULTIPLICATIVE
+        }
+        Expr::BinaryFloat64Literal(literal)
+            if is_layout && is_scientific(&format!("{:?}", literal.value)) =>
+        {
+            MULTIPLICATIVE
+        }
+        Expr::Negation(_) => PREFIX,
+        Expr::SignedIntLiteral(literal) if literal.value < 0 => PREFIX,
+        Expr::BinaryFloat32Literal(literal) if literal.value.is_sign_negative() => PREFIX,
+        Expr::BinaryFloat64Literal(literal) if literal.value.is_sign_negative() => PREFIX,
+        Expr::SquareRoot(_) | Expr::CubeRoot(_) if notation == Notation::Symbols => PREFIX,
+        Expr::Power(_) => POWER,
+        Expr::Square(_) | Expr::Cube(_) if notation != Notation::Functions => POWER,
+        _ => ATOM,
+    }
+}
+
+/// Returns `true` for expressions that can be the base of a power without parentheses in the [`Layout`](Notation::Layout) notation.
+pub(crate) fn is_simple_base<S: Sized>(expr: &Expr<S>) -> bool {
+    precedence(expr, Notation::Layout) == ATOM
+        && !matches!(
+            expr,
+            Expr::Division(_)
+                | Expr::Reciprocal(_)
+                | Expr::SquareRoot(_)
+                | Expr::CubeRoot(_)
+                | Expr::Root(_)
+                | Expr::IntRoot(_)
+        )
+}
+
+/// Returns `true` if the shortest representation of a float has an exponent.
+fn is_scientific(float: &str) -> bool {
+    float.contains('e')
+}
+
+/// Writes an [`ExprTree`] by the `write` method of a printer, passing it the decomposer of [`ExprTree`].
+///
+/// Decomposing an [`ExprTree`] never fails, so the only errors returned are the errors of the writer.
+pub(crate) fn write_tree<W: ?Sized + Write>(
+    tree: &ExprTree,
+    writer: &mut W,
+    write: impl FnOnce(
+        &ExprTree,
+        &mut ExprTreeDecomposer,
+        &mut W,
+    ) -> Result<(), TextWriteError<Infallible>>,
+) -> std::fmt::Result {
+    let mut decomposer = ExprTreeDecomposer {};
+    write(tree, &mut decomposer, writer).map_err(|error| match error {
+        TextWriteError::FmtError(error) => error,
+        TextWriteError::DecomposeError(error) => error.unreachable(),
+    })
+}
+
+/// Part of the output of a printer.
+pub(crate) enum Part<'a, S: Sized> {
+    /// Text written as it is.
+    Text(Cow<'static, str>),
+    /// Expression written by the printer in its place.
+    Expr(&'a Expr<S>),
+}
+
+impl<'a, S: Sized> Part<'a, S> {
+    pub(crate) fn text(text: impl Into<Cow<'static, str>>) -> Self {
+        Self::Text(text.into())
+    }
+}
+
+/// Writes an expression without recursion, so that deeply nested expressions cannot overflow the stack.
+///
+/// `expand` pushes the parts of an expression in order, i.e. its text and its operands, which are expanded in turn.
+/// Parts waiting to be written are kept on a heap stack, the next one on top.
+pub(crate) fn write_parts<'a, S: Sized, E: std::error::Error, W: ?Sized + Write>(
+    expr: &'a Expr<S>,
+    writer: &mut W,
+    mut expand: impl FnMut(&'a Expr<S>, &mut Vec<Part<'a, S>>) -> Result<(), TextWriteError<E>>,
+) -> Result<(), TextWriteError<E>> {
+    let mut stack = vec![Part::Expr(expr)];
+    let mut parts = Vec::new();
+    while let Some(part) = stack.pop() {
+        match part {
+            Part::Text(text) => writer.write_str(&text)?,
+            Part::Expr(expr) => {
+                expand(expr, &mut parts)?;
+                stack.extend(parts.drain(..).rev());
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,160 @@
+//! S-expression form of the binary format, e.g. `(add (var 0) (u8 3))`.
+//!
+//! Unlike the other text formats, S-expressions don't describe an [`ExprTree`](crate::v0::expr::ExprTree), but the binary encoding itself.
+//! Every [`ExprToken`] has its own operator, so the exact width of every literal is kept and converting text to binary and back
+//! gives the same text. This makes S-expressions suitable for test fixtures and for reviewing changes of binary files.
+//!
+//! * [`parse_sexpr_expression`] and [`parse_sexpr_file`] convert text into the binary format.
+//! * [`write_sexpr_expression`] and [`write_sexpr_file`] read the binary format and write it as text.
+//!
+//! # Expressions
+//! Every expression is a list of an operator and its operands in the order of the binary encoding.
+//! Boolean literals are written as bare atoms.
+//!
+//! | Token | Operator |
+//! |-------|----------|
+//! | [`Variable`](ExprToken::Variable) | `(var 0)` |
+//! | [`TrueLiteral`](ExprToken::TrueLiteral), [`FalseLiteral`](ExprToken::FalseLiteral) | `true`, `false` |
+//! | [`Addition`](ExprToken::Addition), [`Subtraction`](ExprToken::Subtraction), [`Multiplication`](ExprToken::Multiplication), [`Division`](ExprToken::Division) | `add`, `sub`, `mul`, `div` |
+//! | [`IntDivision`](ExprToken::IntDivision), [`Modulo`](ExprToken::Modulo), [`Power`](ExprToken::Power) | `intdiv`, `mod`, `pow` |
+//! | [`Root`](ExprToken::Root), [`IntRoot`](ExprToken::IntRoot) | `root`, `introot` |
+//! | [`Negation`](ExprToken::Negation), [`Reciprocal`](ExprToken::Reciprocal) | `neg`, `recip` |
+//! | [`Square`](ExprToken::Square), [`Cube`](ExprToken::Cube), [`SquareRoot`](ExprToken::SquareRoot), [`CubeRoot`](ExprToken::CubeRoot) | `square`, `cube`, `sqrt`, `cbrt` |
+//! | [`SignedIntLiteral8`](ExprToken::SignedIntLiteral8) to [`SignedIntLiteral64`](ExprToken::SignedIntLiteral64) | `(i8 -3)`, `i16`, `i32`, `i64` |
+//! | [`UnsignedIntLiteral8`](ExprToken::UnsignedIntLiteral8) to [`UnsignedIntLiteral64`](ExprToken::UnsignedIntLiteral64) | `(u8 3)`, `u16`, `u32`, `u64` |
+//! | [`BinaryFloatLiteral32`](ExprToken::BinaryFloatLiteral32), [`BinaryFloatLiteral64`](ExprToken::BinaryFloatLiteral64) | `(f32 1.5)`, `f64` |
+//!
+//! Integers are written in decimal. Floats are written in their shortest representation which reads back as the same float, e.g. `0.1`,
+//! `1e20`, `-0.0`, `inf` or `NaN`. A NaN other than the one [`f32::NAN`] or [`f64::NAN`] is written as its bits in hexadecimal, e.g. `(f32 0x7fc00001)`.
+//!
+//! # Files
+//! A file is written as a `file` list with the version and the content:
+//! ```text
+//! (file
+//!   (version 0)
+//!   (single-formula
+//!     (configuration
+//!       (entry 1 2))
+//!     (metadata
+//!       (name "Formula")
+//!       (variable-name 0 "x")
+//!       (record 1048832 0x1234)
+//!       (padding 0x0000))
+//!     (expression (add (var 0) (u8 3)))))
+//! ```
+//! * A raw formula is written as `(raw-formula (expression …))` instead of the `single-formula` list.
+//! * Configuration entries are written as `(entry identifier value)`. The value of an entry with an identifier up to `127`
+//!   is an integer, the value of other entries is their data.
+//! * Name and variable name metadata records are written as `name` and `variable-name`. Other records, and name records that
+//!   can't be decoded, are written as `(record identifier data)` with the raw data of the record.
+//!   Bytes after the last record, which are counted in the size of metadata, are written as `padding`.
+//!
+//! Data are written as hexadecimal bytes after `0x`, strings are written in double quotes with the escape sequences
+//! `\"`, `\\`, `\n`, `\r`, `\t` and `\u{…}`. Identifiers and other integers of the binary format are limited to [`usize`].
+//!
+//! The writers put every list of a file on its own line, the parsers accept any whitespace and comments starting with `;`.
+//!
+//! # Examples
+//! ```rust
+//! # use fef::v0::text::sexpr::{parse_sexpr_expression, write_sexpr_expression};
+//! # use fef::v0::config::DEFAULT_CONFIG;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let text = "(add (var 0) (i32 3))";
+//! let bytes = parse_sexpr_expression(text, &DEFAULT_CONFIG)?;
+//! assert_eq!(bytes, vec![0x10, 0x04, 0x00, 0x33, 0x00, 0x00, 0x00, 0x03]);
+//!
+//! let mut written = String::new();
+//! write_sexpr_expression(&mut bytes.as_slice(), &mut written, &DEFAULT_CONFIG)?;
+//! assert_eq!(written, text);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Whole files, including their configuration and metadata:
+//! ```rust
+//! # use fef::v0::text::sexpr::{parse_sexpr_file, write_sexpr_file};
+//! # use fef::v0::config::DEFAULT_CONFIG;
+//! # use fef::v0::read::read_file;
+//! # use fef::v0::raw::VariableLengthEnum;
+//! # use fef::v0::traits::ReadFrom;
+//! # use fef::v0::file::File;
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let text = r#"(file
+//!   (version 0)
+//!   (single-formula
+//!     (configuration)
+//!     (metadata
+//!       (variable-name 0 "x"))
+//!     (expression (mul (f64 0.5) (var 0)))))"#;
+//! let bytes = parse_sexpr_file(text, &DEFAULT_CONFIG)?;
+//!
+//! let mut written = String::new();
+//! write_sexpr_file(&mut bytes.as_slice(), &mut written, &DEFAULT_CONFIG)?;
+//! assert_eq!(written, text);
+//!
+//! let mut reader = bytes.as_slice();
+//! let _version = VariableLengthEnum::read_from(&mut reader, &DEFAULT_CONFIG)?;
+//! let file = read_file(&mut reader, &DEFAULT_CONFIG)?;
+//! assert!(matches!(file, File::SingleFormula(_)));
+//! # Ok(())
+//! # }
+//! ```
+
+mod parse;
+mod print;
+mod syntax;
+
+pub use parse::{parse_sexpr_expression, parse_sexpr_file};
+pub use print::{write_sexpr_expression, write_sexpr_file};
+
+use crate::v0::tokens::ExprToken;
+
+/// Operators of the expression tokens.
+const OPERATORS: [(ExprToken, &str); 28] = [
+    (ExprToken::Variable, "var"),
+    (ExprToken::TrueLiteral, "true"),
+    (ExprToken::FalseLiteral, "false"),
+    (ExprToken::Addition, "add"),
+    (ExprToken::Subtraction, "sub"),
+    (ExprToken::Multiplication, "mul"),
+    (ExprToken::Division, "div"),
+    (ExprToken::IntDivision, "intdiv"),
+    (ExprToken::Modulo, "mod"),
+    (ExprToken::Power, "pow"),
+    (ExprToken::Negation, "neg"),
+    (ExprToken::Root, "root"),
+    (ExprToken::IntRoot, "introot"),
+    (ExprToken::Square, "square"),
+    (ExprToken::Cube, "cube"),
+    (ExprToken::SquareRoot, "sqrt"),
+    (ExprToken::CubeRoot, "cbrt"),
+    (ExprToken::Reciprocal, "recip"),
+    (ExprToken::SignedIntLiteral8, "i8"),
+    (ExprToken::SignedIntLiteral16, "i16"),
+    (ExprToken::SignedIntLiteral32, "i32"),
+    (ExprToken::SignedIntLiteral64, "i64"),
+    (ExprToken::UnsignedIntLiteral8, "u8"),
+    (ExprToken::UnsignedIntLiteral16, "u16"),
+    (ExprToken::UnsignedIntLiteral32, "u32"),
+    (ExprToken::UnsignedIntLiteral64, "u64"),
+    (ExprToken::BinaryFloatLiteral32, "f32"),
+    (ExprToken::BinaryFloatLiteral64, "f64"),
+];
+
+fn operator_name(token: ExprToken) -> &'static str {
+    OPERATORS
+        .iter()
+        .find(|(operator, _)| *operator == token)
+        .map(|(_, name)| *name)
+        .expect("every expression token has an operator")
+}
+
+fn operator_token(name: &str) -> Option<ExprToken> {
+    OPERATORS
+        .iter()
+        .find(|(_, operator)| *operator == name)
+        .map(|(token, _)| *token)
+}
+
+/// Identifiers of configuration entries up to this value have an integer value, other entries have data.
+const MAX_ENUM_CONFIGURATION_IDENTIFIER: usize = 0x7F;
@@ -0,0 +1,413 @@
+use crate::v0::{
+    config::Config,
+    raw::VariableLengthEnum,
+    text::{error::SexprParseError, Span},
+    tokens::{ExprToken, FileContentTypeToken, MetadataToken},
+    traits::WriteTo,
+};
+
+use super::{
+    operator_token,
+    syntax::{read_sexpr, Sexpr},
+    MAX_ENUM_CONFIGURATION_IDENTIFIER,
+};
+
+/// Parses an [S-expression](super) of an expression and returns its binary encoding.
+///
+/// Nested lists are parsed without recursion, so arbitrarily deep expressions cannot overflow the stack.
+///
+/// # Examples
+/// ```rust
+/// # use fef::v0::text::sexpr::parse_sexpr_expression;
+/// # use fef::v0::text::error::SexprParseError;
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let bytes = parse_sexpr_expression("(neg (u16 300))", &DEFAULT_CONFIG)?;
+/// assert_eq!(bytes, vec![0x17, 0x39, 0x01, 0x2C]);
+///
+/// let error = parse_sexpr_expression("(i8 300)", &DEFAULT_CONFIG).unwrap_err();
+/// assert!(matches!(error, SexprParseError::LiteralOutOfRange { .. }));
+/// # Ok(())
+/// # }
+/// ```
+pub fn parse_sexpr_expression<C: ?Sized + Config>(
+    text: &str,
+    configuration: &C,
+) -> Result<Vec<u8>, SexprParseError> {
+    let sexpr = read_sexpr(text)?;
+    let mut bytes = Vec::new();
+    expression(&sexpr, &mut bytes, configuration)?;
+    Ok(bytes)
+}
+
+/// Parses an [S-expression](super#files) of a file and returns its binary encoding, starting with the version.
+pub fn parse_sexpr_file<C: ?Sized + Config>(
+    text: &str,
+    configuration: &C,
+) -> Result<Vec<u8>, SexprParseError> {
+    let sexpr = read_sexpr(text)?;
+    let mut bytes = Vec::new();
+    let [version, content] = form(&sexpr, "file")?;
+    let [version] = form(version, "version")?;
+    write_usize(integer(version)?, &mut bytes, configuration);
+
+    let (name, arguments, span) = head(content)?;
+    match name {
+        "raw-formula" => {
+            let [expression] = arguments_of(name, arguments, span)?;
+            write_usize(
+                FileContentTypeToken::RawFormula.into(),
+                &mut bytes,
+                configuration,
+            );
+            expression_form(expression, &mut bytes, configuration)?;
+        }
+        "single-formula" => {
+            let [configuration_list, metadata, expression] = arguments_of(name, arguments, span)?;
+            write_usize(
+                FileContentTypeToken::SingleFormula.into(),
+                &mut bytes,
+                configuration,
+            );
+            configuration_entries(configuration_list, &mut bytes, configuration)?;
+            metadata_records(metadata, &mut bytes, configuration)?;
+            expression_form(expression, &mut bytes, configuration)?;
+        }
+        _ => return Err(unexpected("(raw-formula …) or (single-formula …)", content)),
+    }
+    Ok(bytes)
+}
+
+fn unexpected(expected: &str, found: &Sexpr) -> SexprParseError {
+    SexprParseError::UnexpectedToken {
+        expected: expected.to_string(),
+        found: found.describe(),
+        span: found.span(),
+    }
+}
+
+fn write_usize<C: ?Sized + Config>(value: usize, bytes: &mut Vec<u8>, configuration: &C) {
+    VariableLengthEnum::from(value)
+        .write_to(bytes, configuration)
+        .expect("writing into a vector never fails");
+}
+
+/// Splits a list into its leading atom, the remaining items and the span of the atom. A bare atom is a list without arguments.
+fn head(sexpr: &Sexpr) -> Result<(&str, &[Sexpr], Span), SexprParseError> {
+    match sexpr {
+        Sexpr::Atom { text, span } => Ok((text, &[], *span)),
+        Sexpr::List { items, .. } => match items.split_first() {
+            Some((Sexpr::Atom { text, span }, arguments)) => Ok((text, arguments, *span)),
+            Some((item, _)) => Err(unexpected("a name", item)),
+            None => Err(unexpected("a name", sexpr)),
+        },
+        Sexpr::String { .. } => Err(unexpected("a list", sexpr)),
+    }
+}
+
+fn arguments_of<'a, const N: usize>(
+    name: &str,
+    arguments: &'a [Sexpr],
+    span: Span,
+) -> Result<&'a [Sexpr; N], SexprParseError> {
+    arguments
+        .try_into()
+        .map_err(|_| SexprParseError::ArgumentCount {
+            form: name.to_string(),
+            expected: N,
+            found: arguments.len(),
+            span,
+        })
+}
+
+/// Reads a list starting with `name` with exactly `N` arguments.
+fn form<'a, const N: usize>(
+    sexpr: &'a Sexpr,
+    name: &str,
+) -> Result<&'a [Sexpr; N], SexprParseError> {
+    let (arguments, span) = variadic_form(sexpr, name)?;
+    arguments_of(name, arguments, span)
+}
+
+/// Reads a list starting with `name` with any number of arguments.
+fn variadic_form<'a>(sexpr: &'a Sexpr, name: &str) -> Result<(&'a [Sexpr], Span), SexprParseError> {
+    match head(sexpr) {
+        Ok((found, arguments, span)) if found == name && matches!(sexpr, Sexpr::List { .. }) => {
+            Ok((arguments, span))
+        }
+        _ => Err(unexpected(&format!("({} …)", name), sexpr)),
+    }
+}
+
+fn integer<T: TryFrom<i128>>(sexpr: &Sexpr) -> Result<T, SexprParseError> {
+    let Sexpr::Atom { text, span } = sexpr else {
+        return Err(unexpected("an integer", sexpr));
+    };
+    let value: i128 = text.parse().map_err(|_| unexpected("an integer", sexpr))?;
+    value
+        .try_into()
+        .map_err(|_| SexprParseError::LiteralOutOfRange {
+            literal: text.clone(),
+            span: *span,
+        })
+}
+
+fn string(sexpr: &Sexpr) -> Result<&str, SexprParseError> {
+    match sexpr {
+        Sexpr::String { text, .. } => Ok(text),
+        _ => Err(unexpected("a string", sexpr)),
+    }
+}
+
+fn data(sexpr: &Sexpr) -> Result<Vec<u8>, SexprParseError> {
+    let error = || unexpected("hexadecimal data", sexpr);
+    let Sexpr::Atom { text, .. } = sexpr else {
+        return Err(error());
+    };
+    let digits = text.strip_prefix("0x").ok_or_else(error)?;
+    if digits.len() % 2 != 0 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(error());
+    }
+    (0..digits.len())
+        .step_by(2)
+        .map(|index| u8::from_str_radix(&digits[index..index + 2], 16).map_err(|_| error()))
+        .collect()
+}
+
+/// Reads the value of a float literal, given either as a number or as its bits after `0x`.
+fn float<F: std::str::FromStr, B>(
+    sexpr: &Sexpr,
+    from_bits: impl Fn(&str) -> Option<B>,
+    to_float: impl Fn(B) -> F,
+) -> Result<F, SexprParseError> {
+    let Sexpr::Atom { text, span } = sexpr else {
+        return Err(unexpected("a float", sexpr));
+    };
+    match text.strip_prefix("0x") {
+        Some(bits) => {
+            from_bits(bits)
+                .map(to_float)
+                .ok_or_else(|| SexprParseError::LiteralOutOfRange {
+                    literal: text.clone(),
+                    span: *span,
+                })
+        }
+        None => text.parse().map_err(|_| unexpected("a float", sexpr)),
+    }
+}
+
+/// Reads an `(expression …)` list.
+fn expression_form<C: ?Sized + Config>(
+    sexpr: &Sexpr,
+    bytes: &mut Vec<u8>,
+    configuration: &C,
+) -> Result<(), SexprParseError> {
+    let [inner] = form(sexpr, "expression")?;
+    expression(inner, bytes, configuration)
+}
+
+/// Writes the expression without recursion, so that deeply nested expressions cannot overflow the stack.
+fn expression<C: ?Sized + Config>(
+    sexpr: &Sexpr,
+    bytes: &mut Vec<u8>,
+    configuration: &C,
+) -> Result<(), SexprParseError> {
+    // Expressions waiting to be written, the next one on top.
+    let mut stack = vec![sexpr];
+    while let Some(sexpr) = stack.pop() {
+        expression_header(sexpr, bytes, configuration, &mut stack)?;
+    }
+    Ok(())
+}
+
+/// Writes the token and the value of the expression and pushes its operands to the stack, lhs on top.
+fn expression_header<'a, C: ?Sized + Config>(
+    sexpr: &'a Sexpr,
+    bytes: &mut Vec<u8>,
+    configuration: &C,
+    stack: &mut Vec<&'a Sexpr>,
+) -> Result<(), SexprParseError> {
+    let (name, arguments, span) = match sexpr {
+        Sexpr::String { .. } => return Err(unexpected("an expression", sexpr)),
+        _ => head(sexpr)?,
+    };
+    let token = operator_token(name).ok_or_else(|| SexprParseError::UnknownOperator {
+        name: name.to_string(),
+        span,
+    })?;
+    token
+        .write_to(bytes, configuration)
+        .expect("writing into a vector never fails");
+    match token {
+        ExprToken::TrueLiteral | ExprToken::FalseLiteral => {
+            let [] = arguments_of(name, arguments, span)?;
+        }
+        ExprToken::Variable => {
+            let [identifier] = arguments_of(name, arguments, span)?;
+            write_usize(integer(identifier)?, bytes, configuration);
+        }
+        ExprToken::SignedIntLiteral8 => {
+            let [value] = arguments_of(name, arguments, span)?;
+            bytes.extend(integer::<i8>(value)?.to_be_bytes());
+        }
+        ExprToken::SignedIntLiteral16 => {
+            let [value] = arguments_of(name, arguments, span)?;
+            bytes.extend(integer::<i16>(value)?.to_be_bytes());
+        }
+        ExprToken::SignedIntLiteral32 => {
+            let [value] = arguments_of(name, arguments, span)?;
+            bytes.extend(integer::<i32>(value)?.to_be_bytes());
+        }
+        ExprToken::SignedIntLiteral64 => {
+            let [value] = arguments_of(name, arguments, span)?;
+            bytes.extend(integer::<i64>(value)?.to_be_bytes());
+        }
+        ExprToken::UnsignedIntLiteral8 => {
+            let [value] = arguments_of(name, arguments, span)?;
+            bytes.extend(integer::<u8>(value)?.to_be_bytes());
+        }
+        ExprToken::UnsignedIntLiteral16 => {
+            let [value] = arguments_of(name, arguments, span)?;
+            bytes.extend(integer::<u16>(value)?.to_be_bytes());
+        }
+        ExprToken::UnsignedIntLiteral32 => {
+            let [value] = arguments_of(name, arguments, span)?;
+            bytes.extend(integer::<u32>(value)?.to_be_bytes());
+        }
+        ExprToken::UnsignedIntLiteral64 => {
+            let [value] = arguments_of(name, arguments, span)?;
+            bytes.extend(integer::<u64>(value)?.to_be_bytes());
+        }
+        ExprToken::BinaryFloatLiteral32 => {
+            let [value] = arguments_of(name, arguments, span)?;
+            let value: f32 = float(
+                value,
+                |bits| u32::from_str_radix(bits, 16).ok(),
+                f32::from_bits,
+            )?;
+            bytes.extend(value.to_be_bytes());
+        }
+        ExprToken::BinaryFloatLiteral64 => {
+            let [value] = arguments_of(name, arguments, span)?;
+            let value: f64 = float(
+                value,
+                |bits| u64::from_str_radix(bits, 16).ok(),
+                f64::from_bits,
+            )?;
+            bytes.extend(value.to_be_bytes());
+        }
+        ExprToken::Negation
+        | ExprToken::Square
+        | ExprToken::Cube
+        | ExprToken::SquareRoot
+        | ExprToken::CubeRoot
+        | ExprToken::Reciprocal => {
+            let [inner] = arguments_of(name, arguments, span)?;
+            stack.push(inner);
+        }
+        ExprToken::Addition
+        | ExprToken::Subtraction
+        | ExprToken::Multiplication
+        | ExprToken::Division
+        | ExprToken::IntDivision
+        | ExprToken::Modulo
+        | ExprToken::Power
+        | ExprToken::Root
+        | ExprToken::IntRoot => {
+            let [lhs, rhs] = arguments_of(name, arguments, span)?;
+            stack.push(rhs);
+            stack.push(lhs);
+        }
+    }
+    Ok(())
+}
+
+/// Reads a `(configuration …)` list.
+fn configuration_entries<C: ?Sized + Config>(
+    sexpr: &Sexpr,
+    bytes: &mut Vec<u8>,
+    configuration: &C,
+) -> Result<(), SexprParseError> {
+    let (entries, _) = variadic_form(sexpr, "configuration")?;
+    write_usize(entries.len(), bytes, configuration);
+    for entry in entries {
+        let [identifier, value] = form(entry, "entry")?;
+        let identifier: usize = integer(identifier)?;
+        write_usize(identifier, bytes, configuration);
+        if identifier <= MAX_ENUM_CONFIGURATION_IDENTIFIER {
+            write_usize(integer(value)?, bytes, configuration);
+        } else {
+            let value = data(value)?;
+            write_usize(value.len(), bytes, configuration);
+            bytes.extend(value);
+        }
+    }
+    Ok(())
+}
+
+/// Reads a `(metadata …)` list.
+fn metadata_records<C: ?Sized + Config>(
+    sexpr: &Sexpr,
+    bytes: &mut Vec<u8>,
+    configuration: &C,
+) -> Result<(), SexprParseError> {
+    let (mut records, _) = variadic_form(sexpr, "metadata")?;
+    let mut padding = Vec::new();
+    if let Some(last) = records.last() {
+        if matches!(head(last), Ok(("padding", _, _))) {
+            let [data_item] = form(last, "padding")?;
+            padding = data(data_item)?;
+            records = &records[..records.len() - 1];
+            if records.is_empty() {
+                return Err(unexpected("a metadata record", last));
+            }
+        }
+    }
+
+    let mut content = Vec::new();
+    for record in records {
+        let (name, arguments, span) = head(record)?;
+        let (identifier, data) = match name {
+            "name" => {
+                let [name] = arguments_of(name, arguments, span)?;
+                let mut data = Vec::new();
+                string(name)?
+                    .to_string()
+                    .write_to(&mut data, configuration)
+                    .expect("writing into a vector never fails");
+                (MetadataToken::Name.into(), data)
+            }
+            "variable-name" => {
+                let [identifier, name] = arguments_of(name, arguments, span)?;
+                let mut data = Vec::new();
+                write_usize(integer(identifier)?, &mut data, configuration);
+                string(name)?
+                    .to_string()
+                    .write_to(&mut data, configuration)
+                    .expect("writing into a vector never fails");
+                (MetadataToken::VariableName.into(), data)
+            }
+            "record" => {
+                let [identifier, record_data] = arguments_of(name, arguments, span)?;
+                (integer(identifier)?, data(record_data)?)
+            }
+            _ => {
+                return Err(unexpected(
+                    "(name …), (variable-name …), (record …) or (padding …)",
+                    record,
+                ))
+            }
+        };
+        write_usize(identifier, &mut content, configuration);
+        write_usize(data.len(), &mut content, configuration);
+        content.extend(data);
+    }
+    content.extend(padding);
+
+    write_usize(records.len(), bytes, configuration);
+    if !records.is_empty() {
+        write_usize(content.len(), bytes, configuration);
+        bytes.extend(content);
+    }
+    Ok(())
+}
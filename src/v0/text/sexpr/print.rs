@@ -0,0 +1,305 @@
+use std::{
+    fmt::Write,
+    io::{ErrorKind, Read},
+};
+
+use crate::v0::{
+    config::Config,
+    raw::VariableLengthEnum,
+    text::error::SexprWriteError,
+    tokens::{ExprToken, FileContentTypeToken, MetadataToken},
+    traits::ReadFrom,
+};
+
+use super::{operator_name, MAX_ENUM_CONFIGURATION_IDENTIFIER};
+
+/// Reads an expression in the binary format and writes it as an [S-expression](super).
+///
+/// The expression is written without recursion, so arbitrarily deep expressions cannot overflow the stack.
+///
+/// # Examples
+/// ```rust
+/// # use fef::v0::text::sexpr::write_sexpr_expression;
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let bytes = [0x16, 0x04, 0x00, 0x42, 0x3F, 0xC0, 0x00, 0x00];
+///
+/// let mut text = String::new();
+/// write_sexpr_expression(&mut bytes.as_slice(), &mut text, &DEFAULT_CONFIG)?;
+/// assert_eq!(text, "(pow (var 0) (f32 1.5))");
+/// # Ok(())
+/// # }
+/// ```
+pub fn write_sexpr_expression<R: ?Sized + Read, W: ?Sized + Write, C: ?Sized + Config>(
+    reader: &mut R,
+    writer: &mut W,
+    configuration: &C,
+) -> Result<(), SexprWriteError> {
+    // Characters written after the operand being written, the next one on top. A space is followed by another operand.
+    let mut pending: Vec<char> = Vec::new();
+    loop {
+        if write_sexpr_header(reader, writer, configuration, &mut pending)? {
+            continue;
+        }
+        // The operand is complete, close the operations until one of them expects another operand.
+        loop {
+            match pending.pop() {
+                None => return Ok(()),
+                Some(' ') => {
+                    writer.write_char(' ')?;
+                    break;
+                }
+                Some(character) => writer.write_char(character)?,
+            }
+        }
+    }
+}
+
+/// Reads a token and writes the expression up to its first operand.
+///
+/// Returns `true` if the expression is an operation, whose operands are read next. The characters after its operands are pushed to `pending`.
+fn write_sexpr_header<R: ?Sized + Read, W: ?Sized + Write, C: ?Sized + Config>(
+    reader: &mut R,
+    writer: &mut W,
+    configuration: &C,
+    pending: &mut Vec<char>,
+) -> Result<bool, SexprWriteError> {
+    let token = ExprToken::read_from(reader, configuration)?;
+    let name = operator_name(token);
+    match token {
+        ExprToken::TrueLiteral | ExprToken::FalseLiteral => writer.write_str(name)?,
+        ExprToken::Variable => write!(writer, "({} {})", name, read_usize(reader, configuration)?)?,
+        ExprToken::SignedIntLiteral8 => {
+            write_literal(name, i8::from_be_bytes(read_array(reader)?), writer)?
+        }
+        ExprToken::SignedIntLiteral16 => {
+            write_literal(name, i16::from_be_bytes(read_array(reader)?), writer)?
+        }
+        ExprToken::SignedIntLiteral32 => {
+            write_literal(name, i32::from_be_bytes(read_array(reader)?), writer)?
+        }
+        ExprToken::SignedIntLiteral64 => {
+            write_literal(name, i64::from_be_bytes(read_array(reader)?), writer)?
+        }
+        ExprToken::UnsignedIntLiteral8 => {
+            write_literal(name, u8::from_be_bytes(read_array(reader)?), writer)?
+        }
+        ExprToken::UnsignedIntLiteral16 => {
+            write_literal(name, u16::from_be_bytes(read_array(reader)?), writer)?
+        }
+        ExprToken::UnsignedIntLiteral32 => {
+            write_literal(name, u32::from_be_bytes(read_array(reader)?), writer)?
+        }
+        ExprToken::UnsignedIntLiteral64 => {
+            write_literal(name, u64::from_be_bytes(read_array(reader)?), writer)?
+        }
+        ExprToken::BinaryFloatLiteral32 => {
+            let value = f32::from_be_bytes(read_array(reader)?);
+            if value.is_nan() && value.to_bits() != f32::NAN.to_bits() {
+                write!(writer, "({} {:#010x})", name, value.to_bits())?
+            } else {
+                write!(writer, "({} {:?})", name, value)?
+            }
+        }
+        ExprToken::BinaryFloatLiteral64 => {
+            let value = f64::from_be_bytes(read_array(reader)?);
+            if value.is_nan() && value.to_bits() != f64::NAN.to_bits() {
+                write!(writer, "({} {:#018x})", name, value.to_bits())?
+            } else {
+                write!(writer, "({} {:?})", name, value)?
+            }
+        }
+        ExprToken::Negation
+        | ExprToken::Square
+        | ExprToken::Cube
+        | ExprToken::SquareRoot
+        | ExprToken::CubeRoot
+        | ExprToken::Reciprocal => {
+            write!(writer, "({} ", name)?;
+            pending.push(')');
+            return Ok(true);
+        }
+        ExprToken::Addition
+        | ExprToken::Subtraction
+        | ExprToken::Multiplication
+        | ExprToken::Division
+        | ExprToken::IntDivision
+        | ExprToken::Modulo
+        | ExprToken::Power
+        | ExprToken::Root
+        | ExprToken::IntRoot => {
+            write!(writer, "({} ", name)?;
+            pending.extend([')', ' ']);
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Reads a file in the binary format, starting with the version, and writes it as an [S-expression](super#files).
+pub fn write_sexpr_file<R: ?Sized + Read, W: ?Sized + Write, C: ?Sized + Config>(
+    reader: &mut R,
+    writer: &mut W,
+    configuration: &C,
+) -> Result<(), SexprWriteError> {
+    let version = read_usize(reader, configuration)?;
+    write!(writer, "(file\n  (version {})\n", version)?;
+    match FileContentTypeToken::read_from(reader, configuration)? {
+        FileContentTypeToken::RawFormula => writer.write_str("  (raw-formula\n")?,
+        FileContentTypeToken::SingleFormula => {
+            writer.write_str("  (single-formula\n")?;
+            write_configuration(reader, writer, configuration)?;
+            write_metadata(reader, writer, configuration)?;
+        }
+    }
+    writer.write_str("    (expression ")?;
+    write_sexpr_expression(reader, writer, configuration)?;
+    writer.write_str(")))")?;
+    Ok(())
+}
+
+fn write_literal<T: std::fmt::Display, W: ?Sized + Write>(
+    name: &str,
+    value: T,
+    writer: &mut W,
+) -> std::fmt::Result {
+    write!(writer, "({} {})", name, value)
+}
+
+fn read_usize<R: ?Sized + Read, C: ?Sized + Config>(
+    reader: &mut R,
+    configuration: &C,
+) -> Result<usize, SexprWriteError> {
+    Ok(VariableLengthEnum::read_from(reader, configuration)?.try_into()?)
+}
+
+fn read_array<R: ?Sized + Read, const N: usize>(reader: &mut R) -> std::io::Result<[u8; N]> {
+    let mut buffer = [0u8; N];
+    reader.read_exact(&mut buffer)?;
+    Ok(buffer)
+}
+
+fn read_data<R: ?Sized + Read>(reader: &mut R, length: usize) -> std::io::Result<Vec<u8>> {
+    let mut data = Vec::new();
+    reader.take(length as u64).read_to_end(&mut data)?;
+    if data.len() < length {
+        return Err(ErrorKind::UnexpectedEof.into());
+    }
+    Ok(data)
+}
+
+fn write_data<W: ?Sized + Write>(data: &[u8], writer: &mut W) -> std::fmt::Result {
+    writer.write_str("0x")?;
+    for byte in data {
+        write!(writer, "{:02x}", byte)?;
+    }
+    Ok(())
+}
+
+fn write_string<W: ?Sized + Write>(text: &str, writer: &mut W) -> std::fmt::Result {
+    writer.write_char('"')?;
+    for character in text.chars() {
+        match character {
+            '"' => writer.write_str("\\\"")?,
+            '\\' => writer.write_str("\\\\")?,
+            '\n' => writer.write_str("\\n")?,
+            '\r' => writer.write_str("\\r")?,
+            '\t' => writer.write_str("\\t")?,
+            _ if character.is_control() => write!(writer, "\\u{{{:x}}}", character as u32)?,
+            _ => writer.write_char(character)?,
+        }
+    }
+    writer.write_char('"')
+}
+
+fn write_configuration<R: ?Sized + Read, W: ?Sized + Write, C: ?Sized + Config>(
+    reader: &mut R,
+    writer: &mut W,
+    configuration: &C,
+) -> Result<(), SexprWriteError> {
+    let count = read_usize(reader, configuration)?;
+    writer.write_str("    (configuration")?;
+    for _ in 0..count {
+        let identifier = read_usize(reader, configuration)?;
+        write!(writer, "\n      (entry {} ", identifier)?;
+        if identifier <= MAX_ENUM_CONFIGURATION_IDENTIFIER {
+            write!(writer, "{}", read_usize(reader, configuration)?)?;
+        } else {
+            let length = read_usize(reader, configuration)?;
+            write_data(&read_data(reader, length)?, writer)?;
+        }
+        writer.write_char(')')?;
+    }
+    writer.write_str(")\n")?;
+    Ok(())
+}
+
+fn write_metadata<R: ?Sized + Read, W: ?Sized + Write, C: ?Sized + Config>(
+    reader: &mut R,
+    writer: &mut W,
+    configuration: &C,
+) -> Result<(), SexprWriteError> {
+    let count = read_usize(reader, configuration)?;
+    writer.write_str("    (metadata")?;
+    if count != 0 {
+        let byte_size = read_usize(reader, configuration)?;
+        let content = read_data(reader, byte_size)?;
+        let mut remaining = content.as_slice();
+        let overflow = |_| SexprWriteError::MetadataOverflow { byte_size };
+        for _ in 0..count {
+            let identifier = read_usize(&mut remaining, configuration).map_err(overflow)?;
+            let length = read_usize(&mut remaining, configuration).map_err(overflow)?;
+            if length > remaining.len() {
+                return Err(SexprWriteError::MetadataOverflow { byte_size });
+            }
+            let (data, rest) = remaining.split_at(length);
+            remaining = rest;
+            writer.write_str("\n      ")?;
+            write_record(identifier, data, writer, configuration)?;
+        }
+        if !remaining.is_empty() {
+            writer.write_str("\n      (padding ")?;
+            write_data(remaining, writer)?;
+            writer.write_char(')')?;
+        }
+    }
+    writer.write_str(")\n")?;
+    Ok(())
+}
+
+/// Writes a metadata record, decoding the data of name and variable name records.
+fn write_record<W: ?Sized + Write, C: ?Sized + Config>(
+    identifier: usize,
+    data: &[u8],
+    writer: &mut W,
+    configuration: &C,
+) -> std::fmt::Result {
+    let mut remaining = data;
+    match MetadataToken::try_from(identifier) {
+        Ok(MetadataToken::Name) => {
+            if let Ok(name) = String::read_from(&mut remaining, configuration) {
+                if remaining.is_empty() {
+                    writer.write_str("(name ")?;
+                    write_string(&name, writer)?;
+                    return writer.write_char(')');
+                }
+            }
+        }
+        Ok(MetadataToken::VariableName) => {
+            if let (Ok(variable), Ok(name)) = (
+                read_usize(&mut remaining, configuration),
+                String::read_from(&mut remaining, configuration),
+            ) {
+                if remaining.is_empty() {
+                    write!(writer, "(variable-name {} ", variable)?;
+                    write_string(&name, writer)?;
+                    return writer.write_char(')');
+                }
+            }
+        }
+        _ => {}
+    }
+    write!(writer, "(record {} ", identifier)?;
+    write_data(data, writer)?;
+    writer.write_char(')')
+}
@@ -0,0 +1,231 @@
+//! Reader of the generic S-expression syntax.
+//!
+//! Reads lists in parentheses, atoms and strings in double quotes. Whitespace separates items and a `;` starts a comment
+//! that continues to the end of the line.
+
+use std::{iter::Peekable, str::Chars};
+
+use crate::v0::text::{error::SexprParseError, Position, Span};
+
+/// Item of an S-expression.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) enum Sexpr {
+    List { items: Vec<Sexpr>, span: Span },
+    Atom { text: String, span: Span },
+    String { text: String, span: Span },
+}
+
+impl Sexpr {
+    pub(super) fn span(&self) -> Span {
+        match self {
+            Sexpr::List { span, .. } | Sexpr::Atom { span, .. } | Sexpr::String { span, .. } => {
+                *span
+            }
+        }
+    }
+
+    /// Describes the item for error messages.
+    pub(super) fn describe(&self) -> String {
+        match self {
+            Sexpr::List { items, .. } => match items.first() {
+                Some(Sexpr::Atom { text, .. }) => format!("({} …)", text),
+                Some(_) => "a list".to_string(),
+                None => "()".to_string(),
+            },
+            Sexpr::Atom { text, .. } => text.clone(),
+            Sexpr::String { text, .. } => format!("{:?}", text),
+        }
+    }
+}
+
+/// Drops the item without recursion, so that deeply nested lists cannot overflow the stack.
+///
+/// Items of nested lists are moved to a heap stack, so every list dropped by the compiler generated drop glue is empty.
+impl Drop for Sexpr {
+    fn drop(&mut self) {
+        let Sexpr::List { items, .. } = self else {
+            return;
+        };
+        let mut stack = std::mem::take(items);
+        while let Some(mut item) = stack.pop() {
+            if let Sexpr::List { items, .. } = &mut item {
+                stack.append(items);
+            }
+        }
+    }
+}
+
+fn is_atom_character(character: char) -> bool {
+    !character.is_whitespace() && !matches!(character, '(' | ')' | '"' | ';')
+}
+
+struct Reader<'a> {
+    chars: Peekable<Chars<'a>>,
+    position: Position,
+}
+
+/// List whose closing parenthesis wasn't read yet.
+struct OpenList {
+    items: Vec<Sexpr>,
+    start: Position,
+}
+
+/// Reads a text consisting of exactly one S-expression.
+pub(super) fn read_sexpr(text: &str) -> Result<Sexpr, SexprParseError> {
+    Reader {
+        chars: text.chars().peekable(),
+        position: Position::START,
+    }
+    .document()
+}
+
+impl Reader<'_> {
+    fn bump(&mut self) -> Option<char> {
+        let character = self.chars.next()?;
+        self.position = self.position.advance(character);
+        Some(character)
+    }
+
+    fn unexpected_end(&self, expected: &str) -> SexprParseError {
+        SexprParseError::UnexpectedToken {
+            expected: expected.to_string(),
+            found: "end of input".to_string(),
+            span: Span::new(self.position, self.position),
+        }
+    }
+
+    fn skip_whitespace_and_comments(&mut self) {
+        while let Some(character) = self.chars.peek().copied() {
+            if character == ';' {
+                while self.chars.peek().is_some_and(|&c| c != '\n') {
+                    self.bump();
+                }
+            } else if character.is_whitespace() {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+    }
+
+    fn atom(&mut self) -> Sexpr {
+        let start = self.position;
+        let mut text = String::new();
+        while let Some(character) = self.chars.peek().copied() {
+            if !is_atom_character(character) {
+                break;
+            }
+            text.push(character);
+            self.bump();
+        }
+        Sexpr::Atom {
+            text,
+            span: Span::new(start, self.position),
+        }
+    }
+
+    /// Reads an escape sequence after the `\`.
+    fn escape(&mut self, start: Position) -> Result<char, SexprParseError> {
+        let invalid = |reader: &Self, character| SexprParseError::UnexpectedCharacter {
+            character,
+            span: Span::new(start, reader.position),
+        };
+        match self.bump() {
+            Some('"') => Ok('"'),
+            Some('\\') => Ok('\\'),
+            Some('n') => Ok('\n'),
+            Some('r') => Ok('\r'),
+            Some('t') => Ok('\t'),
+            Some('u') => {
+                match self.bump() {
+                    Some('{') => {}
+                    Some(character) => return Err(invalid(self, character)),
+                    None => return Err(self.unexpected_end("an escape sequence")),
+                }
+                let mut code = String::new();
+                loop {
+                    match self.bump() {
+                        Some('}') => break,
+                        Some(character) if character.is_ascii_hexdigit() => code.push(character),
+                        Some(character) => return Err(invalid(self, character)),
+                        None => return Err(self.unexpected_end("an escape sequence")),
+                    }
+                }
+                u32::from_str_radix(&code, 16)
+                    .ok()
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| invalid(self, '}'))
+            }
+            Some(character) => Err(invalid(self, character)),
+            None => Err(self.unexpected_end("an escape sequence")),
+        }
+    }
+
+    /// Reads a string after the opening `"`.
+    fn string(&mut self, start: Position) -> Result<Sexpr, SexprParseError> {
+        let mut text = String::new();
+        loop {
+            let escape_start = self.position;
+            match self.bump() {
+                Some('"') => break,
+                Some('\\') => text.push(self.escape(escape_start)?),
+                Some(character) => text.push(character),
+                None => return Err(self.unexpected_end("'\"'")),
+            }
+        }
+        Ok(Sexpr::String {
+            text,
+            span: Span::new(start, self.position),
+        })
+    }
+
+    fn document(mut self) -> Result<Sexpr, SexprParseError> {
+        let mut open: Vec<OpenList> = Vec::new();
+        loop {
+            self.skip_whitespace_and_comments();
+            let start = self.position;
+            let item = match self.chars.peek().copied() {
+                None if open.is_empty() => return Err(self.unexpected_end("an S-expression")),
+                None => return Err(self.unexpected_end("')'")),
+                Some('(') => {
+                    self.bump();
+                    open.push(OpenList {
+                        items: Vec::new(),
+                        start,
+                    });
+                    continue;
+                }
+                Some(')') => {
+                    self.bump();
+                    let list = open.pop().ok_or(SexprParseError::UnexpectedCharacter {
+                        character: ')',
+                        span: Span::new(start, self.position),
+                    })?;
+                    Sexpr::List {
+                        items: list.items,
+                        span: Span::new(list.start, self.position),
+                    }
+                }
+                Some('"') => {
+                    self.bump();
+                    self.string(start)?
+                }
+                Some(_) => self.atom(),
+            };
+            match open.last_mut() {
+                Some(parent) => parent.items.push(item),
+                None => {
+                    self.skip_whitespace_and_comments();
+                    let start = self.position;
+                    return match self.bump() {
+                        None => Ok(item),
+                        Some(character) => Err(SexprParseError::UnexpectedCharacter {
+                            character,
+                            span: Span::new(start, self.position),
+                        }),
+                    };
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+/// Position of a character in a text.
+///
+/// Both the line and the column start at `1`. Columns are counted in characters, not bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Position {
+    /// Position of the first character of a text.
+    pub const START: Position = Position { line: 1, column: 1 };
+
+    /// Returns the position of the character following the given character.
+    pub(crate) fn advance(self, character: char) -> Position {
+        if character == '\n' {
+            Position {
+                line: self.line + 1,
+                column: 1,
+            }
+        } else {
+            Position {
+                line: self.line,
+                column: self.column + 1,
+            }
+        }
+    }
+}
+
+/// Formats the position as `line:column`.
+impl std::fmt::Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Part of a text between two positions.
+///
+/// The `start` is the position of the first character of the span and the `end` is the position right after the last character,
+/// so an empty span (e.g. the end of the text) has the same start and end.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Span {
+    /// Creates a span between the two positions.
+    pub fn new(start: Position, end: Position) -> Self {
+        Self { start, end }
+    }
+
+    /// Returns the smallest span containing both spans.
+    pub fn join(self, other: Span) -> Span {
+        Span {
+            start: self.start.min(other.start),
+            end: self.end.max(other.end),
+        }
+    }
+}
+
+/// Formats the span as `line:column-line:column`.
+impl std::fmt::Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::v0::{
+    metadata::{MetadataRecord, VariableNameMetadataRecordObj},
+    raw::VariableLengthEnum,
+};
+
+/// Two-way mapping between names of variables and their identifiers.
+///
+/// Text formats refer to variables by their names, while expressions identify [variables](crate::v0::expr::ExprVariable) by a [`VariableLengthEnum`].
+/// Parsers assign identifiers to new names, printers look up the names of identifiers. The names can be taken from and stored into
+/// [variable name metadata records](VariableNameMetadataRecordObj).
+///
+/// # Examples
+/// ```rust
+/// # use fef::v0::text::VariableNames;
+/// # use fef::v0::raw::VariableLengthEnum;
+/// let mut names = VariableNames::new();
+/// names.insert("x".to_string(), 1.into());
+///
+/// assert_eq!(names.get_or_insert("x"), VariableLengthEnum::from(1));
+/// assert_eq!(names.get_or_insert("y"), VariableLengthEnum::from(0));
+/// assert_eq!(names.name(&VariableLengthEnum::from(0)), Some("y"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct VariableNames {
+    identifiers: HashMap<String, VariableLengthEnum>,
+    names: BTreeMap<VariableLengthEnum, String>,
+}
+
+impl VariableNames {
+    /// Creates an empty mapping.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps the name to the identifier, replacing any previous mapping of either of them.
+    pub fn insert(&mut self, name: String, identifier: VariableLengthEnum) {
+        if let Some(old_identifier) = self.identifiers.remove(&name) {
+            self.names.remove(&old_identifier);
+        }
+        if let Some(old_name) = self.names.remove(&identifier) {
+            self.identifiers.remove(&old_name);
+        }
+        self.identifiers.insert(name.clone(), identifier.clone());
+        self.names.insert(identifier, name);
+    }
+
+    /// Returns the identifier of the variable with the given name.
+    pub fn identifier(&self, name: &str) -> Option<&VariableLengthEnum> {
+        self.identifiers.get(name)
+    }
+
+    /// Returns the name of the variable with the given identifier.
+    pub fn name(&self, identifier: &VariableLengthEnum) -> Option<&str> {
+        self.names.get(identifier).map(String::as_str)
+    }
+
+    /// Returns the identifier of the variable with the given name.
+    ///
+    /// If the name is not mapped yet, it is mapped to the smallest identifier not used by any other variable.
+    pub fn get_or_insert(&mut self, name: &str) -> VariableLengthEnum {
+        if let Some(identifier) = self.identifiers.get(name) {
+            return identifier.clone();
+        }
+        let identifier = (0..)
+            .map(VariableLengthEnum::from)
+            .find(|identifier| !self.names.contains_key(identifier))
+            .expect("there is always an unused identifier");
+        self.insert(name.to_string(), identifier.clone());
+        identifier
+    }
+
+    /// Returns an iterator over the identifiers and names of all variables, ordered by the identifiers.
+    pub fn iter(&self) -> impl Iterator<Item = (&VariableLengthEnum, &str)> {
+        self.names
+            .iter()
+            .map(|(identifier, name)| (identifier, name.as_str()))
+    }
+
+    /// Returns the number of mapped variables.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Returns `true` if no variables are mapped.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Collects the names from all [variable name](MetadataRecord::VariableName) records of the metadata.
+    pub fn from_metadata<'a>(metadata: impl IntoIterator<Item = &'a MetadataRecord>) -> Self {
+        metadata
+            .into_iter()
+            .filter_map(|record| match record {
+                MetadataRecord::VariableName(record) => Some(record.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Converts the mapping into variable name metadata records, ordered by the identifiers.
+    pub fn to_metadata_records(&self) -> Vec<VariableNameMetadataRecordObj> {
+        self.iter()
+            .map(|(identifier, name)| {
+                VariableNameMetadataRecordObj::new(name.to_string(), identifier.clone())
+            })
+            .collect()
+    }
+}
+
+/// Collects the mapping from variable name metadata records.
+impl FromIterator<VariableNameMetadataRecordObj> for VariableNames {
+    fn from_iter<T: IntoIterator<Item = VariableNameMetadataRecordObj>>(iter: T) -> Self {
+        let mut names = Self::new();
+        for record in iter {
+            names.insert(
+                record.name().to_string(),
+                record.variable_identifier().clone(),
+            );
+        }
+        names
+    }
+}
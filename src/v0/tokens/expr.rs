@@ -39,7 +39,7 @@ pub enum ExprToken {
     UnsignedIntLiteral8 = 0x38,
     UnsignedIntLiteral16 = 0x39,
     UnsignedIntLiteral32 = 0x3B,
-    UnsignedIntLiteral64 = 0x3CF,
+    UnsignedIntLiteral64 = 0x3C,
     BinaryFloatLiteral32 = 0x42,
     BinaryFloatLiteral64 = 0x43,
 }
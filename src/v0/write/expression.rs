@@ -3,9 +3,10 @@ use std::io::Write;
 use crate::v0::{
     config::Config,
     expr::{
-        error::ExprWriteWithDecomposerError,
+        arena::{error::ArenaDecomposeError, ExprArena, NodeId},
+        error::{DecomposeError, ExprWriteWithDecomposerError},
         traits::{Decomposer, DecompositionRefContainer, TryWriteToWithDecomposer},
-        ExprTree,
+        write_expr, ExprTree,
     },
 };
 
@@ -15,6 +16,8 @@ use crate::v0::{
 /// into their respective parts. This is useful when writing expressions that are not in memory trees, but are
 /// instead stored in a different format. Most of the time, you will want to use the [`write_expression_tree`] function,
 /// which writes an [`ExprTree`] to a byte stream.
+///
+/// The expression is written without recursion, so deeply nested expressions cannot overflow the stack.
 pub fn write_expression<
     W: ?Sized + Write,
     S: Sized,
@@ -57,22 +60,11 @@ impl Decomposer<ExprTree> for ExprTreeDecomposer {
 /// # use fef::v0::write::write_expression_tree;
 /// # use fef::v0::config::DEFAULT_CONFIG;
 /// # use fef::v0::expr::ExprTree;
-/// # use fef::v0::expr::Expr;
-/// # use fef::v0::expr::ExprVariable;
-/// # use fef::v0::expr::ExprSquare;
-/// # use fef::v0::expr::ExprAddition;
-/// # use fef::v0::expr::ExprSquareRoot;
-/// # use fef::v0::raw::VariableLengthEnum;
 /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
-/// let a: ExprTree = Expr::<ExprTree>::Variable(ExprVariable::from(VariableLengthEnum::from(0)).into()).into();
-/// let b: ExprTree = Expr::<ExprTree>::Variable(ExprVariable::from(VariableLengthEnum::from(1)).into()).into();
-///
-/// let a_squared: ExprTree = Expr::<ExprTree>::Square(ExprSquare::from(a).into()).into();
-/// let b_squared: ExprTree = Expr::<ExprTree>::Square(ExprSquare::from(b).into()).into();
-///
-/// let c_squared: ExprTree = Expr::<ExprTree>::Addition(ExprAddition::from((a_squared, b_squared)).into()).into();
-/// let c: ExprTree = Expr::<ExprTree>::SquareRoot(ExprSquareRoot::from((c_squared)).into()).into();
+/// let a = ExprTree::var(0);
+/// let b = ExprTree::var(1);
 ///
+/// let c = (a.square() + b.square()).sqrt();
 ///
 /// let mut writer = Vec::new();
 /// write_expression_tree(&mut writer, &c, &DEFAULT_CONFIG)?;
@@ -97,3 +89,45 @@ pub fn write_expression_tree<W: ?Sized + Write, C: ?Sized + Config>(
     let mut decomposer = ExprTreeDecomposer {};
     write_expression(byte_stream, tree, config, &mut decomposer)
 }
+
+/// Writes a node of an [`ExprArena`] to a byte stream.
+///
+/// This function replaces [`write_expression`] for arenas, as a [`Decomposer`] cannot return references to expressions
+/// stored outside of the storage type. Nodes used as an operand more than once are written for every use.
+///
+/// # Example
+/// ```rust
+/// # use fef::v0::write::write_expression_from_arena;
+/// # use fef::v0::config::DEFAULT_CONFIG;
+/// # use fef::v0::expr::{arena::ExprArena, Expr, ExprNegation, ExprVariable};
+/// # use fef::v0::raw::VariableLengthEnum;
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut arena = ExprArena::new();
+/// let a = arena.push(Expr::Variable(ExprVariable::from(VariableLengthEnum::from(0))));
+/// let negation = arena.push(Expr::Negation(ExprNegation::from(a)));
+///
+/// let mut writer = Vec::new();
+/// write_expression_from_arena(&mut writer, &arena, negation, &DEFAULT_CONFIG)?;
+///
+/// assert_eq!(writer, vec![0x17, 0x04, 0x00]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn write_expression_from_arena<W: ?Sized + Write, C: ?Sized + Config>(
+    byte_stream: &mut W,
+    arena: &ExprArena,
+    node: NodeId,
+    config: &C,
+) -> Result<(), ExprWriteWithDecomposerError<ArenaDecomposeError>> {
+    let lookup = |node: &NodeId| {
+        arena.get(*node).ok_or(DecomposeError::CustomError(
+            ArenaDecomposeError::UnknownNode {
+                node: node.index(),
+                len: arena.len(),
+            },
+        ))
+    };
+    write_expr(lookup(&node)?, byte_stream, config, |operand| {
+        Ok(lookup(operand)?)
+    })
+}
@@ -8,7 +8,8 @@ mod expression;
 mod file;
 mod metadata;
 
-pub use expression::{write_expression, write_expression_tree};
+pub(crate) use expression::ExprTreeDecomposer;
+pub use expression::{write_expression, write_expression_from_arena, write_expression_tree};
 
 pub use configuration::write_configuration;
 